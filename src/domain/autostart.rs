@@ -0,0 +1,103 @@
+use crate::domain::desktop_entry::DesktopEntry;
+
+pub const ENABLED_KEY: &str = "X-GNOME-Autostart-enabled";
+pub const DELAY_KEY: &str = "X-GNOME-Autostart-Delay";
+
+const MAIN_GROUP: &str = "Desktop Entry";
+
+/// How a login item behaves, as read from its desktop entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutostartInfo {
+    /// Not `Hidden` and not switched off with `X-GNOME-Autostart-enabled`.
+    pub enabled: bool,
+    /// Seconds to wait after login.
+    pub delay: Option<u32>,
+    /// Whether `OnlyShowIn`/`NotShowIn` let it start in the current desktop.
+    pub runs_here: bool,
+}
+
+pub fn autostart_info(entry: &DesktopEntry, current_desktops: &[String]) -> AutostartInfo {
+    AutostartInfo {
+        enabled: !entry.hidden && extra(entry, ENABLED_KEY) != Some("false"),
+        delay: extra(entry, DELAY_KEY).and_then(|v| v.trim().parse().ok()),
+        runs_here: shown_in(entry, current_desktops),
+    }
+}
+
+/// The spec's `OnlyShowIn`/`NotShowIn` rule against `$XDG_CURRENT_DESKTOP`,
+/// which may list several desktops.
+pub fn shown_in(entry: &DesktopEntry, current_desktops: &[String]) -> bool {
+    let matches = |list: &[String]| {
+        list.iter()
+            .any(|d| current_desktops.iter().any(|c| c.eq_ignore_ascii_case(d)))
+    };
+    if !entry.only_show_in.is_empty() {
+        return matches(&entry.only_show_in);
+    }
+    !matches(&entry.not_show_in)
+}
+
+/// Switches a login item on or off. `Hidden` is what the autostart spec
+/// honours everywhere; the GNOME key is kept in step for GNOME's settings.
+pub fn with_enabled(entry: &DesktopEntry, enabled: bool) -> DesktopEntry {
+    let hidden = (!enabled).then_some("true");
+    entry.with_value(MAIN_GROUP, "Hidden", hidden).with_value(
+        MAIN_GROUP,
+        ENABLED_KEY,
+        Some(if enabled { "true" } else { "false" }),
+    )
+}
+
+pub fn with_delay(entry: &DesktopEntry, seconds: Option<u32>) -> DesktopEntry {
+    let value = seconds.filter(|s| *s > 0).map(|s| s.to_string());
+    entry.with_value(MAIN_GROUP, DELAY_KEY, value.as_deref())
+}
+
+fn extra<'a>(entry: &'a DesktopEntry, key: &str) -> Option<&'a str> {
+    entry
+        .extra
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{autostart_info, with_delay, with_enabled};
+    use crate::domain::desktop_entry::DesktopEntry;
+
+    const ITEM: &str = "[Desktop Entry]\nType=Application\nName=Sync\nExec=sync-daemon\nOnlyShowIn=GNOME;XFCE;\nX-GNOME-Autostart-Delay=10\n";
+
+    fn desktops(list: &[&str]) -> Vec<String> {
+        list.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_autostart_keys() {
+        let entry = DesktopEntry::from_ini_string(ITEM);
+        let info = autostart_info(&entry, &desktops(&["ubuntu", "GNOME"]));
+        assert!(info.enabled);
+        assert_eq!(info.delay, Some(10));
+        assert!(info.runs_here);
+        assert!(!autostart_info(&entry, &desktops(&["KDE"])).runs_here);
+        let off =
+            DesktopEntry::from_ini_string(&format!("{}X-GNOME-Autostart-enabled=false\n", ITEM));
+        assert!(!autostart_info(&off, &[]).enabled);
+    }
+
+    #[test]
+    fn toggles_and_delays() {
+        let entry = DesktopEntry::from_ini_string(ITEM);
+        let off = with_enabled(&entry, false);
+        assert!(off.hidden);
+        assert!(!autostart_info(&off, &[]).enabled);
+        let on = with_enabled(&off, true);
+        assert!(!on.hidden);
+        assert!(autostart_info(&on, &[]).enabled);
+        assert_eq!(autostart_info(&with_delay(&on, None), &[]).delay, None);
+        assert_eq!(
+            autostart_info(&with_delay(&on, Some(3)), &[]).delay,
+            Some(3)
+        );
+    }
+}
@@ -0,0 +1,844 @@
+use std::fmt;
+
+/// A category registered by the Desktop Menu Specification.
+pub struct Category {
+    pub name: &'static str,
+    pub main: bool,
+    pub description: &'static str,
+    /// Alternative sets of categories that must accompany this one; any one
+    /// complete set satisfies the requirement.
+    related: &'static [&'static [&'static str]],
+}
+
+const fn cat(
+    name: &'static str,
+    main: bool,
+    description: &'static str,
+    related: &'static [&'static [&'static str]],
+) -> Category {
+    Category {
+        name,
+        main,
+        description,
+        related,
+    }
+}
+
+/// Main categories first, then additional categories in spec order.
+pub const CATEGORIES: &[Category] = &[
+    cat(
+        "AudioVideo",
+        true,
+        "Application for presenting, creating, or processing multimedia (audio/video)",
+        &[],
+    ),
+    cat("Audio", true, "An audio application", &[&["AudioVideo"]]),
+    cat("Video", true, "A video application", &[&["AudioVideo"]]),
+    cat("Development", true, "An application for development", &[]),
+    cat("Education", true, "Educational software", &[]),
+    cat("Game", true, "A game", &[]),
+    cat(
+        "Graphics",
+        true,
+        "Application for viewing, creating, or processing graphics",
+        &[],
+    ),
+    cat(
+        "Network",
+        true,
+        "Network application such as a web browser",
+        &[],
+    ),
+    cat("Office", true, "An office type application", &[]),
+    cat("Science", true, "Scientific software", &[]),
+    cat("Settings", true, "Settings applications", &[]),
+    cat(
+        "System",
+        true,
+        "System application, \"System Tools\" such as say a log viewer or network monitor",
+        &[],
+    ),
+    cat(
+        "Utility",
+        true,
+        "Small utility application, \"Accessories\"",
+        &[],
+    ),
+    cat(
+        "Building",
+        false,
+        "A tool to build applications",
+        &[&["Development"]],
+    ),
+    cat(
+        "Debugger",
+        false,
+        "A tool to debug applications",
+        &[&["Development"]],
+    ),
+    cat("IDE", false, "IDE application", &[&["Development"]]),
+    cat(
+        "GUIDesigner",
+        false,
+        "A GUI designer application",
+        &[&["Development"]],
+    ),
+    cat("Profiling", false, "A profiling tool", &[&["Development"]]),
+    cat(
+        "RevisionControl",
+        false,
+        "Applications like cvs or subversion",
+        &[&["Development"]],
+    ),
+    cat(
+        "Translation",
+        false,
+        "A translation tool",
+        &[&["Development"]],
+    ),
+    cat("Calendar", false, "Calendar application", &[&["Office"]]),
+    cat(
+        "ContactManagement",
+        false,
+        "E.g. an address book",
+        &[&["Office"]],
+    ),
+    cat(
+        "Database",
+        false,
+        "Application to manage a database",
+        &[&["Office"], &["Development"], &["AudioVideo"]],
+    ),
+    cat(
+        "Dictionary",
+        false,
+        "A dictionary",
+        &[&["Office"], &["TextTools"]],
+    ),
+    cat("Chart", false, "Chart application", &[&["Office"]]),
+    cat(
+        "Email",
+        false,
+        "Email application",
+        &[&["Office"], &["Network"]],
+    ),
+    cat(
+        "Finance",
+        false,
+        "Application to manage your finance",
+        &[&["Office"]],
+    ),
+    cat(
+        "FlowChart",
+        false,
+        "A flowchart application",
+        &[&["Office"]],
+    ),
+    cat("PDA", false, "Tool to manage your PDA", &[&["Office"]]),
+    cat(
+        "ProjectManagement",
+        false,
+        "Project management application",
+        &[&["Office"], &["Development"]],
+    ),
+    cat(
+        "Presentation",
+        false,
+        "Presentation software",
+        &[&["Office"]],
+    ),
+    cat("Spreadsheet", false, "A spreadsheet", &[&["Office"]]),
+    cat("WordProcessor", false, "A word processor", &[&["Office"]]),
+    cat(
+        "2DGraphics",
+        false,
+        "2D based graphical application",
+        &[&["Graphics"]],
+    ),
+    cat(
+        "VectorGraphics",
+        false,
+        "Application for viewing, creating, or processing vector graphics",
+        &[&["Graphics", "2DGraphics"]],
+    ),
+    cat(
+        "RasterGraphics",
+        false,
+        "Application for viewing, creating, or processing raster (bitmap) graphics",
+        &[&["Graphics", "2DGraphics"]],
+    ),
+    cat(
+        "3DGraphics",
+        false,
+        "Application for viewing, creating, or processing 3-D graphics",
+        &[&["Graphics"]],
+    ),
+    cat(
+        "Scanning",
+        false,
+        "Tool to scan a file/text",
+        &[&["Graphics"]],
+    ),
+    cat(
+        "OCR",
+        false,
+        "Optical character recognition application",
+        &[&["Graphics", "Scanning"]],
+    ),
+    cat(
+        "Photography",
+        false,
+        "Camera tools, etc.",
+        &[&["Graphics"], &["Office"]],
+    ),
+    cat(
+        "Publishing",
+        false,
+        "Desktop Publishing applications and Color Management tools",
+        &[&["Graphics"], &["Office"]],
+    ),
+    cat(
+        "Viewer",
+        false,
+        "Tool to view e.g. a graphic or pdf file",
+        &[&["Graphics"], &["Office"]],
+    ),
+    cat("TextTools", false, "A text tool utility", &[&["Utility"]]),
+    cat(
+        "DesktopSettings",
+        false,
+        "Configuration tool for the GUI",
+        &[&["Settings"]],
+    ),
+    cat(
+        "HardwareSettings",
+        false,
+        "A tool to manage hardware components, like sound cards, video cards or printers",
+        &[&["Settings"]],
+    ),
+    cat(
+        "Printing",
+        false,
+        "A tool to manage printers",
+        &[&["HardwareSettings", "Settings"]],
+    ),
+    cat(
+        "PackageManager",
+        false,
+        "A package manager application",
+        &[&["Settings"]],
+    ),
+    cat("Dialup", false, "A dial-up program", &[&["Network"]]),
+    cat(
+        "InstantMessaging",
+        false,
+        "An instant messaging client",
+        &[&["Network"]],
+    ),
+    cat("Chat", false, "A chat client", &[&["Network"]]),
+    cat("IRCClient", false, "An IRC client", &[&["Network"]]),
+    cat(
+        "Feed",
+        false,
+        "RSS, podcast and other subscription based contents",
+        &[&["Network"]],
+    ),
+    cat(
+        "FileTransfer",
+        false,
+        "Tools like FTP or P2P programs",
+        &[&["Network"]],
+    ),
+    cat(
+        "HamRadio",
+        false,
+        "HAM radio software",
+        &[&["Network"], &["Audio"]],
+    ),
+    cat(
+        "News",
+        false,
+        "A news reader or a news ticker",
+        &[&["Network"]],
+    ),
+    cat("P2P", false, "A P2P program", &[&["Network"]]),
+    cat(
+        "RemoteAccess",
+        false,
+        "A tool to remotely manage your PC",
+        &[&["Network"]],
+    ),
+    cat("Telephony", false, "Telephony via PC", &[&["Network"]]),
+    cat(
+        "TelephonyTools",
+        false,
+        "Telephony tools, to dial a number, manage PBX, ...",
+        &[&["Utility"]],
+    ),
+    cat(
+        "VideoConference",
+        false,
+        "Video Conference software",
+        &[&["Network"]],
+    ),
+    cat("WebBrowser", false, "A web browser", &[&["Network"]]),
+    cat(
+        "WebDevelopment",
+        false,
+        "A tool for web developers",
+        &[&["Network"], &["Development"]],
+    ),
+    cat(
+        "Midi",
+        false,
+        "An app related to MIDI",
+        &[&["AudioVideo", "Audio"]],
+    ),
+    cat("Mixer", false, "Just a mixer", &[&["AudioVideo", "Audio"]]),
+    cat(
+        "Sequencer",
+        false,
+        "A sequencer",
+        &[&["AudioVideo", "Audio"]],
+    ),
+    cat("Tuner", false, "A tuner", &[&["AudioVideo", "Audio"]]),
+    cat("TV", false, "A TV application", &[&["AudioVideo", "Video"]]),
+    cat(
+        "AudioVideoEditing",
+        false,
+        "Application to edit audio/video files",
+        &[&["Audio"], &["Video"], &["AudioVideo"]],
+    ),
+    cat(
+        "Player",
+        false,
+        "Application to play audio/video files",
+        &[&["Audio"], &["Video"], &["AudioVideo"]],
+    ),
+    cat(
+        "Recorder",
+        false,
+        "Application to record audio/video files",
+        &[&["Audio"], &["Video"], &["AudioVideo"]],
+    ),
+    cat(
+        "DiscBurning",
+        false,
+        "Application to burn a disc",
+        &[&["AudioVideo"]],
+    ),
+    cat("ActionGame", false, "An action game", &[&["Game"]]),
+    cat("AdventureGame", false, "Adventure style game", &[&["Game"]]),
+    cat("ArcadeGame", false, "Arcade style game", &[&["Game"]]),
+    cat("BoardGame", false, "A board game", &[&["Game"]]),
+    cat("BlocksGame", false, "Falling blocks game", &[&["Game"]]),
+    cat("CardGame", false, "A card game", &[&["Game"]]),
+    cat("KidsGame", false, "A game for kids", &[&["Game"]]),
+    cat(
+        "LogicGame",
+        false,
+        "Logic games like puzzles, etc",
+        &[&["Game"]],
+    ),
+    cat("RolePlaying", false, "A role playing game", &[&["Game"]]),
+    cat("Shooter", false, "A shooter game", &[&["Game"]]),
+    cat("Simulation", false, "A simulation game", &[&["Game"]]),
+    cat("SportsGame", false, "A sports game", &[&["Game"]]),
+    cat("StrategyGame", false, "A strategy game", &[&["Game"]]),
+    cat(
+        "Art",
+        false,
+        "Software to teach arts",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Construction",
+        false,
+        "Construction software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Music",
+        false,
+        "Musical software",
+        &[&["AudioVideo"], &["Education"]],
+    ),
+    cat(
+        "Languages",
+        false,
+        "Software to learn foreign languages",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "ArtificialIntelligence",
+        false,
+        "Artificial Intelligence software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Astronomy",
+        false,
+        "Astronomy software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Biology",
+        false,
+        "Biology software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Chemistry",
+        false,
+        "Chemistry software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "ComputerScience",
+        false,
+        "ComputerScience software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "DataVisualization",
+        false,
+        "Data visualization software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Economy",
+        false,
+        "Economy software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Electricity",
+        false,
+        "Electricity software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Geography",
+        false,
+        "Geography software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Geology",
+        false,
+        "Geology software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Geoscience",
+        false,
+        "Geoscience software, GIS",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "History",
+        false,
+        "History software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Humanities",
+        false,
+        "Software for philosophy, psychology and other humanities",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "ImageProcessing",
+        false,
+        "Image Processing software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Literature",
+        false,
+        "Literature software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Maps",
+        false,
+        "Software for viewing maps, navigation, mapping, GPS",
+        &[&["Education"], &["Science"], &["Utility"]],
+    ),
+    cat(
+        "Math",
+        false,
+        "Math software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "NumericalAnalysis",
+        false,
+        "Numerical analysis software",
+        &[&["Education", "Math"], &["Science", "Math"]],
+    ),
+    cat(
+        "MedicalSoftware",
+        false,
+        "Medical software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Physics",
+        false,
+        "Physics software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Robotics",
+        false,
+        "Robotics software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "Spirituality",
+        false,
+        "Religious and spiritual software, theology",
+        &[&["Education"], &["Science"], &["Utility"]],
+    ),
+    cat(
+        "Sports",
+        false,
+        "Sports software",
+        &[&["Education"], &["Science"]],
+    ),
+    cat(
+        "ParallelComputing",
+        false,
+        "Parallel computing software",
+        &[
+            &["Education", "ComputerScience"],
+            &["Science", "ComputerScience"],
+        ],
+    ),
+    cat("Amusement", false, "A simple amusement", &[]),
+    cat(
+        "Archiving",
+        false,
+        "A tool to archive/backup data",
+        &[&["Utility"]],
+    ),
+    cat(
+        "Compression",
+        false,
+        "A tool to manage compressed data/archives",
+        &[&["Utility"]],
+    ),
+    cat(
+        "Electronics",
+        false,
+        "Electronics software, e.g. a circuit designer",
+        &[],
+    ),
+    cat(
+        "Emulator",
+        false,
+        "Emulator of another platform, such as a DOS emulator",
+        &[&["System"], &["Game"]],
+    ),
+    cat(
+        "Engineering",
+        false,
+        "Engineering software, e.g. CAD programs",
+        &[],
+    ),
+    cat(
+        "FileTools",
+        false,
+        "A file tool utility",
+        &[&["Utility"], &["System"]],
+    ),
+    cat(
+        "FileManager",
+        false,
+        "A file manager",
+        &[&["System", "FileTools"]],
+    ),
+    cat(
+        "TerminalEmulator",
+        false,
+        "A terminal emulator application",
+        &[&["System"]],
+    ),
+    cat("Filesystem", false, "A file system tool", &[&["System"]]),
+    cat(
+        "Monitor",
+        false,
+        "Monitor application/applet that monitors some resource or activity",
+        &[&["System"], &["Network"]],
+    ),
+    cat(
+        "Security",
+        false,
+        "A security tool",
+        &[&["Settings"], &["System"]],
+    ),
+    cat(
+        "Accessibility",
+        false,
+        "Accessibility",
+        &[&["Settings"], &["Utility"]],
+    ),
+    cat("Calculator", false, "A calculator", &[&["Utility"]]),
+    cat(
+        "Clock",
+        false,
+        "A clock application/applet",
+        &[&["Utility"]],
+    ),
+    cat("TextEditor", false, "A text editor", &[&["Utility"]]),
+    cat("Documentation", false, "Help or documentation", &[]),
+    cat(
+        "Adult",
+        false,
+        "Application handles adult or explicit material",
+        &[],
+    ),
+    cat(
+        "Core",
+        false,
+        "Important application, core to correct functioning of the desktop environment",
+        &[],
+    ),
+    cat(
+        "KDE",
+        false,
+        "Application based on KDE libraries",
+        &[&["Qt"]],
+    ),
+    cat(
+        "GNOME",
+        false,
+        "Application based on GNOME libraries",
+        &[&["GTK"]],
+    ),
+    cat(
+        "XFCE",
+        false,
+        "Application based on XFCE libraries",
+        &[&["GTK"]],
+    ),
+    cat(
+        "DDE",
+        false,
+        "Application based on DDE libraries",
+        &[&["Qt"]],
+    ),
+    cat("GTK", false, "Application based on GTK+ libraries", &[]),
+    cat("Qt", false, "Application based on Qt libraries", &[]),
+    cat("Motif", false, "Application based on Motif libraries", &[]),
+    cat(
+        "Java",
+        false,
+        "Application based on Java GUI libraries, such as AWT or Swing",
+        &[],
+    ),
+    cat(
+        "ConsoleOnly",
+        false,
+        "Application that only works inside a terminal (text-based or command line application)",
+        &[],
+    ),
+];
+
+/// Reserved categories only make sense for a specific desktop and therefore
+/// require `OnlyShowIn`.
+pub const RESERVED: &[&str] = &["Screensaver", "TrayIcon", "Applet", "Shell"];
+
+pub fn find(name: &str) -> Option<&'static Category> {
+    CATEGORIES.iter().find(|c| c.name == name)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CategoryIssue {
+    Unknown {
+        name: String,
+        suggestion: Option<&'static str>,
+    },
+    MissingRelated {
+        name: String,
+        related: &'static [&'static [&'static str]],
+    },
+    Reserved(String),
+    NoMainCategory,
+}
+
+impl fmt::Display for CategoryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CategoryIssue::Unknown {
+                name,
+                suggestion: Some(s),
+            } => write!(f, "unknown category \"{}\", did you mean \"{}\"?", name, s),
+            CategoryIssue::Unknown { name, .. } => write!(
+                f,
+                "\"{}\" is not a registered category, extensions must start with X-",
+                name
+            ),
+            CategoryIssue::MissingRelated { name, related } => {
+                let sets: Vec<String> = related.iter().map(|set| set.join(";")).collect();
+                write!(f, "category \"{}\" requires {}", name, sets.join(" or "))
+            }
+            CategoryIssue::Reserved(name) => write!(
+                f,
+                "reserved category \"{}\" requires OnlyShowIn to be set",
+                name
+            ),
+            CategoryIssue::NoMainCategory => {
+                write!(
+                    f,
+                    "no registered main category, menus may file this under Other"
+                )
+            }
+        }
+    }
+}
+
+/// Checks a `Categories` list against the registry. `has_only_show_in` tells
+/// whether reserved categories are allowed.
+pub fn check_categories(categories: &[String], has_only_show_in: bool) -> Vec<CategoryIssue> {
+    let mut issues = Vec::new();
+    for name in categories {
+        if name.starts_with("X-") {
+            continue;
+        }
+        if RESERVED.contains(&name.as_str()) {
+            if !has_only_show_in {
+                issues.push(CategoryIssue::Reserved(name.clone()));
+            }
+            continue;
+        }
+        let Some(category) = find(name) else {
+            let suggestion = CATEGORIES
+                .iter()
+                .map(|c| c.name)
+                .chain(RESERVED.iter().copied())
+                .find(|c| c.eq_ignore_ascii_case(name));
+            issues.push(CategoryIssue::Unknown {
+                name: name.clone(),
+                suggestion,
+            });
+            continue;
+        };
+        let satisfied = category.related.is_empty()
+            || category
+                .related
+                .iter()
+                .any(|set| set.iter().all(|r| categories.iter().any(|c| c == r)));
+        if !satisfied {
+            issues.push(CategoryIssue::MissingRelated {
+                name: name.clone(),
+                related: category.related,
+            });
+        }
+    }
+    if !categories.is_empty() && !categories.iter().any(|c| find(c).is_some_and(|c| c.main)) {
+        issues.push(CategoryIssue::NoMainCategory);
+    }
+    issues
+}
+
+/// Guesses categories for an application from the MIME types it handles.
+/// Falls back to `Utility` so there is always something to offer.
+pub fn suggest_categories(mime_types: &[String]) -> Vec<&'static str> {
+    let has = |prefix: &str| mime_types.iter().any(|m| m.starts_with(prefix));
+    if has("x-scheme-handler/http") || mime_types.iter().any(|m| m == "text/html") {
+        vec!["Network", "WebBrowser"]
+    } else if has("inode/directory") {
+        vec!["System", "FileTools", "FileManager"]
+    } else if has("video/") {
+        vec!["AudioVideo", "Video", "Player"]
+    } else if has("audio/") {
+        vec!["AudioVideo", "Audio", "Player"]
+    } else if has("image/") {
+        vec!["Graphics", "Viewer"]
+    } else if has("text/") {
+        vec!["Utility", "TextEditor"]
+    } else {
+        vec!["Utility"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CATEGORIES, CategoryIssue, check_categories, find, suggest_categories};
+
+    fn list(s: &str) -> Vec<String> {
+        s.split(';').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn related_categories_are_registered() {
+        for category in CATEGORIES {
+            for name in category.related.iter().flat_map(|set| set.iter()) {
+                assert!(find(name).is_some(), "{} -> {}", category.name, name);
+            }
+        }
+    }
+
+    #[test]
+    fn accepts_well_formed_lists() {
+        assert!(check_categories(&list("AudioVideo;Audio;Midi;X-Custom"), false).is_empty());
+        assert!(check_categories(&list("Science;Math;NumericalAnalysis"), false).is_empty());
+        assert!(check_categories(&list("Utility;TrayIcon"), true).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_related_categories() {
+        let issues = check_categories(&list("Audio;Mixer"), false);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0].to_string(),
+            "category \"Audio\" requires AudioVideo"
+        );
+        assert_eq!(
+            issues[1].to_string(),
+            "category \"Mixer\" requires AudioVideo;Audio"
+        );
+        let issues = check_categories(&list("Education;NumericalAnalysis"), false);
+        assert!(
+            issues[0]
+                .to_string()
+                .ends_with("Education;Math or Science;Math")
+        );
+    }
+
+    #[test]
+    fn reports_unknown_and_reserved_categories() {
+        let issues = check_categories(&list("utility;Foo;Applet"), false);
+        assert_eq!(
+            issues[0],
+            CategoryIssue::Unknown {
+                name: "utility".into(),
+                suggestion: Some("Utility"),
+            }
+        );
+        assert!(matches!(
+            &issues[1],
+            CategoryIssue::Unknown {
+                suggestion: None,
+                ..
+            }
+        ));
+        assert_eq!(issues[2], CategoryIssue::Reserved("Applet".into()));
+        assert_eq!(issues[3], CategoryIssue::NoMainCategory);
+    }
+
+    #[test]
+    fn suggests_from_mime_types() {
+        assert_eq!(suggest_categories(&[]), vec!["Utility"]);
+        assert_eq!(
+            suggest_categories(&list("image/png;image/jpeg")),
+            vec!["Graphics", "Viewer"]
+        );
+        assert_eq!(
+            suggest_categories(&list("text/html;x-scheme-handler/https")),
+            vec!["Network", "WebBrowser"]
+        );
+    }
+}
@@ -1,8 +1,12 @@
-use std::fmt::Write as _;
+use crate::domain::exec_command::{ExecCommand, ExecError, normalize_command_line};
+use crate::domain::ini_document::{IniDocument, IniGroup, render_groups};
+use crate::domain::locale::{Locale, Translations, split_locale_key};
+use crate::domain::validation::{Diagnostic, Severity, validate_document};
 
 #[derive(Debug, Clone, Default)]
 pub struct DesktopEntry {
     pub type_field: String,
+    pub version: Option<String>,
     pub name: String,
     pub generic_name: Option<String>,
     pub comment: Option<String>,
@@ -19,183 +23,298 @@ pub struct DesktopEntry {
     pub try_exec: Option<String>,
     pub path: Option<String>,
     pub url: Option<String>,
-    pub actions: Vec<String>,
+    pub hidden: bool,
+    pub dbus_activatable: bool,
+    pub startup_wm_class: Option<String>,
+    pub prefers_non_default_gpu: bool,
+    pub single_main_window: bool,
+    pub implements: Vec<String>,
+    pub actions: Vec<DesktopAction>,
     pub extra: Vec<(String, String)>,
 
-    // Localized variants
-    pub name_localized: Vec<(String, String)>,
-    pub generic_name_localized: Vec<(String, String)>,
-    pub comment_localized: Vec<(String, String)>,
+    // Localized variants (`Key[locale]=`)
+    pub name_localized: Translations<String>,
+    pub generic_name_localized: Translations<String>,
+    pub comment_localized: Translations<String>,
+    pub icon_localized: Translations<String>,
+    pub keywords_localized: Translations<Vec<String>>,
+
+    // Original file layout, kept so that saving rewrites only changed lines.
+    pub document: Option<IniDocument>,
+}
+
+/// An application action declared in `Actions=` and described by its own
+/// `[Desktop Action <id>]` group.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DesktopAction {
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    pub exec: String,
+    pub name_localized: Translations<String>,
+    pub icon_localized: Translations<String>,
+}
+
+impl DesktopAction {
+    pub fn group_name(&self) -> String {
+        format!("Desktop Action {}", self.id)
+    }
 }
 
 impl DesktopEntry {
+    /// `Name` as shown to a user of `locale`.
+    pub fn localized_name(&self, locale: Option<&Locale>) -> &str {
+        pick(&self.name, &self.name_localized, locale)
+    }
+
+    /// `Exec` parsed into its arguments, as it would be written to disk.
+    pub fn exec_command(&self) -> Result<ExecCommand, ExecError> {
+        ExecCommand::parse(&normalize_exec(self.exec.trim()))
+    }
+
+    /// Validates the entry as it would be written to disk.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        validate_document(&IniDocument::parse(&self.to_ini_string()))
+    }
+
+    /// Fails with the first error-level diagnostic; warnings and hints do
+    /// not block saving.
     pub fn validate(&self) -> Result<(), String> {
-        if self.type_field.is_empty() {
-            return Err("Type is required".into());
-        }
-        if self.name.trim().is_empty() {
-            return Err("Name is required".into());
+        match self
+            .diagnostics()
+            .into_iter()
+            .find(|d| d.severity == Severity::Error)
+        {
+            Some(d) => Err(d.message),
+            None => Ok(()),
         }
-        match self.type_field.as_str() {
-            "Application" => {
-                if self.exec.trim().is_empty() {
-                    return Err("Exec is required for Type=Application".into());
-                }
+    }
+
+    pub fn to_ini_string(&self) -> String {
+        self.render(self.to_groups())
+    }
+
+    /// A copy with the raw `value` of `key` in `group`, or without the key
+    /// when `value` is `None`. The rest of the file is kept as it is.
+    pub fn with_value(&self, group: &str, key: &str, value: Option<&str>) -> DesktopEntry {
+        let mut groups = self.to_groups();
+        let idx = match groups.iter().position(|g| g.name == group) {
+            Some(idx) => idx,
+            None => {
+                groups.push(IniGroup::new(group));
+                groups.len() - 1
+            }
+        };
+        let entries = &mut groups[idx].entries;
+        match (entries.iter().position(|(k, _)| k == key), value) {
+            (Some(i), Some(v)) => entries[i].1 = v.to_string(),
+            (Some(i), None) => {
+                entries.remove(i);
             }
-            "Link" => {
-                if self.url.as_deref().unwrap_or("").trim().is_empty() {
-                    return Err("URL is required for Type=Link".into());
+            (None, Some(v)) => entries.push((key.to_string(), v.to_string())),
+            (None, None) => {}
+        }
+        DesktopEntry::from_ini_string(&self.render(groups))
+    }
+
+    fn render(&self, mut groups: Vec<IniGroup>) -> String {
+        match &self.document {
+            Some(document) => {
+                // Implicit `false` flags are only written when the file already has them.
+                for g in &mut groups {
+                    let present = document.entries(&g.name);
+                    g.entries.retain(|(k, v)| {
+                        v != "false" || !is_bool_key(k) || present.iter().any(|(pk, _)| pk == k)
+                    });
                 }
+                let managed = self.managed_groups(document);
+                let mut document = document.clone();
+                document.merge(&groups, &managed, same_value);
+                document.to_string()
             }
-            "Directory" => {}
-            _ => return Err("Type must be one of Application, Link, Directory".into()),
+            None => render_groups(&groups),
         }
-        Ok(())
     }
 
-    pub fn to_ini_string(&self) -> String {
-        let mut s = String::new();
-        let _ = writeln!(&mut s, "[Desktop Entry]");
-        let _ = writeln!(&mut s, "Type={}", self.type_field);
-        let _ = writeln!(&mut s, "Name={}", escape(&self.name));
-        for (lang, val) in &self.name_localized {
-            let _ = writeln!(&mut s, "Name[{}]={}", lang, escape(val));
+    /// The entry as ordered key/value groups, in the canonical layout used
+    /// for new files.
+    pub fn to_groups(&self) -> Vec<IniGroup> {
+        let is_app = self.type_field == "Application";
+        let mut g = IniGroup::new("Desktop Entry");
+        g.push("Type", &self.type_field);
+        if let Some(v) = &self.version {
+            g.push("Version", escape_string(v.trim()));
         }
+        g.push("Name", escape_string(&self.name));
+        push_translations(&mut g, "Name", &self.name_localized);
         if let Some(v) = &self.generic_name {
-            let _ = writeln!(&mut s, "GenericName={}", escape(v));
-        }
-        for (lang, val) in &self.generic_name_localized {
-            let _ = writeln!(&mut s, "GenericName[{}]={}", lang, escape(val));
+            g.push("GenericName", escape_string(v));
         }
+        push_translations(&mut g, "GenericName", &self.generic_name_localized);
         if let Some(v) = &self.comment {
-            let _ = writeln!(&mut s, "Comment={}", escape(v));
+            g.push("Comment", escape_string(v));
         }
-        for (lang, val) in &self.comment_localized {
-            let _ = writeln!(&mut s, "Comment[{}]={}", lang, escape(val));
+        push_translations(&mut g, "Comment", &self.comment_localized);
+        if is_app && !self.exec.is_empty() {
+            g.push("Exec", escape_string(&normalize_exec(self.exec.trim())));
         }
-        if self.type_field == "Application" && !self.exec.is_empty() {
-            let _ = writeln!(&mut s, "Exec={}", normalize_exec(self.exec.trim()));
-        }
-        if self.type_field == "Application"
-            && let Some(v) = &self.try_exec
-        {
-            let _ = writeln!(&mut s, "TryExec={}", v.trim());
+        if is_app && let Some(v) = &self.try_exec {
+            g.push("TryExec", escape_string(v.trim()));
         }
         if let Some(v) = &self.icon {
-            let _ = writeln!(&mut s, "Icon={}", v.trim());
+            g.push("Icon", escape_string(v.trim()));
         }
-        if self.type_field == "Application"
-            && let Some(v) = &self.path
-        {
-            let _ = writeln!(&mut s, "Path={}", v.trim());
+        push_translations(&mut g, "Icon", &self.icon_localized);
+        if is_app && let Some(v) = &self.path {
+            g.push("Path", escape_string(v.trim()));
         }
         if self.type_field == "Link"
             && let Some(v) = &self.url
         {
-            let _ = writeln!(&mut s, "URL={}", v.trim());
+            g.push("URL", escape_string(v.trim()));
         }
-        if self.type_field == "Application" {
-            let _ = writeln!(
-                &mut s,
-                "Terminal={}",
-                if self.terminal { "true" } else { "false" }
-            );
+        if is_app {
+            g.push("Terminal", bool_str(self.terminal));
         }
-        let _ = writeln!(
-            &mut s,
-            "NoDisplay={}",
-            if self.no_display { "true" } else { "false" }
-        );
-        if self.type_field == "Application" {
-            let _ = writeln!(
-                &mut s,
-                "StartupNotify={}",
-                if self.startup_notify { "true" } else { "false" }
-            );
+        g.push("NoDisplay", bool_str(self.no_display));
+        if is_app {
+            g.push("StartupNotify", bool_str(self.startup_notify));
+        }
+        // Newer flags are only spelled out when set; an existing `false` is
+        // kept by the merge in `to_ini_string`.
+        let flags = [
+            ("Hidden", self.hidden, true),
+            ("DBusActivatable", self.dbus_activatable, is_app),
+            ("PrefersNonDefaultGPU", self.prefers_non_default_gpu, is_app),
+            ("SingleMainWindow", self.single_main_window, is_app),
+        ];
+        for (key, value, applies) in flags {
+            if applies && (value || self.document.is_some()) {
+                g.push(key, bool_str(value));
+            }
+        }
+        if is_app && let Some(v) = &self.startup_wm_class {
+            g.push("StartupWMClass", escape_string(v.trim()));
+        }
+        if !self.implements.is_empty() {
+            g.push("Implements", join_list(&self.implements));
         }
-        if self.type_field == "Application" {
-            let categories = if self.categories.is_empty() {
-                vec!["Utility".to_string()]
-            } else {
-                self.categories.clone()
-            };
-            let _ = writeln!(&mut s, "Categories={};", categories.join(";"));
+        if is_app && !self.categories.is_empty() {
+            g.push("Categories", join_list(&self.categories));
         }
-        if self.type_field == "Application" && !self.mime_type.is_empty() {
-            let _ = writeln!(&mut s, "MimeType={};", self.mime_type.join(";"));
+        if is_app && !self.mime_type.is_empty() {
+            g.push("MimeType", join_list(&self.mime_type));
         }
-        if self.type_field == "Application" && !self.keywords.is_empty() {
-            let _ = writeln!(&mut s, "Keywords={};", self.keywords.join(";"));
+        if is_app && !self.keywords.is_empty() {
+            g.push("Keywords", join_list(&self.keywords));
+        }
+        if is_app {
+            for (lang, val) in self.keywords_localized.iter() {
+                g.push(format!("Keywords[{}]", lang), join_list(val));
+            }
         }
         if !self.only_show_in.is_empty() {
-            let _ = writeln!(&mut s, "OnlyShowIn={};", self.only_show_in.join(";"));
+            g.push("OnlyShowIn", join_list(&self.only_show_in));
         }
         if !self.not_show_in.is_empty() {
-            let _ = writeln!(&mut s, "NotShowIn={};", self.not_show_in.join(";"));
+            g.push("NotShowIn", join_list(&self.not_show_in));
         }
-        if self.type_field == "Application" && !self.actions.is_empty() {
-            let _ = writeln!(&mut s, "Actions={};", self.actions.join(";"));
+        if is_app && !self.actions.is_empty() {
+            let ids: Vec<String> = self.actions.iter().map(|a| a.id.clone()).collect();
+            g.push("Actions", join_list(&ids));
         }
         for (k, v) in &self.extra {
             if !k.trim().is_empty() {
-                let _ = writeln!(&mut s, "{}={}", k.trim(), v.trim());
+                g.push(k.trim(), v.trim());
             }
         }
-        s
-    }
 
-    pub fn from_ini_string(content: &str) -> Self {
-        let mut entry = DesktopEntry::default();
-        let mut in_desktop = false;
-
-        for line in content.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
-                continue;
+        let mut groups = vec![g];
+        if is_app {
+            for action in &self.actions {
+                let mut g = IniGroup::new(action.group_name());
+                g.push("Name", escape_string(&action.name));
+                push_translations(&mut g, "Name", &action.name_localized);
+                if let Some(v) = &action.icon {
+                    g.push("Icon", escape_string(v.trim()));
+                }
+                push_translations(&mut g, "Icon", &action.icon_localized);
+                if !action.exec.trim().is_empty() {
+                    g.push("Exec", escape_string(&normalize_exec(action.exec.trim())));
+                }
+                groups.push(g);
             }
+        }
+        groups
+    }
 
-            if line.starts_with('[') && line.ends_with(']') {
-                in_desktop = line == "[Desktop Entry]";
-                continue;
-            }
+    // Groups this entry owns inside `document`: the main group and every
+    // action group that was declared in the original `Actions=` key.
+    fn managed_groups(&self, document: &IniDocument) -> Vec<String> {
+        let mut managed = vec!["Desktop Entry".to_string()];
+        let declared = document
+            .entries("Desktop Entry")
+            .into_iter()
+            .filter(|(k, _)| *k == "Actions")
+            .flat_map(|(_, v)| split_list(v));
+        managed.extend(declared.map(|id| format!("Desktop Action {}", id)));
+        managed
+    }
 
-            if !in_desktop {
-                continue;
-            }
+    pub fn from_ini_string(content: &str) -> Self {
+        let document = IniDocument::parse(content);
+        let mut entry = DesktopEntry::default();
+        let mut action_ids = Vec::new();
 
-            if let Some((k, v)) = line.split_once('=') {
-                let key = k.trim();
-                let val = v.trim().to_string();
-                match key {
-                    "Type" => entry.type_field = val,
-                    "Name" => entry.name = val,
-                    _ if key.starts_with("Name[") && key.ends_with(']') => {
-                        push_localized(&mut entry.name_localized, key, "Name", &val)
-                    }
-                    "GenericName" => entry.generic_name = Some(val),
-                    _ if key.starts_with("GenericName[") && key.ends_with(']') => {
-                        push_localized(&mut entry.generic_name_localized, key, "GenericName", &val)
+        for (key, raw) in document.entries("Desktop Entry") {
+            let val = unescape_string(raw);
+            if let (base, Some(locale)) = split_locale_key(key) {
+                let target = match base {
+                    "Name" => &mut entry.name_localized,
+                    "GenericName" => &mut entry.generic_name_localized,
+                    "Comment" => &mut entry.comment_localized,
+                    "Icon" => &mut entry.icon_localized,
+                    "Keywords" => {
+                        entry.keywords_localized.set(locale, split_list(raw));
+                        continue;
                     }
-                    "Comment" => entry.comment = Some(val),
-                    _ if key.starts_with("Comment[") && key.ends_with(']') => {
-                        push_localized(&mut entry.comment_localized, key, "Comment", &val)
+                    _ => {
+                        entry.extra.push((key.to_string(), raw.to_string()));
+                        continue;
                     }
-                    "Exec" => entry.exec = val,
-                    "TryExec" => entry.try_exec = Some(val),
-                    "Icon" => entry.icon = Some(val),
-                    "Path" => entry.path = Some(val),
-                    "URL" => entry.url = Some(val),
-                    "Terminal" => entry.terminal = val.eq_ignore_ascii_case("true"),
-                    "NoDisplay" => entry.no_display = val.eq_ignore_ascii_case("true"),
-                    "StartupNotify" => entry.startup_notify = val.eq_ignore_ascii_case("true"),
-                    "Categories" => entry.categories = split_semicolon(&val),
-                    "MimeType" => entry.mime_type = split_semicolon(&val),
-                    "Keywords" => entry.keywords = split_semicolon(&val),
-                    "OnlyShowIn" => entry.only_show_in = split_semicolon(&val),
-                    "NotShowIn" => entry.not_show_in = split_semicolon(&val),
-                    "Actions" => entry.actions = split_semicolon(&val),
-                    _ => entry.extra.push((key.to_string(), val)),
+                };
+                target.set(locale, val);
+                continue;
+            }
+            match key {
+                "Type" => entry.type_field = val,
+                "Version" => entry.version = Some(val),
+                "Name" => entry.name = val,
+                "GenericName" => entry.generic_name = Some(val),
+                "Comment" => entry.comment = Some(val),
+                "Exec" => entry.exec = val,
+                "TryExec" => entry.try_exec = Some(val),
+                "Icon" => entry.icon = Some(val),
+                "Path" => entry.path = Some(val),
+                "URL" => entry.url = Some(val),
+                "Terminal" => entry.terminal = val.eq_ignore_ascii_case("true"),
+                "NoDisplay" => entry.no_display = val.eq_ignore_ascii_case("true"),
+                "StartupNotify" => entry.startup_notify = val.eq_ignore_ascii_case("true"),
+                "Hidden" => entry.hidden = val.eq_ignore_ascii_case("true"),
+                "DBusActivatable" => entry.dbus_activatable = val.eq_ignore_ascii_case("true"),
+                "StartupWMClass" => entry.startup_wm_class = Some(val),
+                "PrefersNonDefaultGPU" => {
+                    entry.prefers_non_default_gpu = val.eq_ignore_ascii_case("true")
                 }
+                "SingleMainWindow" => entry.single_main_window = val.eq_ignore_ascii_case("true"),
+                "Implements" => entry.implements = split_list(raw),
+                "Categories" => entry.categories = split_list(raw),
+                "MimeType" => entry.mime_type = split_list(raw),
+                "Keywords" => entry.keywords = split_list(raw),
+                "OnlyShowIn" => entry.only_show_in = split_list(raw),
+                "NotShowIn" => entry.not_show_in = split_list(raw),
+                "Actions" => action_ids = split_list(raw),
+                _ => entry.extra.push((key.to_string(), raw.to_string())),
             }
         }
 
@@ -203,60 +322,183 @@ impl DesktopEntry {
             entry.type_field = "Application".into();
         }
 
+        // Only actions listed in `Actions=` are meaningful; groups that are not
+        // listed stay untouched in the document.
+        entry.actions = action_ids
+            .into_iter()
+            .map(|id| {
+                let mut action = DesktopAction {
+                    id,
+                    ..DesktopAction::default()
+                };
+                for (key, val) in document.entries(&action.group_name()) {
+                    parse_action_key(&mut action, key, val);
+                }
+                action
+            })
+            .collect();
+
+        entry.document = Some(document);
         entry
     }
 }
 
-fn escape(input: &str) -> String {
-    input.replace('\n', "\\n")
+fn parse_action_key(action: &mut DesktopAction, key: &str, raw: &str) {
+    let val = unescape_string(raw);
+    match split_locale_key(key) {
+        ("Name", Some(locale)) => action.name_localized.set(locale, val),
+        ("Icon", Some(locale)) => action.icon_localized.set(locale, val),
+        ("Name", None) => action.name = val,
+        ("Icon", None) => action.icon = Some(val),
+        ("Exec", None) => action.exec = val,
+        _ => {}
+    }
 }
 
-fn split_semicolon(s: &str) -> Vec<String> {
-    s.split(';')
-        .map(|p| p.trim().to_string())
-        .filter(|p| !p.is_empty())
-        .collect()
+// Keys whose value is a `;`-separated list, compared element-wise on save.
+fn is_list_key(key: &str) -> bool {
+    let base = key.split('[').next().unwrap_or(key);
+    matches!(
+        base,
+        "Categories"
+            | "MimeType"
+            | "Keywords"
+            | "OnlyShowIn"
+            | "NotShowIn"
+            | "Actions"
+            | "Implements"
+    )
 }
 
-fn push_localized(vec: &mut Vec<(String, String)>, key: &str, prefix: &str, val: &str) {
-    let lang = key
-        .trim_start_matches(prefix)
-        .trim_start_matches('[')
-        .trim_end_matches(']')
-        .to_string();
-    vec.push((lang, val.to_string()));
+fn is_bool_key(key: &str) -> bool {
+    matches!(
+        key,
+        "Terminal"
+            | "NoDisplay"
+            | "StartupNotify"
+            | "Hidden"
+            | "DBusActivatable"
+            | "PrefersNonDefaultGPU"
+            | "SingleMainWindow"
+    )
 }
 
-fn normalize_exec(exec: &str) -> String {
-    if exec.is_empty() {
-        return String::new();
+fn same_value(key: &str, old: &str, new: &str) -> bool {
+    if is_list_key(key) {
+        return split_list(old) == split_list(new);
     }
+    old == new
+        || unescape_string(old) == unescape_string(new)
+        || (is_bool_key(key) && old.eq_ignore_ascii_case(new))
+}
+
+fn bool_str(value: bool) -> &'static str {
+    if value { "true" } else { "false" }
+}
 
-    // Keep expert input untouched when it already starts as a quoted command.
-    if exec.starts_with('"') || exec.starts_with('\'') {
-        return exec.to_string();
+/// Escapes a string, localestring or iconstring value: backslash, newline,
+/// tab, carriage return, and leading or trailing spaces (as `\s`) so they
+/// survive the whitespace trimming around `=`.
+pub fn escape_string(input: &str) -> String {
+    let lead = input.len() - input.trim_start_matches(' ').len();
+    let trail_start = input.trim_end_matches(' ').len().max(lead);
+    let mut out = String::with_capacity(input.len());
+    for (idx, c) in input.char_indices() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            ' ' if idx < lead || idx >= trail_start => out.push_str("\\s"),
+            _ => out.push(c),
+        }
     }
+    out
+}
+
+/// Reverses [`escape_string`]. Unknown escape sequences are kept verbatim.
+pub fn unescape_string(input: &str) -> String {
+    unescape(input, false).concat()
+}
+
+/// Splits a list value on unescaped `;` and unescapes every element,
+/// including the `\;` separator escape.
+pub fn split_list(input: &str) -> Vec<String> {
+    unescape(input, true)
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
 
-    if let Some(appimage_idx) = exec.find(".AppImage") {
-        let end = appimage_idx + ".AppImage".len();
-        let (candidate, rest) = exec.split_at(end);
-        let candidate_trimmed = candidate.trim();
-        let looks_like_path = candidate_trimmed.starts_with('/')
-            || candidate_trimmed.starts_with("./")
-            || candidate_trimmed.starts_with("~/");
-        let needs_quotes = candidate_trimmed.contains(char::is_whitespace);
+/// Joins list elements with `;` (trailing one included), escaping each
+/// element and any `;` it contains.
+pub fn join_list(items: &[String]) -> String {
+    let mut out = String::new();
+    for item in items {
+        out.push_str(&escape_string(item).replace(';', "\\;"));
+        out.push(';');
+    }
+    out
+}
 
-        if looks_like_path && needs_quotes {
-            return format!("\"{}\"{}", candidate_trimmed, rest);
+// Shared unescaper: yields one string, or one per `;`-separated element.
+fn unescape(input: &str, as_list: bool) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        let current = parts.last_mut().expect("parts is never empty");
+        match c {
+            '\\' => match chars.next() {
+                Some('s') => current.push(' '),
+                Some('n') => current.push('\n'),
+                Some('t') => current.push('\t'),
+                Some('r') => current.push('\r'),
+                Some('\\') => current.push('\\'),
+                Some(';') if as_list => current.push(';'),
+                Some(other) => {
+                    current.push('\\');
+                    current.push(other);
+                }
+                None => current.push('\\'),
+            },
+            ';' if as_list => parts.push(String::new()),
+            _ => current.push(c),
         }
     }
+    parts
+}
 
-    exec.to_string()
+fn push_translations(g: &mut IniGroup, key: &str, translations: &Translations<String>) {
+    for (lang, val) in translations.iter() {
+        g.push(format!("{}[{}]", key, lang), escape_string(val));
+    }
+}
+
+fn pick<'a>(
+    default: &'a str,
+    translations: &'a Translations<String>,
+    locale: Option<&Locale>,
+) -> &'a str {
+    locale
+        .and_then(|l| translations.lookup(l))
+        .map(String::as_str)
+        .unwrap_or(default)
+}
+
+// Serializing never looks at the disk: only `.AppImage` paths are quoted
+// here. The editor quotes other typed paths once it has checked the file
+// exists.
+fn normalize_exec(exec: &str) -> String {
+    normalize_command_line(exec, |_| false)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{DesktopEntry, normalize_exec};
+    use super::{
+        DesktopAction, DesktopEntry, Locale, escape_string, join_list, normalize_exec, split_list,
+        unescape_string,
+    };
 
     #[test]
     fn parse_ini_entry() {
@@ -267,7 +509,13 @@ mod tests {
         assert_eq!(entry.name, "My App");
         assert_eq!(entry.exec, "/usr/bin/my-app");
         assert_eq!(entry.keywords, vec!["alpha", "beta"]);
-        assert_eq!(entry.name_localized.len(), 1);
+        assert_eq!(
+            entry
+                .name_localized
+                .lookup(&Locale::parse("fr").unwrap())
+                .map(String::as_str),
+            Some("Mon App")
+        );
     }
 
     #[test]
@@ -290,6 +538,227 @@ mod tests {
         assert!(reparsed.no_display);
     }
 
+    #[test]
+    fn modern_keys_are_typed_fields() {
+        let input = "[Desktop Entry]\nVersion=1.5\nType=Application\nName=Chat\nExec=chat\nDBusActivatable=true\nStartupWMClass=chat-electron\nPrefersNonDefaultGPU=true\nSingleMainWindow=true\nImplements=org.gnome.Shell.SearchProvider2;\nCategories=Network;\nHidden=false\n";
+        let entry = DesktopEntry::from_ini_string(input);
+        assert!(entry.extra.is_empty());
+        assert_eq!(entry.version.as_deref(), Some("1.5"));
+        assert!(
+            entry.dbus_activatable && entry.prefers_non_default_gpu && entry.single_main_window
+        );
+        assert!(!entry.hidden);
+        assert_eq!(entry.startup_wm_class.as_deref(), Some("chat-electron"));
+        assert_eq!(entry.implements, vec!["org.gnome.Shell.SearchProvider2"]);
+        assert_eq!(entry.to_ini_string(), input);
+    }
+
+    #[test]
+    fn new_entries_only_write_set_modern_flags() {
+        let entry = DesktopEntry {
+            type_field: "Application".into(),
+            name: "Chat".into(),
+            exec: "chat".into(),
+            single_main_window: true,
+            ..DesktopEntry::default()
+        };
+        let ini = entry.to_ini_string();
+        assert!(ini.contains("SingleMainWindow=true\n"));
+        assert!(!ini.contains("Hidden"));
+        assert!(!ini.contains("DBusActivatable"));
+    }
+
+    #[test]
+    fn parse_action_groups_in_declared_order() {
+        let input = "[Desktop Entry]\nType=Application\nName=Firefox\nExec=firefox %u\nActions=new-window;new-private-window;\n\n[Desktop Action new-private-window]\nName=New Private Window\nName[fr]=Nouvelle fenêtre privée\nExec=firefox --private-window %u\n\n[Desktop Action new-window]\nName=New Window\nIcon=firefox\nExec=firefox --new-window %u\n";
+        let entry = DesktopEntry::from_ini_string(input);
+
+        let ids: Vec<&str> = entry.actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["new-window", "new-private-window"]);
+        assert_eq!(entry.actions[0].icon.as_deref(), Some("firefox"));
+        assert_eq!(entry.actions[1].name, "New Private Window");
+        assert_eq!(entry.actions[1].exec, "firefox --private-window %u");
+        assert_eq!(
+            entry.actions[1]
+                .name_localized
+                .lookup(&Locale::parse("fr").unwrap())
+                .map(String::as_str),
+            Some("Nouvelle fenêtre privée")
+        );
+        assert!(entry.extra.is_empty());
+    }
+
+    #[test]
+    fn every_localized_key_is_kept_typed() {
+        let input = "[Desktop Entry]\nType=Application\nName=Files\nName[de]=Dateien\nName[sr@latin]=Datoteke\nExec=files\nIcon=files\nIcon[fr]=fichiers\nCategories=Utility;\nKeywords=folder;manager;\nKeywords[de]=Ordner;Verwaltung;\nActions=new;\n\n[Desktop Action new]\nName=New Window\nName[de]=Neues Fenster\nIcon[de]=neu\nExec=files --new\n";
+        let entry = DesktopEntry::from_ini_string(input);
+        assert!(entry.extra.is_empty());
+        assert_eq!(
+            entry
+                .icon_localized
+                .lookup(&Locale::parse("fr").unwrap())
+                .map(String::as_str),
+            Some("fichiers")
+        );
+        assert_eq!(
+            entry
+                .keywords_localized
+                .lookup(&Locale::parse("de").unwrap()),
+            Some(&vec!["Ordner".to_string(), "Verwaltung".to_string()])
+        );
+        assert_eq!(
+            entry.actions[0]
+                .icon_localized
+                .lookup(&Locale::parse("de").unwrap())
+                .map(String::as_str),
+            Some("neu")
+        );
+        assert_eq!(entry.to_ini_string(), input);
+
+        let rebuilt = DesktopEntry {
+            document: None,
+            ..entry.clone()
+        };
+        assert_eq!(
+            DesktopEntry::from_ini_string(&rebuilt.to_ini_string()).keywords_localized,
+            entry.keywords_localized
+        );
+    }
+
+    #[test]
+    fn localized_name_matches_locale() {
+        let entry = DesktopEntry::from_ini_string(
+            "[Desktop Entry]\nName=Files\nName[de]=Dateien\nName[sr@latin]=Datoteke\n",
+        );
+        let name = |l: &str| entry.localized_name(Locale::parse(l).as_ref()).to_string();
+        assert_eq!(name("de_AT.UTF-8"), "Dateien");
+        assert_eq!(name("sr_RS@latin"), "Datoteke");
+        assert_eq!(name("sr_RS"), "Files");
+        assert_eq!(entry.localized_name(None), "Files");
+    }
+
+    #[test]
+    fn round_trip_keeps_actions() {
+        let mut entry = DesktopEntry::from_ini_string(
+            "[Desktop Entry]\nType=Application\nName=Editor\nExec=editor\n",
+        );
+        entry.actions.push(DesktopAction {
+            id: "new-document".into(),
+            name: "New Document".into(),
+            exec: "editor --new".into(),
+            ..DesktopAction::default()
+        });
+        let ini = entry.to_ini_string();
+        assert!(ini.contains("Actions=new-document;\n"));
+        assert!(ini.contains("[Desktop Action new-document]\nName=New Document\n"));
+
+        let reparsed = DesktopEntry::from_ini_string(&ini);
+        assert_eq!(reparsed.actions, entry.actions);
+    }
+
+    #[test]
+    fn validate_rejects_invalid_actions() {
+        let mut entry = DesktopEntry::from_ini_string(
+            "[Desktop Entry]\nType=Application\nName=Editor\nExec=editor\n",
+        );
+        entry.actions.push(DesktopAction {
+            id: "bad id".into(),
+            name: "Bad".into(),
+            ..DesktopAction::default()
+        });
+        assert!(entry.validate().is_err());
+
+        entry.actions[0].id = "good-id".into();
+        assert!(entry.validate().is_ok());
+
+        entry.actions[0].name.clear();
+        assert!(entry.validate().is_err());
+    }
+
+    #[test]
+    fn unchanged_entry_round_trips_byte_for_byte() {
+        let input = "# Vendor launcher\n[Desktop Entry]\nVersion=1.0\nName=Firefox\nName[fr]=Firefox\nExec=firefox %u\n\n# Keep this comment\nIcon=firefox\nType=Application\nCategories=Network;WebBrowser\nX-Vendor=1\nX-Vendor=2\nActions=new-window;\n\n[Desktop Action new-window]\nName=New Window\nExec=firefox --new-window %u\n\n[X-Unknown Group]\nFoo=bar\n";
+        let entry = DesktopEntry::from_ini_string(input);
+        assert_eq!(entry.to_ini_string(), input);
+    }
+
+    #[test]
+    fn editing_one_field_touches_one_line() {
+        let input = "[Desktop Entry]\n# comment\nName=Old\nExec=app\nType=Application\nCategories=Utility\n\n[X-Extra]\nA=1\n";
+        let mut entry = DesktopEntry::from_ini_string(input);
+        entry.name = "New".into();
+        assert_eq!(
+            entry.to_ini_string(),
+            "[Desktop Entry]\n# comment\nName=New\nExec=app\nType=Application\nCategories=Utility\n\n[X-Extra]\nA=1\n"
+        );
+    }
+
+    #[test]
+    fn removing_an_action_drops_its_group_only() {
+        let input = "[Desktop Entry]\nType=Application\nName=App\nExec=app\nCategories=Utility;\nActions=one;\n\n[Desktop Action one]\nName=One\nExec=app --one\n\n[Desktop Action stray]\nName=Stray\n";
+        let mut entry = DesktopEntry::from_ini_string(input);
+        entry.actions.clear();
+        assert_eq!(
+            entry.to_ini_string(),
+            "[Desktop Entry]\nType=Application\nName=App\nExec=app\nCategories=Utility;\n\n[Desktop Action stray]\nName=Stray\n"
+        );
+    }
+
+    #[test]
+    fn escape_sequences_round_trip() {
+        let cases = [
+            ("Line\nBreak", "Line\\nBreak"),
+            ("Tab\there", "Tab\\there"),
+            ("Carriage\rReturn", "Carriage\\rReturn"),
+            ("C:\\Path", "C:\\\\Path"),
+            ("  padded ", "\\s\\spadded\\s"),
+            ("inner space", "inner space"),
+        ];
+        for (value, escaped) in cases {
+            assert_eq!(escape_string(value), escaped);
+            assert_eq!(unescape_string(escaped), value);
+        }
+        assert_eq!(unescape_string("a\\sb\\qc"), "a b\\qc");
+    }
+
+    #[test]
+    fn list_values_escape_semicolons() {
+        let items = vec!["semi;colon".to_string(), "back\\slash".to_string()];
+        let joined = join_list(&items);
+        assert_eq!(joined, "semi\\;colon;back\\\\slash;");
+        assert_eq!(split_list(&joined), items);
+        assert_eq!(split_list("a;b\\;c;;"), vec!["a", "b;c"]);
+    }
+
+    #[test]
+    fn escaped_values_survive_repeated_saves() {
+        let input = "[Desktop Entry]\nType=Application\nName=Back\\\\slash\nComment=Two\\nlines\nExec=app\nKeywords=one\\;two;three;\nCategories=Utility;\n";
+        let entry = DesktopEntry::from_ini_string(input);
+        assert_eq!(entry.name, "Back\\slash");
+        assert_eq!(entry.comment.as_deref(), Some("Two\nlines"));
+        assert_eq!(entry.keywords, vec!["one;two", "three"]);
+        assert_eq!(entry.to_ini_string(), input);
+
+        let mut fresh = entry.clone();
+        fresh.document = None;
+        let reparsed = DesktopEntry::from_ini_string(&fresh.to_ini_string());
+        assert_eq!(reparsed.name, entry.name);
+        assert_eq!(reparsed.comment, entry.comment);
+        assert_eq!(reparsed.keywords, entry.keywords);
+    }
+
+    #[test]
+    fn validate_rejects_broken_exec_lines() {
+        let mut entry = DesktopEntry::from_ini_string(
+            "[Desktop Entry]\nType=Application\nName=App\nExec=\"/opt/app\n",
+        );
+        assert!(entry.validate().is_err());
+        entry.exec = "app %f %U".into();
+        assert!(entry.validate().is_err());
+        entry.exec = "\"/opt/My App/app\" %U".into();
+        assert!(entry.validate().is_ok());
+    }
+
     #[test]
     fn normalize_exec_quotes_spaced_appimage_path() {
         let src = "/home/user/My Tools/r2d3.AppImage --minimized";
@@ -300,6 +769,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serializing_exec_does_not_probe_the_disk() {
+        let mut entry =
+            DesktopEntry::from_ini_string("[Desktop Entry]\nType=Application\nName=App\n");
+        // An existing file is still left for the editor to quote.
+        let dir = std::env::temp_dir().join(format!("ls exec {}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let tool = dir.join("run");
+        std::fs::write(&tool, "").unwrap();
+        entry.exec = format!("{} --x", tool.display());
+        assert_eq!(normalize_exec(&entry.exec), entry.exec);
+        std::fs::remove_dir_all(&dir).unwrap();
+        entry.exec = "/opt/My Tools/tool.AppImage --x".into();
+        let program = entry.exec_command().unwrap().program();
+        assert_eq!(program.as_deref(), Some("/opt/My Tools/tool.AppImage"));
+    }
+
     #[test]
     fn normalize_exec_keeps_regular_command() {
         let src = "firefox --new-window";
@@ -0,0 +1,226 @@
+use crate::domain::desktop_entry::DesktopEntry;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One key that differs between two entries. Values are raw key-file values;
+/// localized keys such as `Name[de]` are keys of their own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChange {
+    pub group: String,
+    pub key: String,
+    pub kind: ChangeKind,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl KeyChange {
+    pub fn is_same_key(&self, other: &KeyChange) -> bool {
+        self.group == other.group && self.key == other.key
+    }
+}
+
+/// Keys added, removed or changed going from `old` to `new`, compared in
+/// their serialized form so that formatting differences do not count.
+pub fn diff_entries(old: &DesktopEntry, new: &DesktopEntry) -> Vec<KeyChange> {
+    let (old, new) = (flatten(old), flatten(new));
+    let mut changes = Vec::new();
+    for (group, key, value) in &old {
+        let other = new
+            .iter()
+            .find(|(g, k, _)| g == group && k == key)
+            .map(|(_, _, v)| v);
+        match other {
+            Some(v) if v == value => {}
+            other => changes.push(KeyChange {
+                group: group.clone(),
+                key: key.clone(),
+                kind: if other.is_some() {
+                    ChangeKind::Changed
+                } else {
+                    ChangeKind::Removed
+                },
+                old: Some(value.clone()),
+                new: other.cloned(),
+            }),
+        }
+    }
+    for (group, key, value) in &new {
+        if !old.iter().any(|(g, k, _)| g == group && k == key) {
+            changes.push(KeyChange {
+                group: group.clone(),
+                key: key.clone(),
+                kind: ChangeKind::Added,
+                old: None,
+                new: Some(value.clone()),
+            });
+        }
+    }
+    changes
+}
+
+/// Undoes `change` on `entry`: the key gets its old value back, or is
+/// removed if it was added.
+pub fn take_old(entry: &DesktopEntry, change: &KeyChange) -> DesktopEntry {
+    entry.with_value(&change.group, &change.key, change.old.as_deref())
+}
+
+/// A key both sides changed, to different values, since the common base.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub group: String,
+    pub key: String,
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+impl MergeConflict {
+    /// Resolves the conflict on `entry` in favour of the other side.
+    pub fn take_theirs(&self, entry: &DesktopEntry) -> DesktopEntry {
+        entry.with_value(&self.group, &self.key, self.theirs.as_deref())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Merge {
+    /// `ours` with every non-conflicting change from `theirs` applied.
+    /// Conflicting keys keep our value.
+    pub entry: DesktopEntry,
+    pub applied: Vec<KeyChange>,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Three-way merge of two edits of `base`, key by key.
+pub fn merge_entries(base: &DesktopEntry, ours: &DesktopEntry, theirs: &DesktopEntry) -> Merge {
+    let our_changes = diff_entries(base, ours);
+    let mut merge = Merge {
+        entry: ours.clone(),
+        applied: Vec::new(),
+        conflicts: Vec::new(),
+    };
+    for change in diff_entries(base, theirs) {
+        match our_changes.iter().find(|c| c.is_same_key(&change)) {
+            None => {
+                merge.entry =
+                    merge
+                        .entry
+                        .with_value(&change.group, &change.key, change.new.as_deref());
+                merge.applied.push(change);
+            }
+            Some(ours) if ours.new == change.new => {}
+            Some(ours) => merge.conflicts.push(MergeConflict {
+                group: change.group,
+                key: change.key,
+                base: change.old,
+                ours: ours.new.clone(),
+                theirs: change.new,
+            }),
+        }
+    }
+    merge
+}
+
+fn flatten(entry: &DesktopEntry) -> Vec<(String, String, String)> {
+    entry
+        .to_groups()
+        .into_iter()
+        .flat_map(|g| {
+            g.entries
+                .into_iter()
+                .map(move |(k, v)| (g.name.clone(), k, v))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangeKind, diff_entries, merge_entries, take_old};
+    use crate::domain::desktop_entry::DesktopEntry;
+
+    const SYSTEM: &str = "[Desktop Entry]\nType=Application\nName=Editor\nName[de]=Bearbeiter\nExec=editor %F\nIcon=editor\nCategories=Utility;TextEditor;\nActions=new;\n\n[Desktop Action new]\nName=New Window\nExec=editor --new\n";
+
+    #[test]
+    fn reports_added_removed_and_changed_keys() {
+        let system = DesktopEntry::from_ini_string(SYSTEM);
+        let ours = DesktopEntry::from_ini_string(
+            &SYSTEM
+                .replace("Name[de]=Bearbeiter\n", "")
+                .replace("Icon=editor", "Icon=my-editor\nName[fr]=Éditeur")
+                .replace("Exec=editor --new", "Exec=editor --new-window"),
+        );
+        let changes = diff_entries(&system, &ours);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.group.as_str(), c.key.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Desktop Entry", "Name[de]", ChangeKind::Removed),
+                ("Desktop Entry", "Icon", ChangeKind::Changed),
+                ("Desktop Action new", "Exec", ChangeKind::Changed),
+                ("Desktop Entry", "Name[fr]", ChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn formatting_is_not_a_change() {
+        let system = DesktopEntry::from_ini_string(SYSTEM);
+        let ours = DesktopEntry::from_ini_string(&SYSTEM.replace(
+            "Categories=Utility;TextEditor;",
+            "Categories=Utility;TextEditor",
+        ));
+        assert!(diff_entries(&system, &ours).is_empty());
+    }
+
+    #[test]
+    fn cherry_picks_old_values() {
+        let system = DesktopEntry::from_ini_string(SYSTEM);
+        let ours = DesktopEntry::from_ini_string(
+            &SYSTEM.replace("Icon=editor\n", "# mine\nIcon=mine\nX-Mine=1\n"),
+        );
+        let changes = diff_entries(&system, &ours);
+        assert_eq!(changes.len(), 2);
+        let picked = take_old(&ours, &changes[0]);
+        assert_eq!(picked.icon.as_deref(), Some("editor"));
+        let picked = take_old(&picked, &changes[1]);
+        assert!(
+            picked
+                .to_ini_string()
+                .contains("# mine\nIcon=editor\nCategories")
+        );
+        assert!(diff_entries(&system, &picked).is_empty());
+    }
+
+    #[test]
+    fn merges_independent_edits_and_reports_conflicts() {
+        let base = DesktopEntry::from_ini_string(SYSTEM);
+        let ours = DesktopEntry::from_ini_string(
+            &SYSTEM
+                .replace("Icon=editor", "Icon=mine")
+                .replace("Exec=editor %F", "Exec=editor --ours %F"),
+        );
+        let theirs = DesktopEntry::from_ini_string(
+            &SYSTEM
+                .replace("Name=Editor", "Name=Text Editor")
+                .replace("Exec=editor %F", "Exec=editor --theirs %F")
+                .replace("Icon=editor", "Icon=mine"),
+        );
+        let merge = merge_entries(&base, &ours, &theirs);
+        assert_eq!(merge.entry.name, "Text Editor");
+        assert_eq!(merge.entry.icon.as_deref(), Some("mine"));
+        assert_eq!(merge.entry.exec, "editor --ours %F");
+        assert_eq!(merge.applied.len(), 1);
+        assert_eq!(merge.conflicts.len(), 1);
+        assert_eq!(merge.conflicts[0].key, "Exec");
+        let resolved = merge.conflicts[0].take_theirs(&merge.entry);
+        assert_eq!(resolved.exec, "editor --theirs %F");
+        assert_eq!(resolved.name, "Text Editor");
+    }
+}
@@ -0,0 +1,735 @@
+use crate::domain::uri::{file_uri, has_scheme, local_path};
+use std::fmt;
+
+/// Characters that force an argument to be quoted (Desktop Entry spec,
+/// "The Exec key").
+const RESERVED: &[char] = &[
+    ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(', ')',
+    '`',
+];
+
+/// Characters that must be backslash-escaped inside a quoted argument.
+const QUOTED_ESCAPES: &[char] = &['"', '`', '$', '\\'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldCode {
+    /// `%f`: a single file name.
+    File,
+    /// `%F`: a list of files.
+    Files,
+    /// `%u`: a single URL.
+    Url,
+    /// `%U`: a list of URLs.
+    Urls,
+    /// `%i`: `--icon <Icon>`.
+    Icon,
+    /// `%c`: the translated Name.
+    Name,
+    /// `%k`: location of the desktop file.
+    DesktopFile,
+    /// `%d %D %n %N %v %m`: deprecated, expanded to nothing.
+    Deprecated(char),
+    /// Any other letter.
+    Invalid(char),
+}
+
+impl FieldCode {
+    pub fn from_char(c: char) -> Self {
+        match c {
+            'f' => FieldCode::File,
+            'F' => FieldCode::Files,
+            'u' => FieldCode::Url,
+            'U' => FieldCode::Urls,
+            'i' => FieldCode::Icon,
+            'c' => FieldCode::Name,
+            'k' => FieldCode::DesktopFile,
+            'd' | 'D' | 'n' | 'N' | 'v' | 'm' => FieldCode::Deprecated(c),
+            _ => FieldCode::Invalid(c),
+        }
+    }
+
+    pub fn as_char(self) -> char {
+        match self {
+            FieldCode::File => 'f',
+            FieldCode::Files => 'F',
+            FieldCode::Url => 'u',
+            FieldCode::Urls => 'U',
+            FieldCode::Icon => 'i',
+            FieldCode::Name => 'c',
+            FieldCode::DesktopFile => 'k',
+            FieldCode::Deprecated(c) | FieldCode::Invalid(c) => c,
+        }
+    }
+
+    /// `%f`, `%F`, `%u` or `%U`: the codes that receive opened files or URLs.
+    pub fn takes_files(self) -> bool {
+        matches!(
+            self,
+            FieldCode::File | FieldCode::Files | FieldCode::Url | FieldCode::Urls
+        )
+    }
+
+    /// `%F` or `%U`: must be an argument on its own.
+    pub fn is_list(self) -> bool {
+        matches!(self, FieldCode::Files | FieldCode::Urls)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgPart {
+    Text(String),
+    Field(FieldCode),
+}
+
+/// One argument of the command line, made of literal text and field codes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecArg {
+    pub parts: Vec<ArgPart>,
+}
+
+impl ExecArg {
+    pub fn literal(text: impl Into<String>) -> Self {
+        Self {
+            parts: vec![ArgPart::Text(text.into())],
+        }
+    }
+
+    pub fn field_codes(&self) -> impl Iterator<Item = FieldCode> + '_ {
+        self.parts.iter().filter_map(|p| match p {
+            ArgPart::Field(code) => Some(*code),
+            ArgPart::Text(_) => None,
+        })
+    }
+
+    fn push_text(&mut self, c: char) {
+        match self.parts.last_mut() {
+            Some(ArgPart::Text(t)) => t.push(c),
+            _ => self.parts.push(ArgPart::Text(c.to_string())),
+        }
+    }
+
+    fn needs_quotes(&self) -> bool {
+        self.parts.is_empty()
+            || self
+                .parts
+                .iter()
+                .all(|p| matches!(p, ArgPart::Text(t) if t.is_empty()))
+            || self
+                .parts
+                .iter()
+                .any(|p| matches!(p, ArgPart::Text(t) if t.contains(RESERVED)))
+    }
+
+    fn to_exec_string(&self) -> String {
+        let quoted = self.needs_quotes();
+        let mut out = String::new();
+        if quoted {
+            out.push('"');
+        }
+        for part in &self.parts {
+            match part {
+                ArgPart::Text(t) => {
+                    for c in t.chars() {
+                        if quoted && QUOTED_ESCAPES.contains(&c) {
+                            out.push('\\');
+                        }
+                        if c == '%' {
+                            out.push('%');
+                        }
+                        out.push(c);
+                    }
+                }
+                ArgPart::Field(code) => {
+                    out.push('%');
+                    out.push(code.as_char());
+                }
+            }
+        }
+        if quoted {
+            out.push('"');
+        }
+        out
+    }
+}
+
+/// A parsed `Exec` value (after key-file unescaping), split into arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecCommand {
+    pub args: Vec<ExecArg>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecError {
+    Empty,
+    UnterminatedQuote,
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecError::Empty => write!(f, "command line is empty"),
+            ExecError::UnterminatedQuote => write!(f, "unterminated quoted argument"),
+        }
+    }
+}
+
+impl std::error::Error for ExecError {}
+
+/// Spec violations that parsing tolerates, like desktop launchers do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecIssue {
+    ReservedCharacter(char),
+    SingleQuotes,
+    InvalidEscape(char),
+    FieldCodeInQuotes(char),
+    DeprecatedFieldCode(char),
+    InvalidFieldCode(char),
+    TrailingPercent,
+    MultipleFileCodes,
+    ListCodeNotStandalone(char),
+}
+
+impl fmt::Display for ExecIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecIssue::ReservedCharacter(c) => {
+                write!(f, "reserved character '{}' must be quoted", c)
+            }
+            ExecIssue::SingleQuotes => {
+                write!(f, "single quotes are not supported, use double quotes")
+            }
+            ExecIssue::InvalidEscape(c) => {
+                write!(f, "'\\{}' is not a valid escape inside quotes", c)
+            }
+            ExecIssue::FieldCodeInQuotes(c) => {
+                write!(f, "field code %{} must not be used inside quotes", c)
+            }
+            ExecIssue::DeprecatedFieldCode(c) => write!(f, "field code %{} is deprecated", c),
+            ExecIssue::InvalidFieldCode(c) => write!(f, "%{} is not a valid field code", c),
+            ExecIssue::TrailingPercent => write!(f, "lone '%' at the end, use %% for a literal"),
+            ExecIssue::MultipleFileCodes => {
+                write!(f, "only one of %f, %F, %u or %U may be used")
+            }
+            ExecIssue::ListCodeNotStandalone(c) => {
+                write!(f, "%{} must be an argument on its own", c)
+            }
+        }
+    }
+}
+
+impl ExecCommand {
+    pub fn parse(input: &str) -> Result<Self, ExecError> {
+        parse_inner(input, &mut Vec::new())
+    }
+
+    /// Parses `input` and reports every tolerated spec violation.
+    pub fn check(input: &str) -> Result<Vec<ExecIssue>, ExecError> {
+        let mut issues = Vec::new();
+        let cmd = parse_inner(input, &mut issues)?;
+        let file_codes = cmd.field_codes().filter(|c| c.takes_files()).count();
+        if file_codes > 1 {
+            issues.push(ExecIssue::MultipleFileCodes);
+        }
+        for arg in &cmd.args {
+            if arg.parts.len() > 1
+                && let Some(code) = arg.field_codes().find(|c| c.is_list())
+            {
+                issues.push(ExecIssue::ListCodeNotStandalone(code.as_char()));
+            }
+        }
+        Ok(issues)
+    }
+
+    /// A command running `argv` verbatim, quoting whatever needs it.
+    pub fn from_argv<S: AsRef<str>>(argv: &[S]) -> Self {
+        Self {
+            args: argv.iter().map(|a| ExecArg::literal(a.as_ref())).collect(),
+        }
+    }
+
+    /// The executable, when the first argument is plain text.
+    pub fn program(&self) -> Option<String> {
+        let first = self.args.first()?;
+        match first.parts.as_slice() {
+            [ArgPart::Text(t)] => Some(t.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn field_codes(&self) -> impl Iterator<Item = FieldCode> + '_ {
+        self.args.iter().flat_map(|a| a.field_codes())
+    }
+
+    pub fn to_exec_string(&self) -> String {
+        self.args
+            .iter()
+            .map(|a| a.to_exec_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn parse_inner(input: &str, issues: &mut Vec<ExecIssue>) -> Result<ExecCommand, ExecError> {
+    let mut args = Vec::new();
+    let mut current: Option<ExecArg> = None;
+    let mut chars = input.trim().chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' | '\n' => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(ExecArg::default);
+                if arg.parts.is_empty() {
+                    arg.parts.push(ArgPart::Text(String::new()));
+                }
+                let mut closed = false;
+                while let Some(q) = chars.next() {
+                    match q {
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' => match chars.next() {
+                            Some(e) if QUOTED_ESCAPES.contains(&e) => arg.push_text(e),
+                            Some(e) => {
+                                issues.push(ExecIssue::InvalidEscape(e));
+                                arg.push_text('\\');
+                                arg.push_text(e);
+                            }
+                            None => return Err(ExecError::UnterminatedQuote),
+                        },
+                        '%' => {
+                            if let Some(code) = read_field_code(&mut chars, arg, issues) {
+                                issues.push(ExecIssue::FieldCodeInQuotes(code));
+                            }
+                        }
+                        _ => arg.push_text(q),
+                    }
+                }
+                if !closed {
+                    return Err(ExecError::UnterminatedQuote);
+                }
+            }
+            '\'' => {
+                // Not part of the spec, but shell-style single quotes are common.
+                issues.push(ExecIssue::SingleQuotes);
+                let arg = current.get_or_insert_with(ExecArg::default);
+                if arg.parts.is_empty() {
+                    arg.parts.push(ArgPart::Text(String::new()));
+                }
+                let mut closed = false;
+                for q in chars.by_ref() {
+                    if q == '\'' {
+                        closed = true;
+                        break;
+                    }
+                    arg.push_text(q);
+                }
+                if !closed {
+                    return Err(ExecError::UnterminatedQuote);
+                }
+            }
+            '%' => {
+                let arg = current.get_or_insert_with(ExecArg::default);
+                read_field_code(&mut chars, arg, issues);
+            }
+            _ => {
+                if RESERVED.contains(&c) {
+                    issues.push(ExecIssue::ReservedCharacter(c));
+                }
+                current.get_or_insert_with(ExecArg::default).push_text(c);
+            }
+        }
+    }
+    if let Some(arg) = current.take() {
+        args.push(arg);
+    }
+    if args.is_empty() {
+        return Err(ExecError::Empty);
+    }
+    Ok(ExecCommand { args })
+}
+
+// Consumes the character after a `%`. Returns the code letter when a field
+// code (rather than `%%`) was read.
+fn read_field_code(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    arg: &mut ExecArg,
+    issues: &mut Vec<ExecIssue>,
+) -> Option<char> {
+    match chars.next() {
+        Some('%') => {
+            arg.push_text('%');
+            None
+        }
+        Some(c) => {
+            let code = FieldCode::from_char(c);
+            match code {
+                FieldCode::Deprecated(c) => issues.push(ExecIssue::DeprecatedFieldCode(c)),
+                FieldCode::Invalid(c) => issues.push(ExecIssue::InvalidFieldCode(c)),
+                _ => {}
+            }
+            arg.parts.push(ArgPart::Field(code));
+            Some(c)
+        }
+        None => {
+            issues.push(ExecIssue::TrailingPercent);
+            arg.push_text('%');
+            None
+        }
+    }
+}
+
+/// Quotes the program part of a command typed by hand when it is a path
+/// containing spaces, e.g. `/opt/My App/run --flag` becomes
+/// `"/opt/My App/run" --flag`. The longest whitespace-delimited prefix that
+/// `is_file` accepts wins; `.AppImage` paths are also accepted so launchers
+/// for not-yet-mounted media keep working. Already quoted input is untouched.
+pub fn normalize_command_line(input: &str, is_file: impl Fn(&str) -> bool) -> String {
+    let input = input.trim();
+    if input.is_empty() || input.starts_with('"') || input.starts_with('\'') {
+        return input.to_string();
+    }
+    let looks_like_path =
+        input.starts_with('/') || input.starts_with("./") || input.starts_with("~/");
+    if !looks_like_path || !input.contains(char::is_whitespace) {
+        return input.to_string();
+    }
+
+    let mut boundaries: Vec<usize> = input
+        .char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .map(|(i, _)| i)
+        .collect();
+    boundaries.push(input.len());
+    for &end in boundaries.iter().rev() {
+        let candidate = input[..end].trim_end();
+        if !candidate.contains(char::is_whitespace) {
+            break;
+        }
+        if is_file(candidate) || candidate.ends_with(".AppImage") {
+            let program = ExecArg::literal(candidate).to_exec_string();
+            return format!("{}{}", program, &input[end..]);
+        }
+    }
+    input.to_string()
+}
+
+/// How sample files change what a launch does, as shown by a dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchNote {
+    /// `%f` or `%u` with several files: one process per file.
+    ProcessPerFile(usize),
+    /// Files were given but no field code receives them.
+    FilesIgnored,
+    /// `%f` or `%F` given a URI that is not a local file.
+    NotLocal(String),
+    /// `%F` or `%U` inside a longer argument: the files are joined into it.
+    FilesJoined(char),
+}
+
+impl fmt::Display for LaunchNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchNote::ProcessPerFile(n) => write!(
+                f,
+                "%f and %u take a single file: {} processes start, one per file",
+                n
+            ),
+            LaunchNote::FilesIgnored => {
+                write!(f, "Exec has no %f, %F, %u or %U: the files are not passed")
+            }
+            LaunchNote::NotLocal(uri) => write!(
+                f,
+                "{} is not a local file; %f and %F pass it unchanged",
+                uri
+            ),
+            LaunchNote::FilesJoined(c) => write!(
+                f,
+                "%{} is inside an argument: all files are joined into it with spaces",
+                c
+            ),
+        }
+    }
+}
+
+/// What field codes expand to when a launcher is started.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LaunchContext<'a> {
+    /// `%c`: the Name in the user's locale.
+    pub name: &'a str,
+    /// `%i`: the Icon key.
+    pub icon: Option<&'a str>,
+    /// `%k`: where the desktop file is.
+    pub desktop_file: Option<&'a str>,
+    /// Files to open, as local paths or URIs.
+    pub files: &'a [String],
+}
+
+impl ExecCommand {
+    /// The argument vectors a desktop starts for `ctx`, after field-code
+    /// substitution. `%f` and `%u` take one file each, so several files give
+    /// one process per file; without a file code the files are not passed.
+    pub fn expand(&self, ctx: &LaunchContext) -> Vec<Vec<String>> {
+        let single =
+            self.field_codes().any(|c| c.takes_files()) && !self.field_codes().any(|c| c.is_list());
+        if single && ctx.files.len() > 1 {
+            ctx.files
+                .iter()
+                .map(|f| self.expand_with(ctx, std::slice::from_ref(f)))
+                .collect()
+        } else {
+            vec![self.expand_with(ctx, ctx.files)]
+        }
+    }
+
+    fn expand_with(&self, ctx: &LaunchContext, files: &[String]) -> Vec<String> {
+        let mut argv = Vec::new();
+        for arg in &self.args {
+            match arg.parts.as_slice() {
+                [ArgPart::Field(FieldCode::Files)] => argv.extend(files.iter().map(|f| as_path(f))),
+                [ArgPart::Field(FieldCode::Urls)] => argv.extend(files.iter().map(|f| as_uri(f))),
+                [ArgPart::Field(FieldCode::Icon)] => {
+                    if let Some(icon) = ctx.icon.filter(|i| !i.is_empty()) {
+                        argv.push("--icon".into());
+                        argv.push(icon.into());
+                    }
+                }
+                parts => {
+                    let mut text = String::new();
+                    for part in parts {
+                        match part {
+                            ArgPart::Text(t) => text.push_str(t),
+                            ArgPart::Field(code) => text.push_str(&expand_code(*code, ctx, files)),
+                        }
+                    }
+                    // An argument made only of codes that expand to nothing
+                    // is left out rather than passed as "".
+                    if !text.is_empty() || arg.field_codes().next().is_none() {
+                        argv.push(text);
+                    }
+                }
+            }
+        }
+        argv
+    }
+}
+
+impl ExecCommand {
+    /// What `expand` does with `files` that a reader of the argv might miss.
+    pub fn launch_notes(&self, files: &[String]) -> Vec<LaunchNote> {
+        let mut notes = Vec::new();
+        if files.is_empty() {
+            return notes;
+        }
+        let codes: Vec<FieldCode> = self.field_codes().collect();
+        if !codes.iter().any(|c| c.takes_files()) {
+            notes.push(LaunchNote::FilesIgnored);
+            return notes;
+        }
+        if !codes.iter().any(|c| c.is_list()) && files.len() > 1 {
+            notes.push(LaunchNote::ProcessPerFile(files.len()));
+        }
+        if codes
+            .iter()
+            .any(|c| matches!(c, FieldCode::File | FieldCode::Files))
+        {
+            notes.extend(
+                files
+                    .iter()
+                    .filter(|f| has_scheme(f) && local_path(f).is_none())
+                    .map(|f| LaunchNote::NotLocal(f.clone())),
+            );
+        }
+        for arg in &self.args {
+            if arg.parts.len() > 1
+                && let Some(code) = arg.field_codes().find(|c| c.is_list())
+            {
+                notes.push(LaunchNote::FilesJoined(code.as_char()));
+            }
+        }
+        notes
+    }
+}
+
+fn expand_code(code: FieldCode, ctx: &LaunchContext, files: &[String]) -> String {
+    let join = |f: fn(&str) -> String| files.iter().map(|s| f(s)).collect::<Vec<_>>().join(" ");
+    match code {
+        FieldCode::File => files.first().map(|f| as_path(f)).unwrap_or_default(),
+        FieldCode::Url => files.first().map(|f| as_uri(f)).unwrap_or_default(),
+        FieldCode::Files => join(as_path),
+        FieldCode::Urls => join(as_uri),
+        FieldCode::Icon => ctx.icon.unwrap_or_default().to_string(),
+        FieldCode::Name => ctx.name.to_string(),
+        FieldCode::DesktopFile => ctx.desktop_file.unwrap_or_default().to_string(),
+        FieldCode::Deprecated(_) | FieldCode::Invalid(_) => String::new(),
+    }
+}
+
+// `%f` and `%F` want file names; a URI that is not a local file is passed
+// unchanged.
+fn as_path(file: &str) -> String {
+    if has_scheme(file) {
+        local_path(file).unwrap_or_else(|| file.to_string())
+    } else {
+        file.to_string()
+    }
+}
+
+fn as_uri(file: &str) -> String {
+    if has_scheme(file) {
+        file.to_string()
+    } else {
+        file_uri(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ArgPart, ExecCommand, ExecError, ExecIssue, FieldCode, LaunchContext, LaunchNote,
+        normalize_command_line,
+    };
+
+    #[test]
+    fn parse_splits_arguments_and_field_codes() {
+        let cmd = ExecCommand::parse("firefox --new-window %u").unwrap();
+        assert_eq!(cmd.args.len(), 3);
+        assert_eq!(cmd.program().as_deref(), Some("firefox"));
+        assert_eq!(cmd.args[2].parts, vec![ArgPart::Field(FieldCode::Url)]);
+    }
+
+    #[test]
+    fn parse_handles_quotes_and_escapes() {
+        let cmd = ExecCommand::parse(r#""/opt/My App/run" "say \"hi\" \$HOME" 100%%"#).unwrap();
+        assert_eq!(
+            cmd,
+            ExecCommand::from_argv(&["/opt/My App/run", "say \"hi\" $HOME", "100%"])
+        );
+    }
+
+    #[test]
+    fn parse_reports_unterminated_quote() {
+        assert_eq!(
+            ExecCommand::parse("\"/opt/app --x"),
+            Err(ExecError::UnterminatedQuote)
+        );
+        assert_eq!(ExecCommand::parse("   "), Err(ExecError::Empty));
+    }
+
+    #[test]
+    fn check_flags_deprecated_invalid_and_misplaced_codes() {
+        let issues = ExecCommand::check("app %d %z --files=%F %u").unwrap();
+        assert!(issues.contains(&ExecIssue::DeprecatedFieldCode('d')));
+        assert!(issues.contains(&ExecIssue::InvalidFieldCode('z')));
+        assert!(issues.contains(&ExecIssue::ListCodeNotStandalone('F')));
+        assert!(issues.contains(&ExecIssue::MultipleFileCodes));
+
+        let issues = ExecCommand::check("sh -c echo;ls").unwrap();
+        assert_eq!(issues, vec![ExecIssue::ReservedCharacter(';')]);
+    }
+
+    #[test]
+    fn serialize_quotes_only_when_needed() {
+        let cmd = ExecCommand::from_argv(&["/opt/My App/run", "--name=$USER", "plain", "50%"]);
+        assert_eq!(
+            cmd.to_exec_string(),
+            r#""/opt/My App/run" "--name=\$USER" plain 50%%"#
+        );
+        let reparsed = ExecCommand::parse(&cmd.to_exec_string()).unwrap();
+        assert_eq!(reparsed, cmd);
+    }
+
+    #[test]
+    fn serialize_keeps_field_codes() {
+        let src = "app --open %F";
+        assert_eq!(ExecCommand::parse(src).unwrap().to_exec_string(), src);
+    }
+
+    #[test]
+    fn normalize_quotes_any_existing_spaced_path() {
+        let exists = |p: &str| p == "/opt/My Tools/bin/tool";
+        assert_eq!(
+            normalize_command_line("/opt/My Tools/bin/tool --verbose %f", exists),
+            "\"/opt/My Tools/bin/tool\" --verbose %f"
+        );
+        assert_eq!(
+            normalize_command_line("/usr/bin/tool --verbose", exists),
+            "/usr/bin/tool --verbose"
+        );
+    }
+
+    #[test]
+    fn expand_substitutes_field_codes() {
+        let files = vec![
+            "/tmp/a b.txt".to_string(),
+            "https://example.org/c".to_string(),
+        ];
+        let ctx = LaunchContext {
+            name: "Viewer",
+            icon: Some("viewer"),
+            desktop_file: Some("/usr/share/applications/viewer.desktop"),
+            files: &files,
+        };
+        let cmd = ExecCommand::parse("viewer %i --title=%c %k %U").unwrap();
+        assert_eq!(
+            cmd.expand(&ctx),
+            vec![vec![
+                "viewer",
+                "--icon",
+                "viewer",
+                "--title=Viewer",
+                "/usr/share/applications/viewer.desktop",
+                "file:///tmp/a%20b.txt",
+                "https://example.org/c",
+            ]]
+        );
+        let per_file = ExecCommand::parse("viewer --open %f").unwrap().expand(&ctx);
+        assert_eq!(
+            per_file,
+            vec![
+                vec!["viewer", "--open", "/tmp/a b.txt"],
+                vec!["viewer", "--open", "https://example.org/c"],
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_drops_empty_codes() {
+        let ctx = LaunchContext {
+            name: "Viewer",
+            ..Default::default()
+        };
+        let cmd = ExecCommand::parse("viewer %i %f %d \"\" %%").unwrap();
+        assert_eq!(cmd.expand(&ctx), vec![vec!["viewer", "", "%"]]);
+    }
+
+    #[test]
+    fn launch_notes_explain_the_argv() {
+        let files = vec!["/tmp/a".to_string(), "sftp://host/b".to_string()];
+        let notes = ExecCommand::parse("app %f").unwrap().launch_notes(&files);
+        assert_eq!(
+            notes,
+            vec![
+                LaunchNote::ProcessPerFile(2),
+                LaunchNote::NotLocal("sftp://host/b".into()),
+            ]
+        );
+        let notes = ExecCommand::parse("app --files=%U")
+            .unwrap()
+            .launch_notes(&files);
+        assert_eq!(notes, vec![LaunchNote::FilesJoined('U')]);
+        let notes = ExecCommand::parse("app").unwrap().launch_notes(&files);
+        assert_eq!(notes, vec![LaunchNote::FilesIgnored]);
+        assert!(
+            ExecCommand::parse("app %f")
+                .unwrap()
+                .launch_notes(&[])
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::exec_command::{ArgPart, ExecArg, ExecCommand};
+use std::fmt;
+
+/// The keys a health check looks at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HealthKey {
+    Exec,
+    TryExec,
+    Icon,
+    Path,
+}
+
+impl HealthKey {
+    pub fn name(self) -> &'static str {
+        match self {
+            HealthKey::Exec => "Exec",
+            HealthKey::TryExec => "TryExec",
+            HealthKey::Icon => "Icon",
+            HealthKey::Path => "Path",
+        }
+    }
+}
+
+/// A key whose value points at something that is not there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthIssue {
+    pub key: HealthKey,
+    /// The program, icon or directory that was not found.
+    pub target: String,
+}
+
+impl fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.key {
+            HealthKey::Exec | HealthKey::TryExec => write!(
+                f,
+                "{}: {} is not an executable file or on PATH",
+                self.key.name(),
+                self.target
+            ),
+            HealthKey::Icon => write!(
+                f,
+                "Icon: {} is neither a file nor in the icon theme",
+                self.target
+            ),
+            HealthKey::Path => write!(f, "Path: {} is not a directory", self.target),
+        }
+    }
+}
+
+/// How the checks look things up, so they can run without a real system.
+pub trait Probe {
+    /// An absolute path to an executable, or a name found on `PATH`.
+    fn program_exists(&self, program: &str) -> bool;
+    /// An icon file, or a name in the icon theme.
+    fn icon_exists(&self, icon: &str) -> bool;
+    fn is_dir(&self, path: &str) -> bool;
+}
+
+/// Everything in `entry` that points nowhere. Hidden entries count as
+/// deleted and are not checked.
+pub fn check_entry(entry: &DesktopEntry, probe: &impl Probe) -> Vec<HealthIssue> {
+    let mut issues = Vec::new();
+    if entry.hidden {
+        return issues;
+    }
+    let mut push = |key, target: &str| {
+        issues.push(HealthIssue {
+            key,
+            target: target.to_string(),
+        })
+    };
+    if entry.type_field == "Application" {
+        if let Some(program) = exec_program(&entry.exec)
+            && !probe.program_exists(&program)
+        {
+            push(HealthKey::Exec, &program);
+        }
+        if let Some(try_exec) = entry.try_exec.as_deref().filter(|t| !t.trim().is_empty())
+            && !probe.program_exists(try_exec)
+        {
+            push(HealthKey::TryExec, try_exec);
+        }
+    }
+    if let Some(icon) = entry.icon.as_deref().filter(|i| !i.trim().is_empty())
+        && !probe.icon_exists(icon)
+    {
+        push(HealthKey::Icon, icon);
+    }
+    if let Some(dir) = entry.path.as_deref().filter(|p| !p.trim().is_empty())
+        && !probe.is_dir(dir)
+    {
+        push(HealthKey::Path, dir);
+    }
+    issues
+}
+
+/// The program an `Exec` line runs, looking past a leading `env` and its
+/// `NAME=value` assignments.
+pub fn exec_program(exec: &str) -> Option<String> {
+    let command = ExecCommand::parse(exec).ok()?;
+    let idx = program_index(&command)?;
+    match command.args[idx].parts.as_slice() {
+        [ArgPart::Text(t)] => Some(t.clone()),
+        _ => None,
+    }
+}
+
+/// `entry` with the missing `issue.target` replaced by `replacement`. A
+/// relinked program is also replaced in `TryExec` and in actions that run
+/// the same program.
+pub fn relink(entry: &DesktopEntry, issue: &HealthIssue, replacement: &str) -> DesktopEntry {
+    let mut entry = entry.clone();
+    match issue.key {
+        HealthKey::Exec | HealthKey::TryExec => {
+            entry.exec = replace_program(&entry.exec, &issue.target, replacement);
+            for action in &mut entry.actions {
+                action.exec = replace_program(&action.exec, &issue.target, replacement);
+            }
+            if issue.key == HealthKey::TryExec
+                || entry.try_exec.as_deref() == Some(issue.target.as_str())
+            {
+                entry.try_exec = Some(replacement.to_string());
+            }
+        }
+        HealthKey::Icon => {
+            entry.icon = Some(replacement.to_string());
+            for action in &mut entry.actions {
+                if action.icon.as_deref() == Some(issue.target.as_str()) {
+                    action.icon = Some(replacement.to_string());
+                }
+            }
+        }
+        HealthKey::Path => entry.path = Some(replacement.to_string()),
+    }
+    entry
+}
+
+fn program_index(command: &ExecCommand) -> Option<usize> {
+    let text = |idx: usize| match command.args.get(idx)?.parts.as_slice() {
+        [ArgPart::Text(t)] => Some(t.as_str()),
+        _ => None,
+    };
+    if text(0)?.rsplit('/').next() != Some("env") {
+        return Some(0);
+    }
+    (1..command.args.len()).find(|&idx| text(idx).is_none_or(|t| !t.contains('=')))
+}
+
+fn replace_program(exec: &str, old: &str, new: &str) -> String {
+    let Ok(mut command) = ExecCommand::parse(exec) else {
+        return exec.to_string();
+    };
+    match program_index(&command) {
+        Some(idx) if exec_program(exec).as_deref() == Some(old) => {
+            command.args[idx] = ExecArg::literal(new);
+            command.to_exec_string()
+        }
+        _ => exec.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HealthIssue, HealthKey, Probe, check_entry, exec_program, relink};
+    use crate::domain::desktop_entry::DesktopEntry;
+
+    struct Fake;
+
+    impl Probe for Fake {
+        fn program_exists(&self, program: &str) -> bool {
+            program == "/usr/bin/tool" || program == "sh"
+        }
+        fn icon_exists(&self, icon: &str) -> bool {
+            icon == "tool"
+        }
+        fn is_dir(&self, path: &str) -> bool {
+            path == "/srv"
+        }
+    }
+
+    const BROKEN: &str = "[Desktop Entry]\nType=Application\nName=Tool\nExec=env LANG=C /opt/Tool.AppImage --run %f\nTryExec=/opt/Tool.AppImage\nIcon=/opt/tool.png\nPath=/opt/tool\nActions=new;\n\n[Desktop Action new]\nName=New\nExec=/opt/Tool.AppImage --new\n";
+
+    #[test]
+    fn finds_dangling_keys() {
+        let entry = DesktopEntry::from_ini_string(BROKEN);
+        let keys: Vec<HealthKey> = check_entry(&entry, &Fake).iter().map(|i| i.key).collect();
+        assert_eq!(
+            keys,
+            vec![
+                HealthKey::Exec,
+                HealthKey::TryExec,
+                HealthKey::Icon,
+                HealthKey::Path
+            ]
+        );
+        let fine = DesktopEntry::from_ini_string(
+            "[Desktop Entry]\nType=Application\nName=Tool\nExec=sh -c true\nIcon=tool\nPath=/srv\n",
+        );
+        assert!(check_entry(&fine, &Fake).is_empty());
+    }
+
+    #[test]
+    fn relinks_every_use_of_the_program() {
+        let entry = DesktopEntry::from_ini_string(BROKEN);
+        assert_eq!(
+            exec_program(&entry.exec).as_deref(),
+            Some("/opt/Tool.AppImage")
+        );
+        let issue = HealthIssue {
+            key: HealthKey::Exec,
+            target: "/opt/Tool.AppImage".into(),
+        };
+        let fixed = relink(&entry, &issue, "/usr/bin/tool");
+        assert_eq!(fixed.exec, "env LANG=C /usr/bin/tool --run %f");
+        assert_eq!(fixed.try_exec.as_deref(), Some("/usr/bin/tool"));
+        assert_eq!(fixed.actions[0].exec, "/usr/bin/tool --new");
+        let keys: Vec<HealthKey> = check_entry(&fixed, &Fake).iter().map(|i| i.key).collect();
+        assert_eq!(keys, vec![HealthKey::Icon, HealthKey::Path]);
+    }
+}
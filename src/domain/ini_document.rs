@@ -0,0 +1,474 @@
+use std::fmt;
+
+/// Line-preserving model of a key file. Comments, blank lines, unknown groups,
+/// key order and duplicate keys are kept so that a file can be rewritten with
+/// only the lines that actually changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IniDocument {
+    lines: Vec<IniLine>,
+    trailing_newline: bool,
+    crlf: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum IniLine {
+    Blank(String),
+    Comment(String),
+    Group {
+        raw: String,
+        name: String,
+    },
+    Entry {
+        raw: String,
+        key: String,
+        value: String,
+    },
+    Invalid(String),
+}
+
+/// Read-only view of one line, as yielded by [`IniDocument::items`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IniItem<'a> {
+    Blank,
+    Comment,
+    Group(&'a str),
+    Entry { key: &'a str, value: &'a str },
+    Invalid(&'a str),
+}
+
+/// A group as produced by a serializer: its name and its ordered key/value pairs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IniGroup {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
+impl IniGroup {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((key.into(), value.into()));
+    }
+}
+
+impl IniLine {
+    fn parse(raw: &str) -> Self {
+        let line = raw.trim();
+        if line.is_empty() {
+            IniLine::Blank(raw.to_string())
+        } else if line.starts_with('#') || line.starts_with(';') {
+            IniLine::Comment(raw.to_string())
+        } else if line.starts_with('[') && line.ends_with(']') {
+            IniLine::Group {
+                raw: raw.to_string(),
+                name: line[1..line.len() - 1].to_string(),
+            }
+        } else if let Some((k, v)) = line.split_once('=') {
+            IniLine::Entry {
+                raw: raw.to_string(),
+                key: k.trim().to_string(),
+                value: v.trim().to_string(),
+            }
+        } else {
+            IniLine::Invalid(raw.to_string())
+        }
+    }
+
+    fn raw(&self) -> &str {
+        match self {
+            IniLine::Blank(raw)
+            | IniLine::Comment(raw)
+            | IniLine::Invalid(raw)
+            | IniLine::Group { raw, .. }
+            | IniLine::Entry { raw, .. } => raw,
+        }
+    }
+}
+
+impl IniDocument {
+    pub fn parse(content: &str) -> Self {
+        let trailing_newline = content.ends_with('\n');
+        let body = content.strip_suffix('\n').unwrap_or(content);
+        let lines: Vec<IniLine> = if content.is_empty() {
+            Vec::new()
+        } else {
+            body.split('\n').map(IniLine::parse).collect()
+        };
+        let crlf = lines.first().is_some_and(|l| l.raw().ends_with('\r'));
+        Self {
+            lines,
+            trailing_newline,
+            crlf,
+        }
+    }
+
+    /// Every line with its 1-based line number.
+    pub fn items(&self) -> impl Iterator<Item = (usize, IniItem<'_>)> {
+        self.lines.iter().enumerate().map(|(idx, line)| {
+            let item = match line {
+                IniLine::Blank(_) => IniItem::Blank,
+                IniLine::Comment(_) => IniItem::Comment,
+                IniLine::Group { name, .. } => IniItem::Group(name),
+                IniLine::Entry { key, value, .. } => IniItem::Entry { key, value },
+                IniLine::Invalid(raw) => IniItem::Invalid(raw.trim()),
+            };
+            (idx + 1, item)
+        })
+    }
+
+    /// Key/value pairs of every group called `group`, in file order.
+    pub fn entries(&self, group: &str) -> Vec<(&str, &str)> {
+        let mut current: Option<&str> = None;
+        let mut out = Vec::new();
+        for line in &self.lines {
+            match line {
+                IniLine::Group { name, .. } => current = Some(name),
+                IniLine::Entry { key, value, .. } if current == Some(group) => {
+                    out.push((key.as_str(), value.as_str()))
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    /// Rewrites the document so that the `managed` groups hold exactly the
+    /// entries of `target`, touching as few lines as possible:
+    /// - values considered equal by `same_value(key, old, new)` keep their line,
+    /// - changed values are rewritten in place,
+    /// - new keys are inserted after the key that precedes them in `target`,
+    /// - keys missing from `target` are removed,
+    /// - managed groups missing from `target` are dropped entirely,
+    /// - groups that are neither managed nor in `target` are left untouched.
+    ///
+    /// When a key appears several times, occurrences are paired from the last
+    /// one backwards (the last value wins when parsing); extra leading
+    /// duplicates are left as they are.
+    pub fn merge(
+        &mut self,
+        target: &[IniGroup],
+        managed: &[String],
+        same_value: impl Fn(&str, &str, &str) -> bool,
+    ) {
+        let sections = self.split_sections();
+        let mut out: Vec<IniLine> = Vec::new();
+        let mut merged: Vec<&str> = Vec::new();
+        // Whether the document now ends in lines that were not in it.
+        let mut appended = false;
+
+        for (header, body) in sections {
+            let name = match &header {
+                Some(IniLine::Group { name, .. }) => name.clone(),
+                _ => {
+                    appended = false;
+                    out.extend(body);
+                    continue;
+                }
+            };
+            let wanted = target.iter().find(|g| g.name == name);
+            let already = merged.contains(&name.as_str());
+            match wanted {
+                Some(group) if !already => {
+                    merged.push(&group.name);
+                    out.extend(header);
+                    let (lines, at_end) = self.merge_body(body, group, &same_value);
+                    appended = at_end;
+                    out.extend(lines);
+                }
+                None if managed.contains(&name) && !already => {}
+                _ => {
+                    appended = false;
+                    out.extend(header);
+                    out.extend(body);
+                }
+            }
+        }
+
+        for group in target.iter().filter(|g| !merged.contains(&g.name.as_str())) {
+            appended = true;
+            if out.last().is_some_and(|l| !matches!(l, IniLine::Blank(_))) {
+                out.push(IniLine::Blank(self.line_ending(String::new())));
+            }
+            out.push(IniLine::parse(
+                &self.line_ending(format!("[{}]", group.name)),
+            ));
+            for (k, v) in &group.entries {
+                out.push(self.entry_line(k, v));
+            }
+        }
+
+        self.lines = out;
+        // A file without a final newline keeps it that way, unless new lines
+        // now follow what used to be its last one.
+        if appended {
+            self.trailing_newline = true;
+        }
+    }
+
+    fn merge_body(
+        &self,
+        body: Vec<IniLine>,
+        group: &IniGroup,
+        same_value: &impl Fn(&str, &str, &str) -> bool,
+    ) -> (Vec<IniLine>, bool) {
+        // Pair each target entry with a body line, last occurrences first.
+        let mut matched: Vec<Option<usize>> = vec![None; group.entries.len()];
+        let mut used = vec![false; body.len()];
+        for t in (0..group.entries.len()).rev() {
+            let key = &group.entries[t].0;
+            let found = (0..body.len()).rev().find(|&i| {
+                !used[i] && matches!(&body[i], IniLine::Entry { key: k, .. } if k == key)
+            });
+            if let Some(i) = found {
+                used[i] = true;
+                matched[t] = Some(i);
+            }
+        }
+
+        // Body entries whose key disappeared from the target are removed;
+        // unmatched duplicates of a key that is still present are kept.
+        let keep = |i: usize, line: &IniLine| match line {
+            IniLine::Entry { key, .. } => used[i] || group.entries.iter().any(|(k, _)| k == key),
+            _ => true,
+        };
+
+        // New entries are anchored after the closest preceding matched entry.
+        let mut inserts_after: Vec<Vec<usize>> = vec![Vec::new(); body.len()];
+        let mut inserts_front: Vec<usize> = Vec::new();
+        let mut anchor: Option<usize> = None;
+        for (t, m) in matched.iter().enumerate() {
+            match (m, anchor) {
+                (Some(i), _) => anchor = Some(*i),
+                (None, Some(a)) => inserts_after[a].push(t),
+                (None, None) => inserts_front.push(t),
+            }
+        }
+
+        // Entries with no matched predecessor go before the first entry, or
+        // after the last non-blank line of a group without entries.
+        let front = body
+            .iter()
+            .position(|l| matches!(l, IniLine::Entry { .. }))
+            .or_else(|| {
+                body.iter()
+                    .rposition(|l| !matches!(l, IniLine::Blank(_)))
+                    .map(|i| i + 1)
+            })
+            .unwrap_or(0);
+        let body_len = body.len();
+
+        let mut out = Vec::new();
+        for (i, line) in body.into_iter().enumerate() {
+            if i == front {
+                out.extend(inserts_front.iter().map(|&t| self.target_line(group, t)));
+            }
+            if !keep(i, &line) {
+                continue;
+            }
+            let t_for_line = matched.iter().position(|m| *m == Some(i));
+            match (t_for_line, &line) {
+                (Some(t), IniLine::Entry { key, value, .. })
+                    if !same_value(key, value, &group.entries[t].1) =>
+                {
+                    out.push(self.target_line(group, t));
+                }
+                _ => out.push(line),
+            }
+            out.extend(inserts_after[i].iter().map(|&t| self.target_line(group, t)));
+        }
+        let at_end = (front == body_len && !inserts_front.is_empty())
+            || inserts_after.last().is_some_and(|v| !v.is_empty());
+        if front == body_len {
+            out.extend(inserts_front.iter().map(|&t| self.target_line(group, t)));
+        }
+        (out, at_end)
+    }
+
+    fn split_sections(&self) -> Vec<(Option<IniLine>, Vec<IniLine>)> {
+        let mut sections: Vec<(Option<IniLine>, Vec<IniLine>)> = vec![(None, Vec::new())];
+        for line in &self.lines {
+            match line {
+                IniLine::Group { .. } => sections.push((Some(line.clone()), Vec::new())),
+                _ => {
+                    if let Some(last) = sections.last_mut() {
+                        last.1.push(line.clone());
+                    }
+                }
+            }
+        }
+        sections
+    }
+
+    fn target_line(&self, group: &IniGroup, t: usize) -> IniLine {
+        let (k, v) = &group.entries[t];
+        self.entry_line(k, v)
+    }
+
+    fn entry_line(&self, key: &str, value: &str) -> IniLine {
+        IniLine::Entry {
+            raw: self.line_ending(format!("{}={}", key, value)),
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn line_ending(&self, line: String) -> String {
+        if self.crlf { line + "\r" } else { line }
+    }
+}
+
+/// Renders groups from scratch, one blank line between groups.
+pub fn render_groups(groups: &[IniGroup]) -> String {
+    let mut s = String::new();
+    for (idx, group) in groups.iter().enumerate() {
+        if idx > 0 {
+            s.push('\n');
+        }
+        s.push_str(&format!("[{}]\n", group.name));
+        for (k, v) in &group.entries {
+            s.push_str(&format!("{}={}\n", k, v));
+        }
+    }
+    s
+}
+
+impl fmt::Display for IniDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, line) in self.lines.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("\n")?;
+            }
+            f.write_str(line.raw())?;
+        }
+        if self.trailing_newline {
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IniDocument, IniGroup};
+
+    fn group(name: &str, entries: &[(&str, &str)]) -> IniGroup {
+        IniGroup {
+            name: name.into(),
+            entries: entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    fn exact(_: &str, a: &str, b: &str) -> bool {
+        a == b
+    }
+
+    #[test]
+    fn parse_and_render_is_identity() {
+        let input =
+            "# header\n[Desktop Entry]\nName = App\n\n; note\nweird line\n[X-Other]\nA=1\nA=2";
+        let doc = IniDocument::parse(input);
+        assert_eq!(doc.to_string(), input);
+        assert_eq!(doc.entries("X-Other"), vec![("A", "1"), ("A", "2")]);
+    }
+
+    #[test]
+    fn merge_rewrites_only_changed_lines() {
+        let input = "[Desktop Entry]\n# keep me\nName=Old\nExec=app\n\n[X-Vendor]\nKey=1\n";
+        let mut doc = IniDocument::parse(input);
+        let target = [group("Desktop Entry", &[("Name", "New"), ("Exec", "app")])];
+        doc.merge(&target, &["Desktop Entry".into()], exact);
+        assert_eq!(
+            doc.to_string(),
+            "[Desktop Entry]\n# keep me\nName=New\nExec=app\n\n[X-Vendor]\nKey=1\n"
+        );
+    }
+
+    #[test]
+    fn merge_keeps_a_missing_final_newline() {
+        let input = "[Desktop Entry]\nName=App\nExec=app";
+        let mut doc = IniDocument::parse(input);
+        let target = [group("Desktop Entry", &[("Name", "App"), ("Exec", "app")])];
+        doc.merge(&target, &["Desktop Entry".into()], exact);
+        assert_eq!(doc.to_string(), input);
+
+        let target = [group("Desktop Entry", &[("Name", "New"), ("Exec", "app")])];
+        doc.merge(&target, &["Desktop Entry".into()], exact);
+        assert_eq!(doc.to_string(), "[Desktop Entry]\nName=New\nExec=app");
+
+        let target = [group(
+            "Desktop Entry",
+            &[("Name", "New"), ("Exec", "app"), ("Icon", "app")],
+        )];
+        doc.merge(&target, &["Desktop Entry".into()], exact);
+        assert_eq!(
+            doc.to_string(),
+            "[Desktop Entry]\nName=New\nExec=app\nIcon=app\n"
+        );
+    }
+
+    #[test]
+    fn merge_inserts_after_predecessor_and_removes_missing_keys() {
+        let input = "[Desktop Entry]\nType=Application\nName=App\nIcon=app\nExec=app\n";
+        let mut doc = IniDocument::parse(input);
+        let target = [group(
+            "Desktop Entry",
+            &[
+                ("Type", "Application"),
+                ("Name", "App"),
+                ("Comment", "Hello"),
+                ("Exec", "app"),
+            ],
+        )];
+        doc.merge(&target, &["Desktop Entry".into()], exact);
+        assert_eq!(
+            doc.to_string(),
+            "[Desktop Entry]\nType=Application\nName=App\nComment=Hello\nExec=app\n"
+        );
+    }
+
+    #[test]
+    fn merge_keeps_leading_duplicates_and_updates_last() {
+        let input = "[Desktop Entry]\nName=First\nName=Second\n";
+        let mut doc = IniDocument::parse(input);
+        let target = [group("Desktop Entry", &[("Name", "Third")])];
+        doc.merge(&target, &["Desktop Entry".into()], exact);
+        assert_eq!(doc.to_string(), "[Desktop Entry]\nName=First\nName=Third\n");
+    }
+
+    #[test]
+    fn merge_drops_managed_groups_and_appends_new_ones() {
+        let input =
+            "[Desktop Entry]\nName=App\n\n[Desktop Action old]\nName=Old\n\n[X-Keep]\nA=1\n";
+        let mut doc = IniDocument::parse(input);
+        let target = [
+            group("Desktop Entry", &[("Name", "App")]),
+            group("Desktop Action new", &[("Name", "New")]),
+        ];
+        doc.merge(
+            &target,
+            &["Desktop Entry".into(), "Desktop Action old".into()],
+            exact,
+        );
+        assert_eq!(
+            doc.to_string(),
+            "[Desktop Entry]\nName=App\n\n[X-Keep]\nA=1\n\n[Desktop Action new]\nName=New\n"
+        );
+    }
+
+    #[test]
+    fn merge_preserves_crlf_line_endings() {
+        let input = "[Desktop Entry]\r\nName=Old\r\n";
+        let mut doc = IniDocument::parse(input);
+        let target = [group("Desktop Entry", &[("Name", "New")])];
+        doc.merge(&target, &["Desktop Entry".into()], exact);
+        assert_eq!(doc.to_string(), "[Desktop Entry]\r\nName=New\r\n");
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+
+/// A POSIX locale as used in key suffixes: `lang_COUNTRY.ENCODING@MODIFIER`,
+/// where everything but `lang` is optional.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    pub lang: String,
+    pub country: Option<String>,
+    pub encoding: Option<String>,
+    pub modifier: Option<String>,
+}
+
+impl Locale {
+    pub fn parse(input: &str) -> Option<Self> {
+        let (rest, modifier) = match input.split_once('@') {
+            Some((r, m)) => (r, Some(m)),
+            None => (input, None),
+        };
+        let (rest, encoding) = match rest.split_once('.') {
+            Some((r, e)) => (r, Some(e)),
+            None => (rest, None),
+        };
+        let (lang, country) = match rest.split_once('_') {
+            Some((l, c)) => (l, Some(c)),
+            None => (rest, None),
+        };
+
+        let lang_ok = (2..=3).contains(&lang.len()) && lang.chars().all(|c| c.is_ascii_lowercase());
+        let country_ok = country.is_none_or(|c| {
+            (2..=4).contains(&c.len()) && c.chars().all(|ch| ch.is_ascii_alphanumeric())
+        });
+        let encoding_ok = encoding.is_none_or(|e| {
+            !e.is_empty()
+                && e.chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        });
+        let modifier_ok = modifier
+            .is_none_or(|m| !m.is_empty() && m.chars().all(|ch| ch.is_ascii_alphanumeric()));
+        if !(lang_ok && country_ok && encoding_ok && modifier_ok) {
+            return None;
+        }
+
+        Some(Self {
+            lang: lang.to_string(),
+            country: country.map(str::to_string),
+            encoding: encoding.map(str::to_string),
+            modifier: modifier.map(str::to_string),
+        })
+    }
+
+    /// The locale for translated messages, from `LC_ALL`, `LC_MESSAGES` or
+    /// `LANG`. `None` for the C locale.
+    pub fn current() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|v| !v.is_empty())
+            .and_then(|v| Self::parse(&v))
+    }
+
+    /// Key suffixes to try, best first, as the spec orders them:
+    /// `lang_COUNTRY@MODIFIER`, `lang_COUNTRY`, `lang@MODIFIER`, `lang`.
+    /// The encoding never takes part in matching.
+    pub fn candidates(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        if let (Some(c), Some(m)) = (&self.country, &self.modifier) {
+            out.push(format!("{}_{}@{}", self.lang, c, m));
+        }
+        if let Some(c) = &self.country {
+            out.push(format!("{}_{}", self.lang, c));
+        }
+        if let Some(m) = &self.modifier {
+            out.push(format!("{}@{}", self.lang, m));
+        }
+        out.push(self.lang.clone());
+        out
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lang)?;
+        if let Some(c) = &self.country {
+            write!(f, "_{}", c)?;
+        }
+        if let Some(e) = &self.encoding {
+            write!(f, ".{}", e)?;
+        }
+        if let Some(m) = &self.modifier {
+            write!(f, "@{}", m)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits `Name[fr_FR]` into `("Name", Some("fr_FR"))`.
+pub fn split_locale_key(key: &str) -> (&str, Option<&str>) {
+    match key.strip_suffix(']').and_then(|k| k.split_once('[')) {
+        Some((base, locale)) => (base, Some(locale)),
+        None => (key, None),
+    }
+}
+
+/// The translations of one key (`Key[locale]=value`), in file order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Translations<T> {
+    entries: Vec<(String, T)>,
+}
+
+impl<T> Default for Translations<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> Translations<T> {
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.entries.iter().map(|(l, v)| (l.as_str(), v))
+    }
+
+    /// Sets the value for `locale`, keeping its position when it exists.
+    pub fn set(&mut self, locale: impl Into<String>, value: T) {
+        let locale = locale.into();
+        match self.entries.iter_mut().find(|(l, _)| *l == locale) {
+            Some(slot) => slot.1 = value,
+            None => self.entries.push((locale, value)),
+        }
+    }
+
+    /// The best translation for `locale` following the spec's matching
+    /// rules, or `None` when only the untranslated value applies.
+    pub fn lookup(&self, locale: &Locale) -> Option<&T> {
+        locale.candidates().iter().find_map(|wanted| {
+            self.entries
+                .iter()
+                .find(|(l, _)| strip_encoding(l) == *wanted)
+                .map(|(_, v)| v)
+        })
+    }
+}
+
+impl<T> FromIterator<(String, T)> for Translations<T> {
+    fn from_iter<I: IntoIterator<Item = (String, T)>>(iter: I) -> Self {
+        let mut out = Self::default();
+        for (locale, value) in iter {
+            out.set(locale, value);
+        }
+        out
+    }
+}
+
+fn strip_encoding(locale: &str) -> String {
+    match locale.split_once('.') {
+        Some((head, rest)) => match rest.split_once('@') {
+            Some((_, modifier)) => format!("{}@{}", head, modifier),
+            None => head.to_string(),
+        },
+        None => locale.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Locale, Translations, split_locale_key};
+
+    #[test]
+    fn parse_full_locale() {
+        let l = Locale::parse("sr_RS.UTF-8@latin").unwrap();
+        assert_eq!(l.lang, "sr");
+        assert_eq!(l.country.as_deref(), Some("RS"));
+        assert_eq!(l.encoding.as_deref(), Some("UTF-8"));
+        assert_eq!(l.modifier.as_deref(), Some("latin"));
+        assert_eq!(l.to_string(), "sr_RS.UTF-8@latin");
+    }
+
+    #[test]
+    fn parse_rejects_malformed_locales() {
+        for bad in ["", "French", "fr-FR", "FR", "fr_", "fr@", "f"] {
+            assert!(Locale::parse(bad).is_none(), "{bad} should be rejected");
+        }
+        assert!(Locale::parse("ca@valencia").is_some());
+        assert!(Locale::parse("es_419").is_some());
+    }
+
+    #[test]
+    fn split_locale_key_handles_plain_keys() {
+        assert_eq!(split_locale_key("Name[de]"), ("Name", Some("de")));
+        assert_eq!(split_locale_key("Name"), ("Name", None));
+    }
+
+    #[test]
+    fn lookup_follows_spec_matching_order() {
+        let t: Translations<&str> = [
+            ("sr".to_string(), "sr"),
+            ("sr@latin".to_string(), "sr@latin"),
+            ("sr_RS".to_string(), "sr_RS"),
+            ("de_DE.UTF-8".to_string(), "de_DE"),
+        ]
+        .into_iter()
+        .collect();
+        let at = |l: &str| t.lookup(&Locale::parse(l).unwrap()).copied();
+        assert_eq!(at("sr_RS.UTF-8@latin"), Some("sr_RS"));
+        assert_eq!(at("sr_ME@latin"), Some("sr@latin"));
+        assert_eq!(at("sr_ME"), Some("sr"));
+        assert_eq!(at("de_DE"), Some("de_DE"));
+        assert_eq!(at("de_AT"), None);
+        assert_eq!(at("fr"), None);
+    }
+
+    #[test]
+    fn set_replaces_in_place() {
+        let mut t = Translations::default();
+        t.set("de", 1);
+        t.set("fr", 2);
+        t.set("de", 3);
+        assert_eq!(t.iter().collect::<Vec<_>>(), vec![("de", &3), ("fr", &2)]);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::domain::desktop_entry::{join_list, split_list};
+use crate::domain::ini_document::{IniDocument, IniGroup};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_GROUP: &str = "Default Applications";
+pub const ADDED_GROUP: &str = "Added Associations";
+pub const REMOVED_GROUP: &str = "Removed Associations";
+
+/// One `mimeapps.list`: desktop-file IDs per MIME type, in each of its
+/// three groups.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MimeApps {
+    pub defaults: BTreeMap<String, Vec<String>>,
+    pub added: BTreeMap<String, Vec<String>>,
+    pub removed: BTreeMap<String, Vec<String>>,
+}
+
+impl MimeApps {
+    pub fn parse(content: &str) -> Self {
+        let doc = IniDocument::parse(content);
+        let read = |group: &str| -> BTreeMap<String, Vec<String>> {
+            doc.entries(group)
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), split_list(v)))
+                .collect()
+        };
+        Self {
+            defaults: read(DEFAULT_GROUP),
+            added: read(ADDED_GROUP),
+            removed: read(REMOVED_GROUP),
+        }
+    }
+}
+
+/// The default for `mime` across `files`, highest priority first: the first
+/// listed ID that is `installed`, with the index of the file naming it.
+pub fn resolve_default(
+    files: &[MimeApps],
+    mime: &str,
+    installed: impl Fn(&str) -> bool,
+) -> Option<(usize, String)> {
+    files.iter().enumerate().find_map(|(idx, file)| {
+        let id = file.defaults.get(mime)?.iter().find(|id| installed(id))?;
+        Some((idx, id.clone()))
+    })
+}
+
+/// Whether `id`, which declares `mime`, is still associated with it: the
+/// highest-priority file that adds or removes the pair decides.
+pub fn is_associated(files: &[MimeApps], mime: &str, id: &str) -> bool {
+    let lists = |map: &BTreeMap<String, Vec<String>>| {
+        map.get(mime).is_some_and(|ids| ids.iter().any(|i| i == id))
+    };
+    files
+        .iter()
+        .find_map(|f| {
+            if lists(&f.added) {
+                Some(true)
+            } else if lists(&f.removed) {
+                Some(false)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(true)
+}
+
+/// `content` with `id` as the default for `mime`: first in its
+/// `Default Applications` and `Added Associations` lists and out of
+/// `Removed Associations`. Other lines are left as they are.
+pub fn set_default(content: &str, mime: &str, id: &str) -> String {
+    edit(content, mime, |group, ids| match group {
+        DEFAULT_GROUP | ADDED_GROUP => {
+            ids.retain(|i| i != id);
+            ids.insert(0, id.to_string());
+        }
+        _ => ids.retain(|i| i != id),
+    })
+}
+
+/// `content` with `id` no longer the default for `mime`. Its association
+/// is kept.
+pub fn unset_default(content: &str, mime: &str, id: &str) -> String {
+    edit(content, mime, |group, ids| {
+        if group == DEFAULT_GROUP {
+            ids.retain(|i| i != id)
+        }
+    })
+}
+
+// Rewrites the `mime` key of the three groups through `change`; keys left
+// with no IDs are removed, and so are groups left with no keys.
+fn edit(content: &str, mime: &str, change: impl Fn(&str, &mut Vec<String>)) -> String {
+    let mut doc = IniDocument::parse(content);
+    let managed = [DEFAULT_GROUP, ADDED_GROUP, REMOVED_GROUP].map(String::from);
+    let mut target = Vec::new();
+    for name in &managed {
+        let mut group = IniGroup::new(name.as_str());
+        let mut found = false;
+        for (key, value) in doc.entries(name) {
+            // The last of duplicate keys is the one that counts.
+            if let Some(pos) = group.entries.iter().position(|(k, _)| k == key) {
+                group.entries.remove(pos);
+            }
+            let value = if key == mime {
+                found = true;
+                let mut ids = split_list(value);
+                change(name, &mut ids);
+                join_list(&ids)
+            } else {
+                value.to_string()
+            };
+            group.push(key, value);
+        }
+        if !found {
+            let mut ids = Vec::new();
+            change(name, &mut ids);
+            group.push(mime, join_list(&ids));
+        }
+        group.entries.retain(|(_, v)| !v.is_empty());
+        if !group.entries.is_empty() {
+            target.push(group);
+        }
+    }
+    doc.merge(&target, &managed, |_, old, new| old == new);
+    doc.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MimeApps, is_associated, resolve_default, set_default, unset_default};
+
+    const USER: &str = "# mine\n[Default Applications]\ntext/plain=gedit.desktop;\nx-scheme-handler/http=firefox.desktop;\n\n[Removed Associations]\ntext/html=editor.desktop;\n";
+
+    #[test]
+    fn resolves_by_file_priority() {
+        let files = [
+            MimeApps::parse("[Default Applications]\ntext/plain=gone.desktop;\n"),
+            MimeApps::parse(USER),
+            MimeApps::parse("[Default Applications]\ntext/html=firefox.desktop\n"),
+        ];
+        let installed = |id: &str| id != "gone.desktop";
+        assert_eq!(
+            resolve_default(&files, "text/plain", installed),
+            Some((1, "gedit.desktop".into()))
+        );
+        assert_eq!(
+            resolve_default(&files, "text/html", installed),
+            Some((2, "firefox.desktop".into()))
+        );
+        assert_eq!(resolve_default(&files, "image/png", installed), None);
+        assert!(!is_associated(&files, "text/html", "editor.desktop"));
+        assert!(is_associated(&files, "text/plain", "editor.desktop"));
+    }
+
+    #[test]
+    fn sets_and_unsets_a_default_in_place() {
+        let set = set_default(USER, "text/html", "editor.desktop");
+        assert_eq!(
+            set,
+            "# mine\n[Default Applications]\ntext/plain=gedit.desktop;\nx-scheme-handler/http=firefox.desktop;\ntext/html=editor.desktop;\n\n[Added Associations]\ntext/html=editor.desktop;\n"
+        );
+        let set = set_default(&set, "text/plain", "editor.desktop");
+        let parsed = MimeApps::parse(&set);
+        assert_eq!(
+            parsed.defaults["text/plain"],
+            vec!["editor.desktop", "gedit.desktop"]
+        );
+        let unset = unset_default(&set, "text/plain", "editor.desktop");
+        let parsed = MimeApps::parse(&unset);
+        assert_eq!(parsed.defaults["text/plain"], vec!["gedit.desktop"]);
+        assert!(parsed.added["text/plain"].contains(&"editor.desktop".to_string()));
+        assert_eq!(
+            set_default("", "x-scheme-handler/irc", "chat.desktop"),
+            "[Default Applications]\nx-scheme-handler/irc=chat.desktop;\n\n[Added Associations]\nx-scheme-handler/irc=chat.desktop;\n"
+        );
+    }
+}
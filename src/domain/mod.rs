@@ -1 +1,12 @@
+pub mod autostart;
+pub mod categories;
 pub mod desktop_entry;
+pub mod entry_diff;
+pub mod exec_command;
+pub mod health;
+pub mod ini_document;
+pub mod locale;
+pub mod mime_apps;
+pub mod po_catalog;
+pub mod uri;
+pub mod validation;
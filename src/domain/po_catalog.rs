@@ -0,0 +1,343 @@
+use crate::domain::desktop_entry::{DesktopEntry, join_list, split_list};
+use std::fmt;
+
+/// One message of a gettext catalog.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoMessage {
+    /// `#.` lines: which key the string comes from.
+    pub comments: Vec<String>,
+    /// `#:` lines: which file the string comes from.
+    pub references: Vec<String>,
+    pub fuzzy: bool,
+    pub msgid: String,
+    pub msgstr: String,
+}
+
+/// A `.po` or `.pot` file. The header (the `msgid ""` message) is kept
+/// apart from the messages.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoCatalog {
+    pub header: String,
+    pub messages: Vec<PoMessage>,
+}
+
+/// Outcome of [`apply_catalog`]: keys that were translated, and keys left
+/// untouched because the catalog lacks them or only has a fuzzy guess.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub translated: Vec<String>,
+    pub missing: Vec<String>,
+    pub fuzzy: Vec<String>,
+}
+
+impl PoCatalog {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut catalog = PoCatalog::default();
+        let mut current = PoMessage::default();
+        // Which string continuation lines (`"..."`) append to.
+        let mut field: Option<&str> = None;
+        let mut has_header = false;
+        let mut started = false;
+
+        let mut flush = |msg: &mut PoMessage, started: &mut bool, catalog: &mut PoCatalog| {
+            if !*started {
+                return;
+            }
+            let msg = std::mem::take(msg);
+            if msg.msgid.is_empty() && !has_header {
+                catalog.header = msg.msgstr;
+                has_header = true;
+            } else {
+                catalog.messages.push(msg);
+            }
+            *started = false;
+        };
+
+        for (idx, raw) in content.lines().enumerate() {
+            let line = raw.trim();
+            let lineno = idx + 1;
+            if line.is_empty() || line.starts_with("#~") || line.starts_with("#|") {
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix('#') {
+                if started && field.is_some_and(|f| f != "msgid") {
+                    flush(&mut current, &mut started, &mut catalog);
+                    field = None;
+                }
+                if let Some(c) = rest.strip_prefix('.') {
+                    current.comments.push(c.trim().to_string());
+                } else if let Some(r) = rest.strip_prefix(':') {
+                    current
+                        .references
+                        .extend(r.split_whitespace().map(str::to_string));
+                } else if let Some(flags) = rest.strip_prefix(',') {
+                    current.fuzzy |= flags.split(',').any(|f| f.trim() == "fuzzy");
+                }
+                continue;
+            }
+            if line.starts_with('"') {
+                let text = unquote(line).ok_or_else(|| format!("line {}: bad string", lineno))?;
+                match field {
+                    Some("msgid") => current.msgid.push_str(&text),
+                    Some("msgstr") => current.msgstr.push_str(&text),
+                    Some(_) => {}
+                    None => return Err(format!("line {}: string outside a message", lineno)),
+                }
+                continue;
+            }
+            let (keyword, rest) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("line {}: expected a keyword and a string", lineno))?;
+            let text =
+                unquote(rest.trim()).ok_or_else(|| format!("line {}: bad string", lineno))?;
+            match keyword {
+                "msgctxt" | "msgid" => {
+                    if started && field != Some("msgctxt") {
+                        flush(&mut current, &mut started, &mut catalog);
+                    }
+                    started = true;
+                    if keyword == "msgid" {
+                        current.msgid = text;
+                        field = Some("msgid");
+                    } else {
+                        field = Some("msgctxt");
+                    }
+                }
+                "msgstr" | "msgstr[0]" => {
+                    current.msgstr = text;
+                    field = Some("msgstr");
+                }
+                // Other plural forms and plural ids are irrelevant for key files.
+                k if k.starts_with("msgstr[") || k == "msgid_plural" => field = Some("other"),
+                other => return Err(format!("line {}: unknown keyword {}", lineno, other)),
+            }
+        }
+        flush(&mut current, &mut started, &mut catalog);
+        Ok(catalog)
+    }
+
+    /// The value of a header field such as `Language`.
+    pub fn header_field(&self, name: &str) -> Option<&str> {
+        self.header.lines().find_map(|l| {
+            let (k, v) = l.split_once(':')?;
+            (k.trim() == name).then(|| v.trim())
+        })
+    }
+
+    fn find(&self, msgid: &str) -> Option<&PoMessage> {
+        self.messages.iter().find(|m| m.msgid == msgid)
+    }
+}
+
+impl fmt::Display for PoCatalog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "msgid \"\"")?;
+        writeln!(f, "msgstr \"\"")?;
+        for line in self.header.split_inclusive('\n') {
+            writeln!(f, "\"{}\"", quote(line))?;
+        }
+        for msg in &self.messages {
+            writeln!(f)?;
+            for c in &msg.comments {
+                writeln!(f, "#. {}", c)?;
+            }
+            if !msg.references.is_empty() {
+                writeln!(f, "#: {}", msg.references.join(" "))?;
+            }
+            if msg.fuzzy {
+                writeln!(f, "#, fuzzy")?;
+            }
+            writeln!(f, "msgid \"{}\"", quote(&msg.msgid))?;
+            writeln!(f, "msgstr \"{}\"", quote(&msg.msgstr))?;
+        }
+        Ok(())
+    }
+}
+
+// A translatable string of an entry and where its translation goes.
+enum Target {
+    Name,
+    GenericName,
+    Comment,
+    Keywords,
+    ActionName(usize),
+}
+
+// Same units as `xgettext` extracts from desktop files; Keywords is one
+// message holding the whole list.
+fn translatable(de: &DesktopEntry) -> Vec<(String, String, Target)> {
+    let mut out = Vec::new();
+    if !de.name.trim().is_empty() {
+        out.push(("Name".to_string(), de.name.clone(), Target::Name));
+    }
+    if let Some(v) = de.generic_name.as_ref().filter(|v| !v.trim().is_empty()) {
+        out.push(("GenericName".into(), v.clone(), Target::GenericName));
+    }
+    if let Some(v) = de.comment.as_ref().filter(|v| !v.trim().is_empty()) {
+        out.push(("Comment".into(), v.clone(), Target::Comment));
+    }
+    if !de.keywords.is_empty() {
+        out.push(("Keywords".into(), join_list(&de.keywords), Target::Keywords));
+    }
+    for (idx, action) in de.actions.iter().enumerate() {
+        if !action.name.trim().is_empty() {
+            let key = format!("{}/Name", action.group_name());
+            out.push((key, action.name.clone(), Target::ActionName(idx)));
+        }
+    }
+    out
+}
+
+/// A `.pot` template with every translatable string of `sources`, given as
+/// `(file name, entry)` pairs. Identical strings share one message.
+pub fn extract_template(sources: &[(String, DesktopEntry)]) -> PoCatalog {
+    let mut catalog = PoCatalog {
+        header: "Content-Type: text/plain; charset=UTF-8\nContent-Transfer-Encoding: 8bit\n"
+            .to_string(),
+        messages: Vec::new(),
+    };
+    for (file, de) in sources {
+        for (key, msgid, _) in translatable(de) {
+            let idx = match catalog.messages.iter().position(|m| m.msgid == msgid) {
+                Some(idx) => idx,
+                None => {
+                    catalog.messages.push(PoMessage {
+                        msgid,
+                        ..PoMessage::default()
+                    });
+                    catalog.messages.len() - 1
+                }
+            };
+            let msg = &mut catalog.messages[idx];
+            if !msg.comments.contains(&key) {
+                msg.comments.push(key);
+            }
+            if !msg.references.contains(file) {
+                msg.references.push(file.clone());
+            }
+        }
+    }
+    catalog
+}
+
+/// Merges the translations of `catalog` into `de` under `locale`, like
+/// `msgfmt --desktop`. Fuzzy translations are reported, not applied.
+pub fn apply_catalog(de: &mut DesktopEntry, locale: &str, catalog: &PoCatalog) -> ImportReport {
+    let mut report = ImportReport::default();
+    for (key, msgid, target) in translatable(de) {
+        let msg = catalog.find(&msgid).filter(|m| !m.msgstr.is_empty());
+        let Some(msg) = msg else {
+            report.missing.push(key);
+            continue;
+        };
+        if msg.fuzzy {
+            report.fuzzy.push(key);
+            continue;
+        }
+        let value = msg.msgstr.clone();
+        match target {
+            Target::Name => de.name_localized.set(locale, value),
+            Target::GenericName => de.generic_name_localized.set(locale, value),
+            Target::Comment => de.comment_localized.set(locale, value),
+            Target::Keywords => de.keywords_localized.set(locale, split_list(&value)),
+            Target::ActionName(idx) => de.actions[idx].name_localized.set(locale, value),
+        }
+        report.translated.push(key);
+    }
+    report
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unquote(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            other => out.push(other),
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PoCatalog, apply_catalog, extract_template};
+    use crate::domain::desktop_entry::DesktopEntry;
+    use crate::domain::locale::Locale;
+
+    fn entry() -> DesktopEntry {
+        DesktopEntry::from_ini_string(
+            "[Desktop Entry]\nType=Application\nName=Files\nComment=Browse \"files\"\nExec=files\nKeywords=folder;manager;\nActions=new;\n\n[Desktop Action new]\nName=New Window\nExec=files --new\n",
+        )
+    }
+
+    #[test]
+    fn template_lists_translatable_keys_once() {
+        let pot = extract_template(&[
+            ("files.desktop".to_string(), entry()),
+            ("other.desktop".to_string(), entry()),
+        ]);
+        let ids: Vec<&str> = pot.messages.iter().map(|m| m.msgid.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["Files", "Browse \"files\"", "folder;manager;", "New Window"]
+        );
+        assert_eq!(
+            pot.messages[0].references,
+            vec!["files.desktop", "other.desktop"]
+        );
+        let text = pot.to_string();
+        assert!(text.contains("#. Comment\n#: files.desktop other.desktop\nmsgid \"Browse \\\"files\\\"\"\nmsgstr \"\"\n"));
+        assert_eq!(PoCatalog::parse(&text).unwrap(), pot);
+    }
+
+    #[test]
+    fn import_applies_translations_and_reports_gaps() {
+        let po = "# German\nmsgid \"\"\nmsgstr \"\"\n\"Language: de\\n\"\n\n#. Name\nmsgid \"Files\"\nmsgstr \"Dateien\"\n\n#, fuzzy\nmsgid \"New Window\"\nmsgstr \"Neues Fenster\"\n\nmsgid \"folder;manager;\"\nmsgstr \"\"\n\"Ordner;\"\n\"Verwaltung;\"\n";
+        let catalog = PoCatalog::parse(po).unwrap();
+        assert_eq!(catalog.header_field("Language"), Some("de"));
+
+        let mut de = entry();
+        let report = apply_catalog(&mut de, "de", &catalog);
+        assert_eq!(report.translated, vec!["Name", "Keywords"]);
+        assert_eq!(report.missing, vec!["Comment"]);
+        assert_eq!(report.fuzzy, vec!["Desktop Action new/Name"]);
+
+        let de_locale = Locale::parse("de").unwrap();
+        assert_eq!(
+            de.name_localized.lookup(&de_locale).map(String::as_str),
+            Some("Dateien")
+        );
+        assert_eq!(
+            de.keywords_localized.lookup(&de_locale),
+            Some(&vec!["Ordner".to_string(), "Verwaltung".to_string()])
+        );
+        assert!(de.actions[0].name_localized.lookup(&de_locale).is_none());
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert!(PoCatalog::parse("msgid \"a\"\nmsgstr \"b\nfoo\n").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+/// Bytes left as they are in a `file://` URI path (RFC 3986 unreserved
+/// characters plus the path separator).
+const PATH_SAFE: &[u8] = b"-._~/";
+
+/// Decodes `%XX` escapes; a `%` not followed by two hex digits is kept.
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = text.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Whether `text` starts with a URI scheme such as `https:` or `file:`.
+pub fn has_scheme(text: &str) -> bool {
+    let Some((scheme, _)) = text.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// The `file://` URI of an absolute path.
+pub fn file_uri(path: &str) -> String {
+    let mut out = String::from("file://");
+    for &b in path.as_bytes() {
+        if b.is_ascii_alphanumeric() || PATH_SAFE.contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
+
+/// The local path of a `file:` URI on this host, `None` for anything else.
+pub fn local_path(uri: &str) -> Option<String> {
+    let rest = uri.strip_prefix("file://")?;
+    let path = match rest.strip_prefix("localhost") {
+        Some(p) => p,
+        None => rest,
+    };
+    path.starts_with('/').then(|| percent_decode(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{file_uri, has_scheme, local_path, percent_decode};
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(
+            percent_decode("/home/u/My%20Apps/caf%C3%A9.desktop"),
+            "/home/u/My Apps/café.desktop"
+        );
+        assert_eq!(percent_decode("/a/100%"), "/a/100%");
+    }
+
+    #[test]
+    fn converts_between_paths_and_uris() {
+        let uri = file_uri("/home/u/My Docs/café #1.txt");
+        assert_eq!(uri, "file:///home/u/My%20Docs/caf%C3%A9%20%231.txt");
+        assert_eq!(
+            local_path(&uri).as_deref(),
+            Some("/home/u/My Docs/café #1.txt")
+        );
+        assert_eq!(
+            local_path("file://localhost/tmp/a").as_deref(),
+            Some("/tmp/a")
+        );
+        assert_eq!(local_path("file://server/share/a"), None);
+        assert_eq!(local_path("https://example.org/a"), None);
+        assert!(has_scheme("https://example.org"));
+        assert!(has_scheme("mailto:someone@example.org"));
+        assert!(!has_scheme("/tmp/a:b"));
+        assert!(!has_scheme("notes.txt"));
+    }
+}
@@ -0,0 +1,972 @@
+use crate::domain::categories::{CategoryIssue, check_categories};
+use crate::domain::desktop_entry::{split_list, unescape_string};
+use crate::domain::exec_command::{ExecCommand, ExecIssue};
+use crate::domain::ini_document::{IniDocument, IniItem};
+use crate::domain::locale::{Locale, split_locale_key};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Hint,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Hint => "hint",
+        }
+    }
+}
+
+/// One finding of [`validate_document`]. `code` is stable and meant for
+/// filtering; `message` is for people.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub group: Option<String>,
+    pub key: Option<String>,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "{}: {}", self.severity.label(), self.message)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    String,
+    LocaleString,
+    IconString,
+    Boolean,
+    Strings,
+    LocaleStrings,
+}
+
+impl ValueKind {
+    fn is_localized(self) -> bool {
+        matches!(
+            self,
+            ValueKind::LocaleString | ValueKind::IconString | ValueKind::LocaleStrings
+        )
+    }
+
+    fn is_list(self) -> bool {
+        matches!(self, ValueKind::Strings | ValueKind::LocaleStrings)
+    }
+}
+
+// Which entry types a key is meaningful for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    All,
+    Application,
+    Link,
+}
+
+const ENTRY_KEYS: &[(&str, ValueKind, Scope)] = &[
+    ("Type", ValueKind::String, Scope::All),
+    ("Version", ValueKind::String, Scope::All),
+    ("Name", ValueKind::LocaleString, Scope::All),
+    ("GenericName", ValueKind::LocaleString, Scope::All),
+    ("NoDisplay", ValueKind::Boolean, Scope::All),
+    ("Comment", ValueKind::LocaleString, Scope::All),
+    ("Icon", ValueKind::IconString, Scope::All),
+    ("Hidden", ValueKind::Boolean, Scope::All),
+    ("OnlyShowIn", ValueKind::Strings, Scope::All),
+    ("NotShowIn", ValueKind::Strings, Scope::All),
+    ("DBusActivatable", ValueKind::Boolean, Scope::Application),
+    ("TryExec", ValueKind::String, Scope::Application),
+    ("Exec", ValueKind::String, Scope::Application),
+    ("Path", ValueKind::String, Scope::Application),
+    ("Terminal", ValueKind::Boolean, Scope::Application),
+    ("Actions", ValueKind::Strings, Scope::Application),
+    ("MimeType", ValueKind::Strings, Scope::Application),
+    ("Categories", ValueKind::Strings, Scope::Application),
+    ("Implements", ValueKind::Strings, Scope::All),
+    ("Keywords", ValueKind::LocaleStrings, Scope::Application),
+    ("StartupNotify", ValueKind::Boolean, Scope::Application),
+    ("StartupWMClass", ValueKind::String, Scope::Application),
+    ("URL", ValueKind::String, Scope::Link),
+    (
+        "PrefersNonDefaultGPU",
+        ValueKind::Boolean,
+        Scope::Application,
+    ),
+    ("SingleMainWindow", ValueKind::Boolean, Scope::Application),
+];
+
+const ACTION_KEYS: &[(&str, ValueKind)] = &[
+    ("Name", ValueKind::LocaleString),
+    ("Icon", ValueKind::IconString),
+    ("Exec", ValueKind::String),
+];
+
+// String keys holding a command line or a file system path.
+const PATH_KEYS: &[&str] = &["Exec", "TryExec", "Path"];
+
+const SPEC_VERSIONS: &[&str] = &["1.0", "1.1", "1.2", "1.3", "1.4", "1.5"];
+
+// Keys from older versions of the spec, still found in the wild.
+const DEPRECATED_KEYS: &[&str] = &[
+    "Encoding",
+    "MiniIcon",
+    "TerminalOptions",
+    "Protocols",
+    "Extensions",
+    "BinaryPattern",
+    "MapNotify",
+    "SwallowTitle",
+    "SwallowExec",
+    "SortOrder",
+    "FilePattern",
+    "Dev",
+    "FSType",
+    "MountPoint",
+    "ReadOnly",
+    "UnmountIcon",
+];
+
+struct Entry<'a> {
+    line: usize,
+    key: &'a str,
+    value: &'a str,
+}
+
+struct Group<'a> {
+    line: usize,
+    name: &'a str,
+    entries: Vec<Entry<'a>>,
+}
+
+impl<'a> Group<'a> {
+    fn get(&self, key: &str) -> Option<&Entry<'a>> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    fn push(
+        &mut self,
+        severity: Severity,
+        code: &'static str,
+        group: Option<&str>,
+        key: Option<&str>,
+        line: Option<usize>,
+        message: String,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            code,
+            group: group.map(str::to_string),
+            key: key.map(str::to_string),
+            line,
+            message,
+        });
+    }
+
+    fn on_group(&mut self, severity: Severity, code: &'static str, g: &Group, message: String) {
+        self.push(severity, code, Some(g.name), None, Some(g.line), message);
+    }
+
+    fn on_entry(
+        &mut self,
+        severity: Severity,
+        code: &'static str,
+        g: &Group,
+        e: &Entry,
+        message: String,
+    ) {
+        self.push(
+            severity,
+            code,
+            Some(g.name),
+            Some(e.key),
+            Some(e.line),
+            message,
+        );
+    }
+}
+
+/// Checks a key file against the Desktop Entry Specification, along the
+/// lines of `desktop-file-validate`. Diagnostics are ordered by line.
+pub fn validate_document(document: &IniDocument) -> Vec<Diagnostic> {
+    let mut report = Report::default();
+    let groups = collect_groups(document, &mut report);
+
+    let Some(main) = groups.iter().find(|g| g.name == "Desktop Entry") else {
+        report.push(
+            Severity::Error,
+            "missing-group",
+            None,
+            None,
+            None,
+            "file has no [Desktop Entry] group".into(),
+        );
+        return finish(report);
+    };
+    if groups.first().is_some_and(|g| g.name != "Desktop Entry") {
+        report.on_group(
+            Severity::Error,
+            "first-group",
+            &groups[0],
+            "the first group must be [Desktop Entry]".into(),
+        );
+    }
+
+    let entry_type = main.get("Type").map(|e| e.value.trim()).unwrap_or("");
+    let declared: Vec<String> = main
+        .get("Actions")
+        .map(|e| split_list(e.value))
+        .unwrap_or_default();
+
+    let mut seen_groups: Vec<&str> = Vec::new();
+    for group in &groups {
+        if seen_groups.contains(&group.name) {
+            report.on_group(
+                Severity::Error,
+                "duplicate-group",
+                group,
+                format!("group [{}] appears more than once", group.name),
+            );
+        }
+        seen_groups.push(group.name);
+
+        if group.name == "Desktop Entry" {
+            check_keys(group, &mut report, |base| {
+                ENTRY_KEYS
+                    .iter()
+                    .find(|(k, _, _)| *k == base)
+                    .map(|(_, kind, _)| *kind)
+            });
+            check_scopes(group, entry_type, &mut report);
+        } else if let Some(id) = group.name.strip_prefix("Desktop Action ") {
+            if !declared.iter().any(|d| d == id) {
+                report.on_group(
+                    Severity::Warning,
+                    "unlisted-action-group",
+                    group,
+                    format!("action \"{}\" is not listed in Actions", id),
+                );
+            }
+            check_keys(group, &mut report, |base| {
+                ACTION_KEYS
+                    .iter()
+                    .find(|(k, _)| *k == base)
+                    .map(|(_, kind)| *kind)
+            });
+        } else if !group.name.starts_with("X-") {
+            report.on_group(
+                Severity::Error,
+                "unknown-group",
+                group,
+                format!(
+                    "unknown group [{}], extension groups must start with X-",
+                    group.name
+                ),
+            );
+        }
+    }
+
+    check_required(main, entry_type, &mut report);
+    check_known_values(main, &mut report);
+    check_category_list(main, &mut report);
+    check_mime_exec(main, &mut report);
+    check_actions(main, &groups, &declared, &mut report);
+    finish(report)
+}
+
+fn finish(mut report: Report) -> Vec<Diagnostic> {
+    report
+        .diagnostics
+        .sort_by_key(|d| (d.line.unwrap_or(usize::MAX), d.severity));
+    report.diagnostics
+}
+
+// Groups the document's entries, reporting lines that belong nowhere.
+fn collect_groups<'a>(document: &'a IniDocument, report: &mut Report) -> Vec<Group<'a>> {
+    let mut groups: Vec<Group> = Vec::new();
+    for (line, item) in document.items() {
+        match item {
+            IniItem::Blank | IniItem::Comment => {}
+            IniItem::Group(name) => groups.push(Group {
+                line,
+                name,
+                entries: Vec::new(),
+            }),
+            IniItem::Entry { key, value } => match groups.last_mut() {
+                Some(g) => g.entries.push(Entry { line, key, value }),
+                None => report.push(
+                    Severity::Error,
+                    "entry-outside-group",
+                    None,
+                    Some(key),
+                    Some(line),
+                    format!("key \"{}\" appears before the first group", key),
+                ),
+            },
+            IniItem::Invalid(raw) => report.push(
+                Severity::Error,
+                "invalid-line",
+                groups.last().map(|g| g.name),
+                None,
+                Some(line),
+                format!(
+                    "\"{}\" is not a group header, key=value pair or comment",
+                    raw
+                ),
+            ),
+        }
+    }
+    groups
+}
+
+fn check_keys(group: &Group, report: &mut Report, lookup: impl Fn(&str) -> Option<ValueKind>) {
+    let mut seen: Vec<&str> = Vec::new();
+    for entry in &group.entries {
+        if seen.contains(&entry.key) {
+            report.on_entry(
+                Severity::Error,
+                "duplicate-key",
+                group,
+                entry,
+                format!("key \"{}\" appears more than once", entry.key),
+            );
+        }
+        seen.push(entry.key);
+
+        let (base, locale) = split_locale_key(entry.key);
+        if base.is_empty() || !base.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            report.on_entry(
+                Severity::Error,
+                "invalid-key",
+                group,
+                entry,
+                format!(
+                    "\"{}\" is not a valid key name, only A-Z, a-z, 0-9 and - are allowed",
+                    entry.key
+                ),
+            );
+            continue;
+        }
+        if let Some(locale) = locale
+            && Locale::parse(locale).is_none()
+        {
+            report.on_entry(
+                Severity::Error,
+                "invalid-locale",
+                group,
+                entry,
+                format!("\"{}\" is not a valid locale", locale),
+            );
+        }
+        if base.starts_with("X-") {
+            continue;
+        }
+
+        let Some(kind) = lookup(base) else {
+            if DEPRECATED_KEYS.contains(&base) {
+                report.on_entry(
+                    Severity::Warning,
+                    "deprecated-key",
+                    group,
+                    entry,
+                    format!("key \"{}\" is deprecated", base),
+                );
+            } else {
+                let suggestion = ENTRY_KEYS
+                    .iter()
+                    .map(|(k, _, _)| *k)
+                    .chain(ACTION_KEYS.iter().map(|(k, _)| *k))
+                    .find(|k| k.eq_ignore_ascii_case(base));
+                let hint = suggestion
+                    .map(|k| format!(", did you mean \"{}\"?", k))
+                    .unwrap_or_else(|| ", custom keys must start with X-".into());
+                report.on_entry(
+                    Severity::Error,
+                    "unknown-key",
+                    group,
+                    entry,
+                    format!("unknown key \"{}\"{}", base, hint),
+                );
+            }
+            continue;
+        };
+
+        if locale.is_some() && !kind.is_localized() {
+            report.on_entry(
+                Severity::Error,
+                "not-localizable",
+                group,
+                entry,
+                format!("key \"{}\" cannot be localized", base),
+            );
+        }
+        check_value(group, entry, kind, report);
+    }
+}
+
+fn check_value(group: &Group, entry: &Entry, kind: ValueKind, report: &mut Report) {
+    let value = entry.value;
+    if value.chars().any(|c| c.is_control()) {
+        report.on_entry(
+            Severity::Error,
+            "control-character",
+            group,
+            entry,
+            format!("value of \"{}\" contains a control character", entry.key),
+        );
+    }
+    match kind {
+        ValueKind::Boolean => match value {
+            "true" | "false" => {}
+            "0" | "1" => report.on_entry(
+                Severity::Warning,
+                "deprecated-boolean",
+                group,
+                entry,
+                format!(
+                    "\"{}\" is a deprecated boolean, use true or false for \"{}\"",
+                    value, entry.key
+                ),
+            ),
+            _ => report.on_entry(
+                Severity::Error,
+                "invalid-boolean",
+                group,
+                entry,
+                format!("\"{}\" must be true or false, not \"{}\"", entry.key, value),
+            ),
+        },
+        // Commands and paths name files, which may well be UTF-8; only text
+        // meant to be read is pointed at its localized variant.
+        ValueKind::String | ValueKind::Strings
+            if !value.is_ascii() && !PATH_KEYS.contains(&entry.key) =>
+        {
+            report.on_entry(
+                Severity::Warning,
+                "non-ascii-string",
+                group,
+                entry,
+                format!(
+                    "\"{}\" should be ASCII, use a localized key for other text",
+                    entry.key
+                ),
+            )
+        }
+        ValueKind::IconString => {
+            let icon = unescape_string(value);
+            let has_extension = [".png", ".svg", ".xpm"]
+                .iter()
+                .any(|ext| icon.to_ascii_lowercase().ends_with(ext));
+            if has_extension && !icon.starts_with('/') {
+                report.on_entry(
+                    Severity::Warning,
+                    "icon-extension",
+                    group,
+                    entry,
+                    format!(
+                        "icon name \"{}\" should not have an extension unless it is an absolute path",
+                        icon
+                    ),
+                );
+            }
+        }
+        _ => {}
+    }
+    if let Some(c) = invalid_escape(value, kind.is_list()) {
+        report.on_entry(
+            Severity::Error,
+            "invalid-escape",
+            group,
+            entry,
+            format!("\"\\{}\" is not a valid escape sequence", c),
+        );
+    }
+    if kind.is_list() && !value.trim().is_empty() && !value.trim_end().ends_with(';') {
+        report.on_entry(
+            Severity::Hint,
+            "list-separator",
+            group,
+            entry,
+            format!("list \"{}\" should end with a semicolon", entry.key),
+        );
+    }
+}
+
+fn invalid_escape(value: &str, list: bool) -> Option<char> {
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('s' | 'n' | 't' | 'r' | '\\') => {}
+                Some(';') if list => {}
+                Some(other) => return Some(other),
+                None => return Some(' '),
+            }
+        }
+    }
+    None
+}
+
+// Keys that only make sense for another Type are harmless but suspicious.
+fn check_scopes(group: &Group, entry_type: &str, report: &mut Report) {
+    for entry in &group.entries {
+        let (base, _) = split_locale_key(entry.key);
+        let Some((_, _, scope)) = ENTRY_KEYS.iter().find(|(k, _, _)| *k == base) else {
+            continue;
+        };
+        let fits = match scope {
+            Scope::All => true,
+            Scope::Application => entry_type == "Application",
+            Scope::Link => entry_type == "Link",
+        };
+        if !fits && !entry_type.is_empty() {
+            report.on_entry(
+                Severity::Warning,
+                "key-not-for-type",
+                group,
+                entry,
+                format!("key \"{}\" is not used by Type={}", base, entry_type),
+            );
+        }
+    }
+}
+
+fn check_required(main: &Group, entry_type: &str, report: &mut Report) {
+    let has = |key: &str| main.get(key).is_some_and(|e| !e.value.trim().is_empty());
+    let mut missing = |key: &str, why: &str| {
+        report.on_group(
+            Severity::Error,
+            "missing-key",
+            main,
+            format!("required key \"{}\" is missing{}", key, why),
+        );
+    };
+    if !has("Type") {
+        missing("Type", "");
+    }
+    if !has("Name") {
+        missing("Name", "");
+    }
+    let dbus = main
+        .get("DBusActivatable")
+        .is_some_and(|e| e.value == "true");
+    match entry_type {
+        "Application" => {
+            if !has("Exec") && !dbus {
+                missing("Exec", " for Type=Application");
+            }
+        }
+        "Link" => {
+            if !has("URL") {
+                missing("URL", " for Type=Link");
+            }
+        }
+        "Directory" | "" => {}
+        other => {
+            let entry = main.get("Type").expect("Type is present");
+            report.on_entry(
+                Severity::Error,
+                "invalid-type",
+                main,
+                entry,
+                format!(
+                    "Type \"{}\" is not one of Application, Link or Directory",
+                    other
+                ),
+            );
+        }
+    }
+    if let Some(exec) = main.get("Exec") {
+        check_exec(main, exec, report);
+    }
+}
+
+// Values of typed keys that are syntactically fine but still wrong.
+fn check_known_values(main: &Group, report: &mut Report) {
+    if let Some(version) = main.get("Version")
+        && !SPEC_VERSIONS.contains(&version.value.trim())
+    {
+        report.on_entry(
+            Severity::Warning,
+            "unknown-version",
+            main,
+            version,
+            format!(
+                "Version \"{}\" is not a released spec version ({})",
+                version.value,
+                SPEC_VERSIONS.join(", ")
+            ),
+        );
+    }
+    if let Some(implements) = main.get("Implements") {
+        for name in split_list(implements.value) {
+            if !is_interface_name(&name) {
+                report.on_entry(
+                    Severity::Warning,
+                    "invalid-interface",
+                    main,
+                    implements,
+                    format!("\"{}\" is not a valid D-Bus interface name", name),
+                );
+            }
+        }
+    }
+}
+
+fn check_category_list(main: &Group, report: &mut Report) {
+    let Some(entry) = main.get("Categories") else {
+        return;
+    };
+    let only_show_in = main
+        .get("OnlyShowIn")
+        .is_some_and(|e| !split_list(e.value).is_empty());
+    for issue in check_categories(&split_list(entry.value), only_show_in) {
+        let (severity, code) = match issue {
+            CategoryIssue::Unknown { .. } => (Severity::Warning, "unknown-category"),
+            CategoryIssue::MissingRelated { .. } => (Severity::Warning, "missing-related-category"),
+            CategoryIssue::Reserved(_) => (Severity::Error, "reserved-category"),
+            CategoryIssue::NoMainCategory => (Severity::Hint, "no-main-category"),
+        };
+        report.on_entry(severity, code, main, entry, issue.to_string());
+    }
+}
+
+// Files of the announced types are passed through %f/%F/%u/%U; without one
+// the application is opened but never sees the file.
+fn check_mime_exec(main: &Group, report: &mut Report) {
+    let (Some(mime), Some(exec)) = (main.get("MimeType"), main.get("Exec")) else {
+        return;
+    };
+    if split_list(mime.value).is_empty() {
+        return;
+    }
+    if let Ok(command) = ExecCommand::parse(&unescape_string(exec.value))
+        && !command.field_codes().any(|c| c.takes_files())
+    {
+        report.on_entry(
+            Severity::Warning,
+            "mime-without-file-code",
+            main,
+            exec,
+            "MimeType is set but Exec has no %f, %F, %u or %U to receive the files".into(),
+        );
+    }
+}
+
+/// URI schemes are claimed through `x-scheme-handler/<scheme>` types, which
+/// no MIME database lists.
+pub const SCHEME_HANDLER_PREFIX: &str = "x-scheme-handler/";
+
+/// What a MIME database knows about a type name.
+pub enum MimeLookup {
+    Known,
+    Alias(String),
+    Unknown,
+}
+
+/// Checks the `MimeType` values of `document` against a MIME database,
+/// queried through `lookup`. Kept apart from `validate_document` since it
+/// depends on what is installed.
+pub fn check_mime_types(
+    document: &IniDocument,
+    lookup: impl Fn(&str) -> MimeLookup,
+) -> Vec<Diagnostic> {
+    let mut report = Report::default();
+    let groups = collect_groups(document, &mut Report::default());
+    let Some((main, mime)) = groups
+        .iter()
+        .find(|g| g.name == "Desktop Entry")
+        .and_then(|main| main.get("MimeType").map(|e| (main, e)))
+    else {
+        return Vec::new();
+    };
+    for name in split_list(mime.value) {
+        if name.starts_with(SCHEME_HANDLER_PREFIX) {
+            continue;
+        }
+        match lookup(&name) {
+            MimeLookup::Known => {}
+            MimeLookup::Alias(canonical) => report.on_entry(
+                Severity::Warning,
+                "mime-type-alias",
+                main,
+                mime,
+                format!("\"{}\" is an alias, use \"{}\" instead", name, canonical),
+            ),
+            MimeLookup::Unknown => report.on_entry(
+                Severity::Warning,
+                "unknown-mime-type",
+                main,
+                mime,
+                format!("\"{}\" is not in the MIME database", name),
+            ),
+        }
+    }
+    finish(report)
+}
+
+fn is_interface_name(name: &str) -> bool {
+    let parts: Vec<&str> = name.split('.').collect();
+    parts.len() >= 2
+        && parts.iter().all(|p| {
+            p.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                && p.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+fn check_actions(main: &Group, groups: &[Group], declared: &[String], report: &mut Report) {
+    let Some(actions) = main.get("Actions") else {
+        return;
+    };
+    let dbus = main
+        .get("DBusActivatable")
+        .is_some_and(|e| e.value == "true");
+    let mut seen: Vec<&str> = Vec::new();
+    for id in declared {
+        if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            report.on_entry(
+                Severity::Error,
+                "invalid-action-id",
+                main,
+                actions,
+                format!(
+                    "action identifier \"{}\" may only contain A-Z, a-z, 0-9 and -",
+                    id
+                ),
+            );
+            continue;
+        }
+        if seen.contains(&id.as_str()) {
+            report.on_entry(
+                Severity::Error,
+                "duplicate-action",
+                main,
+                actions,
+                format!("action \"{}\" is listed twice", id),
+            );
+            continue;
+        }
+        seen.push(id);
+
+        let name = format!("Desktop Action {}", id);
+        let Some(group) = groups.iter().find(|g| g.name == name) else {
+            report.on_entry(
+                Severity::Error,
+                "missing-action-group",
+                main,
+                actions,
+                format!("action \"{}\" has no [{}] group", id, name),
+            );
+            continue;
+        };
+        if group.get("Name").is_none_or(|e| e.value.trim().is_empty()) {
+            report.on_group(
+                Severity::Error,
+                "missing-key",
+                group,
+                format!("action \"{}\" requires a Name", id),
+            );
+        }
+        match group.get("Exec") {
+            Some(exec) => check_exec(group, exec, report),
+            None if !dbus => report.on_group(
+                Severity::Warning,
+                "missing-key",
+                group,
+                format!(
+                    "action \"{}\" has no Exec and the application is not D-Bus activatable",
+                    id
+                ),
+            ),
+            None => {}
+        }
+    }
+}
+
+fn check_exec(group: &Group, entry: &Entry, report: &mut Report) {
+    match ExecCommand::check(&unescape_string(entry.value)) {
+        Err(err) => report.on_entry(
+            Severity::Error,
+            "invalid-exec",
+            group,
+            entry,
+            format!("invalid command line: {}", err),
+        ),
+        Ok(issues) => {
+            for issue in issues {
+                let (severity, code) = exec_issue_code(&issue);
+                report.on_entry(severity, code, group, entry, issue.to_string());
+            }
+        }
+    }
+}
+
+fn exec_issue_code(issue: &ExecIssue) -> (Severity, &'static str) {
+    match issue {
+        ExecIssue::InvalidFieldCode(_) => (Severity::Error, "invalid-field-code"),
+        ExecIssue::TrailingPercent => (Severity::Error, "invalid-field-code"),
+        ExecIssue::MultipleFileCodes => (Severity::Error, "multiple-file-codes"),
+        ExecIssue::ListCodeNotStandalone(_) => (Severity::Error, "list-code-not-standalone"),
+        ExecIssue::FieldCodeInQuotes(_) => (Severity::Warning, "field-code-in-quotes"),
+        ExecIssue::DeprecatedFieldCode(_) => (Severity::Warning, "deprecated-field-code"),
+        ExecIssue::ReservedCharacter(_) => (Severity::Warning, "exec-reserved-character"),
+        ExecIssue::SingleQuotes => (Severity::Warning, "exec-single-quotes"),
+        ExecIssue::InvalidEscape(_) => (Severity::Warning, "exec-invalid-escape"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MimeLookup, Severity, check_mime_types, validate_document};
+    use crate::domain::ini_document::IniDocument;
+
+    fn codes(input: &str) -> Vec<(&'static str, Option<usize>)> {
+        validate_document(&IniDocument::parse(input))
+            .into_iter()
+            .map(|d| (d.code, d.line))
+            .collect()
+    }
+
+    #[test]
+    fn valid_file_has_no_diagnostics() {
+        let input = "[Desktop Entry]\nType=Application\nName=Files\nName[de_DE@euro]=Dateien\nExec=nautilus %U\nIcon=org.gnome.Nautilus\nCategories=GTK;Utility;\nActions=new-window;\nX-GNOME-UsesNotifications=true\n\n[Desktop Action new-window]\nName=New Window\nExec=nautilus --new-window\n";
+        assert!(codes(input).is_empty(), "{:?}", codes(input));
+    }
+
+    #[test]
+    fn reports_key_problems_with_lines() {
+        let input = "[Desktop Entry]\nType=Application\nName=App\nExec=app\nTerminal=yes\nStartupWmClass=app\nEncoding=UTF-8\nExec[fr]=app\nName[French]=Appli\nIcon=app.png\n";
+        let found = codes(input);
+        assert!(found.contains(&("invalid-boolean", Some(5))));
+        assert!(found.contains(&("unknown-key", Some(6))));
+        assert!(found.contains(&("deprecated-key", Some(7))));
+        assert!(found.contains(&("not-localizable", Some(8))));
+        assert!(found.contains(&("invalid-locale", Some(9))));
+        assert!(found.contains(&("icon-extension", Some(10))));
+    }
+
+    #[test]
+    fn suggests_key_with_matching_case() {
+        let input = "[Desktop Entry]\nType=Application\nName=App\nExec=app\nstartupwmclass=app\n";
+        let diags = validate_document(&IniDocument::parse(input));
+        assert!(diags[0].message.contains("StartupWMClass"));
+    }
+
+    #[test]
+    fn missing_required_keys_depend_on_type() {
+        assert!(
+            codes("[Desktop Entry]\nType=Application\nName=App\n")
+                .contains(&("missing-key", Some(1)))
+        );
+        assert!(
+            codes("[Desktop Entry]\nType=Application\nName=App\nDBusActivatable=true\n").is_empty()
+        );
+        assert!(
+            codes("[Desktop Entry]\nType=Link\nName=Site\n").contains(&("missing-key", Some(1)))
+        );
+        assert!(
+            codes("[Desktop Entry]\nType=Launcher\nName=X\n").contains(&("invalid-type", Some(2)))
+        );
+    }
+
+    #[test]
+    fn reports_structure_problems() {
+        let input = "Name=Orphan\n[Other]\nA=b\n[Desktop Entry]\nType=Directory\nName=Dir\nName=Again\nnot an entry\n[Desktop Entry]\n";
+        let found = codes(input);
+        assert!(found.contains(&("entry-outside-group", Some(1))));
+        assert!(found.contains(&("first-group", Some(2))));
+        assert!(found.contains(&("unknown-group", Some(2))));
+        assert!(found.contains(&("duplicate-key", Some(7))));
+        assert!(found.contains(&("invalid-line", Some(8))));
+        assert!(found.contains(&("duplicate-group", Some(9))));
+    }
+
+    #[test]
+    fn checks_version_and_interfaces() {
+        let input = "[Desktop Entry]\nVersion=2.0\nType=Application\nName=App\nExec=app\nImplements=org.example.Search;bad name;\n";
+        let found = codes(input);
+        assert!(found.contains(&("unknown-version", Some(2))));
+        assert!(found.contains(&("invalid-interface", Some(6))));
+        assert_eq!(found.len(), 2);
+    }
+
+    #[test]
+    fn exec_field_codes_are_checked() {
+        let found = codes("[Desktop Entry]\nType=Application\nName=App\nExec=app %f %U %x %d\n");
+        assert!(found.contains(&("multiple-file-codes", Some(4))));
+        assert!(found.contains(&("invalid-field-code", Some(4))));
+        assert!(found.contains(&("deprecated-field-code", Some(4))));
+    }
+
+    #[test]
+    fn actions_need_groups_and_names() {
+        let input = "[Desktop Entry]\nType=Application\nName=App\nExec=app\nActions=one;two;\n\n[Desktop Action one]\nExec=app --one\n\n[Desktop Action three]\nName=Three\n";
+        let found = codes(input);
+        assert!(found.contains(&("missing-action-group", Some(5))));
+        assert!(found.contains(&("missing-key", Some(7))));
+        assert!(found.contains(&("unlisted-action-group", Some(10))));
+    }
+
+    #[test]
+    fn checks_categories() {
+        let input = "[Desktop Entry]\nType=Application\nName=App\nExec=app\nCategories=Audio;Midi;Fooo;TrayIcon;\n";
+        let found = codes(input);
+        assert!(found.contains(&("missing-related-category", Some(5))));
+        assert!(found.contains(&("unknown-category", Some(5))));
+        assert!(found.contains(&("reserved-category", Some(5))));
+        assert!(
+            codes("[Desktop Entry]\nType=Application\nName=App\nExec=app\nCategories=X-Tools;\n")
+                .contains(&("no-main-category", Some(5)))
+        );
+    }
+
+    #[test]
+    fn checks_mime_types() {
+        let input = "[Desktop Entry]\nType=Application\nName=App\nExec=app\nMimeType=image/png;image/jpg;foo/bar;\n";
+        assert!(codes(input).contains(&("mime-without-file-code", Some(4))));
+        let diags = check_mime_types(&IniDocument::parse(input), |name| match name {
+            "image/png" => MimeLookup::Known,
+            "image/jpg" => MimeLookup::Alias("image/jpeg".into()),
+            _ => MimeLookup::Unknown,
+        });
+        let found: Vec<_> = diags.iter().map(|d| (d.code, d.line)).collect();
+        assert_eq!(
+            found,
+            vec![("mime-type-alias", Some(5)), ("unknown-mime-type", Some(5))]
+        );
+        assert!(diags[0].message.contains("image/jpeg"));
+    }
+
+    #[test]
+    fn scheme_handlers_are_not_looked_up() {
+        let input = "[Desktop Entry]\nType=Application\nName=App\nExec=app %u\nMimeType=x-scheme-handler/irc;\n";
+        let diags = check_mime_types(&IniDocument::parse(input), |_| MimeLookup::Unknown);
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn hints_do_not_count_as_errors() {
+        let diags = validate_document(&IniDocument::parse(
+            "[Desktop Entry]\nType=Application\nName=App\nExec=app\nCategories=Utility\n",
+        ));
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, Severity::Hint);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::domain::autostart::{with_delay, with_enabled};
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::entry_diff::diff_entries;
+use crate::services::desktop_reader::DesktopReader;
+use crate::services::desktop_scanner::{DesktopScanner, Origin, ScannedEntry};
+use crate::services::desktop_writer::{Written, write_atomic};
+use crate::services::history_store::HistoryStore;
+use crate::services::xdg;
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Login items: desktop entries in `$XDG_CONFIG_HOME/autostart` and the
+/// `autostart` directories of `$XDG_CONFIG_DIRS`. As with launchers, a user
+/// file shadows a system one with the same name.
+pub struct AutostartService;
+
+impl AutostartService {
+    /// Every autostart directory, highest priority first.
+    pub fn dirs() -> Vec<PathBuf> {
+        xdg::autostart_dirs()
+    }
+
+    pub fn user_dir() -> Result<PathBuf> {
+        xdg::config_home()
+            .map(|d| d.join("autostart"))
+            .ok_or_else(|| anyhow!("Cannot resolve config directory"))
+    }
+
+    pub fn scan() -> Vec<ScannedEntry> {
+        let user = Self::user_dir().ok();
+        DesktopScanner::scan_roots(Self::dirs(), user.as_deref())
+    }
+
+    pub fn is_autostart(path: &Path) -> bool {
+        Self::dirs().iter().any(|d| path.starts_with(d))
+    }
+
+    /// Turns the login item at `path` on or off. A system item is switched
+    /// off through a user override; switching it back on removes the
+    /// override again when nothing else was changed in it.
+    pub fn set_enabled(path: &Path, enabled: bool) -> Result<Written> {
+        let (target, system) = Self::user_copy(path)?;
+        let entry = with_enabled(&DesktopReader::read_from_path(&target)?, enabled);
+        if enabled
+            && let Some(system) = system
+            && diff_entries(
+                &with_enabled(&DesktopReader::read_from_path(&system)?, true),
+                &entry,
+            )
+            .is_empty()
+        {
+            let warning = HistoryStore::record_before_write(&target);
+            fs::remove_file(&target).with_context(|| format!("Removing {}", target.display()))?;
+            return Ok(Written {
+                path: system,
+                warning,
+            });
+        }
+        Self::save(&target, &entry)
+    }
+
+    /// Sets `X-GNOME-Autostart-Delay`, or removes it for `None` or zero.
+    pub fn set_delay(path: &Path, seconds: Option<u32>) -> Result<Written> {
+        let (target, _) = Self::user_copy(path)?;
+        let entry = with_delay(&DesktopReader::read_from_path(&target)?, seconds);
+        Self::save(&target, &entry)
+    }
+
+    /// Copies the launcher at `path` into the user autostart directory.
+    pub fn add(path: &Path) -> Result<PathBuf> {
+        let id = DesktopScanner::find(path)
+            .map(|e| e.id)
+            .or_else(|| path.file_name().map(|n| n.to_string_lossy().to_string()))
+            .ok_or_else(|| anyhow!("Not a launcher: {}", path.display()))?;
+        let dir = Self::user_dir()?;
+        let target = dir.join(&id);
+        if target.exists() {
+            return Err(anyhow!("{} already starts at login", id));
+        }
+        fs::create_dir_all(&dir).with_context(|| format!("Creating {}", dir.display()))?;
+        let entry = with_enabled(&DesktopReader::read_from_path(path)?, true);
+        write_atomic(&target, &entry.to_ini_string())?;
+        Ok(target)
+    }
+
+    /// The user file to edit for the login item at `path`, copied from the
+    /// system file when there is no user file yet.
+    pub fn customize(path: &Path) -> Result<PathBuf> {
+        Self::user_copy(path).map(|(target, _)| target)
+    }
+
+    // The user file to edit for `path`, created from the system file when
+    // needed, and the system file it shadows, if any.
+    fn user_copy(path: &Path) -> Result<(PathBuf, Option<PathBuf>)> {
+        let entry = Self::scan()
+            .into_iter()
+            .find(|e| e.active.path == path || e.shadowed.iter().any(|f| f.path == path))
+            .ok_or_else(|| anyhow!("{} is not an autostart entry", path.display()))?;
+        let system = match entry.active.origin {
+            Origin::User => entry.shadowed.first(),
+            _ => Some(&entry.active),
+        }
+        .map(|f| f.path.clone());
+        if entry.active.origin == Origin::User {
+            return Ok((entry.active.path, system));
+        }
+        let dir = Self::user_dir()?;
+        fs::create_dir_all(&dir).with_context(|| format!("Creating {}", dir.display()))?;
+        let target = dir.join(&entry.id);
+        let content = fs::read_to_string(&entry.active.path)
+            .with_context(|| format!("Reading {}", entry.active.path.display()))?;
+        write_atomic(&target, &content)?;
+        Ok((target, system))
+    }
+
+    fn save(target: &Path, entry: &DesktopEntry) -> Result<Written> {
+        let warning = HistoryStore::record_before_write(target);
+        write_atomic(target, &entry.to_ini_string())?;
+        Ok(Written {
+            path: target.to_path_buf(),
+            warning,
+        })
+    }
+}
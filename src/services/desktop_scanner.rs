@@ -0,0 +1,221 @@
+use crate::services::xdg;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Guards against symlink loops inside an applications directory.
+const MAX_DEPTH: usize = 8;
+
+/// Where an applications directory comes from, for display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    User,
+    System,
+    Flatpak,
+    Snap,
+    Nix,
+}
+
+impl Origin {
+    pub fn label(self) -> &'static str {
+        match self {
+            Origin::User => "User",
+            Origin::System => "System",
+            Origin::Flatpak => "Flatpak",
+            Origin::Snap => "Snap",
+            Origin::Nix => "Nix",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopFile {
+    pub path: PathBuf,
+    pub origin: Origin,
+}
+
+/// One desktop-file ID with the file the menu uses and the lower-priority
+/// files it shadows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannedEntry {
+    pub id: String,
+    pub active: DesktopFile,
+    pub shadowed: Vec<DesktopFile>,
+}
+
+pub struct DesktopScanner;
+
+impl DesktopScanner {
+    /// Every `applications` directory, highest priority first.
+    pub fn applications_dirs() -> Vec<PathBuf> {
+        xdg::data_dirs()
+            .into_iter()
+            .map(|d| d.join("applications"))
+            .collect()
+    }
+
+    /// Walks every applications directory and resolves each desktop-file ID
+    /// to the file that wins. Sorted by ID.
+    pub fn scan() -> Vec<ScannedEntry> {
+        let home = xdg::data_home().map(|d| d.join("applications"));
+        Self::scan_roots(Self::applications_dirs(), home.as_deref())
+    }
+
+    /// Same as [`DesktopScanner::scan`] for any list of directories in
+    /// priority order; `user_root` is the one reported as [`Origin::User`].
+    pub fn scan_roots(roots: Vec<PathBuf>, user_root: Option<&Path>) -> Vec<ScannedEntry> {
+        let mut found = Vec::new();
+        for root in roots {
+            let origin = if user_root == Some(root.as_path()) {
+                Origin::User
+            } else {
+                origin_of(&root)
+            };
+            let mut files = Vec::new();
+            walk(&root, 0, &mut files);
+            for path in files {
+                if let Some(id) = desktop_file_id(&root, &path) {
+                    found.push((id, DesktopFile { path, origin }));
+                }
+            }
+        }
+        resolve(found)
+    }
+
+    /// The scanned ID that `path` belongs to, whether it wins or is shadowed.
+    pub fn find(path: &Path) -> Option<ScannedEntry> {
+        Self::scan()
+            .into_iter()
+            .find(|e| e.active.path == path || e.shadowed.iter().any(|f| f.path == path))
+    }
+}
+
+fn walk(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    let Ok(read) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = read.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            if depth < MAX_DEPTH {
+                walk(&path, depth + 1, out);
+            }
+        } else if path.extension().is_some_and(|e| e == "desktop") {
+            out.push(path);
+        }
+    }
+}
+
+fn origin_of(root: &Path) -> Origin {
+    let s = root.to_string_lossy();
+    if s.contains("/flatpak/") {
+        Origin::Flatpak
+    } else if s.contains("/snapd/") {
+        Origin::Snap
+    } else if s.starts_with("/nix/")
+        || s.contains("/.nix-profile/")
+        || s.starts_with("/run/current-system/")
+    {
+        Origin::Nix
+    } else {
+        Origin::System
+    }
+}
+
+/// The spec's desktop-file ID: the path below the applications directory
+/// with `/` replaced by `-`, so `kde4/konsole.desktop` is `kde4-konsole.desktop`.
+pub fn desktop_file_id(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    if parts.is_empty() || !parts.last()?.ends_with(".desktop") {
+        return None;
+    }
+    Some(parts.join("-"))
+}
+
+// `found` is in priority order; the first file seen for an ID wins.
+fn resolve(found: Vec<(String, DesktopFile)>) -> Vec<ScannedEntry> {
+    let mut entries: Vec<ScannedEntry> = Vec::new();
+    for (id, file) in found {
+        match entries.iter_mut().find(|e| e.id == id) {
+            Some(entry) => entry.shadowed.push(file),
+            None => entries.push(ScannedEntry {
+                id,
+                active: file,
+                shadowed: Vec::new(),
+            }),
+        }
+    }
+    entries.sort_by(|a, b| a.id.cmp(&b.id));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DesktopFile, Origin, desktop_file_id, origin_of, resolve};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn computes_desktop_file_ids() {
+        let root = Path::new("/usr/share/applications");
+        assert_eq!(
+            desktop_file_id(root, &root.join("kde4/konsole.desktop")).as_deref(),
+            Some("kde4-konsole.desktop")
+        );
+        assert_eq!(
+            desktop_file_id(root, &root.join("firefox.desktop")).as_deref(),
+            Some("firefox.desktop")
+        );
+        assert_eq!(desktop_file_id(root, Path::new("/tmp/x.desktop")), None);
+    }
+
+    #[test]
+    fn first_directory_wins() {
+        let file = |p: &str, origin| DesktopFile {
+            path: PathBuf::from(p),
+            origin,
+        };
+        let found = vec![
+            (
+                "b.desktop".to_string(),
+                file("/home/u/b.desktop", Origin::User),
+            ),
+            (
+                "a.desktop".to_string(),
+                file("/usr/a.desktop", Origin::System),
+            ),
+            (
+                "b.desktop".to_string(),
+                file("/usr/b.desktop", Origin::System),
+            ),
+        ];
+        let entries = resolve(found);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "a.desktop");
+        assert_eq!(entries[1].active.origin, Origin::User);
+        assert_eq!(entries[1].shadowed[0].path, PathBuf::from("/usr/b.desktop"));
+    }
+
+    #[test]
+    fn recognizes_sandbox_exports() {
+        assert_eq!(
+            origin_of(Path::new("/var/lib/flatpak/exports/share/applications")),
+            Origin::Flatpak
+        );
+        assert_eq!(
+            origin_of(Path::new("/var/lib/snapd/desktop/applications")),
+            Origin::Snap
+        );
+        assert_eq!(
+            origin_of(Path::new("/home/u/.nix-profile/share/applications")),
+            Origin::Nix
+        );
+        assert_eq!(
+            origin_of(Path::new("/usr/share/applications")),
+            Origin::System
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::services::desktop_reader::DesktopReader;
+use crate::services::desktop_writer::{DesktopWriter, Written};
+use crate::services::xdg;
+use anyhow::{Result, anyhow};
+use gtk4::gio::{self, prelude::*};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Names the launcher a desktop copy was made from, so later saves of that
+/// launcher can be carried over.
+pub const SOURCE_KEY: &str = "X-LauncherStudio-Source";
+
+const TRUSTED_ATTRIBUTE: &str = "metadata::trusted";
+const MAIN_GROUP: &str = "Desktop Entry";
+
+/// A launcher file in `XDG_DESKTOP_DIR`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopShortcut {
+    pub path: PathBuf,
+    /// The launcher it was copied from, when placed by this app.
+    pub source: Option<PathBuf>,
+    /// Whether the file manager will launch it without asking first.
+    pub trusted: bool,
+}
+
+/// Copies of launchers on the desktop. Nautilus and the desktop-icons
+/// extensions only run a desktop file that is executable and carries the
+/// GIO `metadata::trusted` attribute, so both are set on every write.
+pub struct DesktopShortcutService;
+
+impl DesktopShortcutService {
+    pub fn dir() -> Result<PathBuf> {
+        xdg::desktop_dir().ok_or_else(|| anyhow!("Cannot resolve desktop directory"))
+    }
+
+    pub fn is_shortcut(path: &Path) -> bool {
+        Self::dir().is_ok_and(|d| path.parent() == Some(d.as_path()))
+    }
+
+    /// Every `.desktop` file on the desktop, sorted by file name.
+    pub fn list() -> Vec<DesktopShortcut> {
+        let Ok(dir) = Self::dir() else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|x| x == "desktop") && p.is_file())
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| DesktopShortcut {
+                source: DesktopReader::read_from_path(&path)
+                    .ok()
+                    .and_then(|de| source_of(&de)),
+                trusted: is_trusted(&path),
+                path,
+            })
+            .collect()
+    }
+
+    /// Copies the launcher at `source` to the desktop and marks the copy
+    /// trusted. An earlier copy of the same launcher is replaced. Returns
+    /// the copy and the error from marking it trusted, which is not fatal:
+    /// the file manager then asks before the first launch.
+    pub fn place(source: &Path) -> Result<(Written, Option<String>)> {
+        if Self::is_shortcut(source) {
+            return Err(anyhow!("{} is already on the desktop", source.display()));
+        }
+        let name = source
+            .file_name()
+            .ok_or_else(|| anyhow!("Not a launcher: {}", source.display()))?;
+        let target = Self::dir()?.join(name);
+        if target.exists()
+            && DesktopReader::read_from_path(&target)
+                .ok()
+                .and_then(|de| source_of(&de))
+                .as_deref()
+                != Some(source)
+        {
+            return Err(anyhow!(
+                "{} already exists and is not a copy of this launcher",
+                target.display()
+            ));
+        }
+        write_copy(source, &target)
+    }
+
+    /// Rewrites the desktop copies of `source` after it changed. Copies that
+    /// already match are left alone. Returns how many were updated, and why
+    /// no history snapshot was kept of some of them, if so.
+    pub fn sync(source: &Path) -> Result<(usize, Option<String>)> {
+        if !source.is_file() || Self::is_shortcut(source) {
+            return Ok((0, None));
+        }
+        let mut updated = 0;
+        let mut warnings = Vec::new();
+        for shortcut in Self::list() {
+            if shortcut.source.as_deref() != Some(source) {
+                continue;
+            }
+            let expected = copy_of(source)?.to_ini_string();
+            if fs::read_to_string(&shortcut.path).is_ok_and(|c| c == expected) && shortcut.trusted {
+                continue;
+            }
+            let (written, _) = write_copy(source, &shortcut.path)?;
+            warnings.extend(written.warning);
+            updated += 1;
+        }
+        Ok((updated, (!warnings.is_empty()).then(|| warnings.join("; "))))
+    }
+}
+
+fn source_of(entry: &DesktopEntry) -> Option<PathBuf> {
+    entry
+        .extra
+        .iter()
+        .find(|(k, _)| k == SOURCE_KEY)
+        .map(|(_, v)| PathBuf::from(v))
+}
+
+fn copy_of(source: &Path) -> Result<DesktopEntry> {
+    let entry = DesktopReader::read_from_path(source)?;
+    Ok(entry.with_value(MAIN_GROUP, SOURCE_KEY, Some(&source.to_string_lossy())))
+}
+
+fn write_copy(source: &Path, target: &Path) -> Result<(Written, Option<String>)> {
+    let written = DesktopWriter::write_executable(&copy_of(source)?, target)?;
+    Ok((written, set_trusted(target).err()))
+}
+
+fn set_trusted(path: &Path) -> Result<(), String> {
+    gio::File::for_path(path)
+        .set_attribute_string(
+            TRUSTED_ATTRIBUTE,
+            "true",
+            gio::FileQueryInfoFlags::NONE,
+            gio::Cancellable::NONE,
+        )
+        .map_err(|e| e.to_string())
+}
+
+fn is_trusted(path: &Path) -> bool {
+    gio::File::for_path(path)
+        .query_info(
+            TRUSTED_ATTRIBUTE,
+            gio::FileQueryInfoFlags::NONE,
+            gio::Cancellable::NONE,
+        )
+        .ok()
+        .and_then(|info| info.attribute_string(TRUSTED_ATTRIBUTE))
+        .is_some_and(|v| v == "true")
+}
@@ -1,9 +1,21 @@
 use crate::domain::desktop_entry::DesktopEntry;
+use crate::services::history_store::HistoryStore;
 use anyhow::{Context, Result, anyhow};
 use directories::BaseDirs;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
+const MAX_SYMLINKS: usize = 40;
+
+/// A launcher that was written. `warning` says why no history snapshot was
+/// kept of the version it replaced; the write itself still went through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Written {
+    pub path: PathBuf,
+    pub warning: Option<String>,
+}
+
 pub struct DesktopWriter;
 
 impl DesktopWriter {
@@ -16,7 +28,7 @@ impl DesktopWriter {
         }
     }
 
-    pub fn write(entry: &DesktopEntry, file_name: &str, overwrite: bool) -> Result<PathBuf> {
+    pub fn write(entry: &DesktopEntry, file_name: &str, overwrite: bool) -> Result<Written> {
         entry.validate().map_err(|e| anyhow!(e))?;
         let dir = Self::user_applications_dir()?;
         fs::create_dir_all(&dir).context("Creating applications directory")?;
@@ -26,48 +38,132 @@ impl DesktopWriter {
         if path.exists() && !overwrite {
             return Err(anyhow!("File already exists: {}", path.display()));
         }
-        let content = entry.to_ini_string();
-        fs::write(&path, content).with_context(|| format!("Writing {}", path.display()))?;
-
-        // Try to set sane permissions (not strictly required)
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(&path)?.permissions();
-            perms.set_mode(0o644);
-            fs::set_permissions(&path, perms)?;
-        }
+        let warning = HistoryStore::record_before_write(&path);
+        write_atomic(&path, &entry.to_ini_string())?;
 
         refresh_desktop_database(&dir);
 
-        Ok(path)
+        Ok(Written { path, warning })
     }
 
-    pub fn write_to_path(entry: &DesktopEntry, path: &Path) -> Result<PathBuf> {
+    pub fn write_to_path(entry: &DesktopEntry, path: &Path) -> Result<Written> {
         entry.validate().map_err(|e| anyhow!(e))?;
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Creating directory {}", parent.display()))?;
         }
-        if path.exists() {
-            let backup_path = PathBuf::from(format!("{}.bak", path.display()));
-            fs::copy(path, &backup_path)
-                .with_context(|| format!("Creating backup {}", backup_path.display()))?;
+        let warning = HistoryStore::record_before_write(path);
+        write_atomic(path, &entry.to_ini_string())?;
+        if let Some(parent) = path.parent() {
+            refresh_desktop_database(parent);
+        }
+        Ok(Written {
+            path: path.to_path_buf(),
+            warning,
+        })
+    }
+
+    /// Like `write_to_path`, for launchers outside an applications directory
+    /// that file managers only run once they are executable.
+    pub fn write_executable(entry: &DesktopEntry, path: &Path) -> Result<Written> {
+        entry.validate().map_err(|e| anyhow!(e))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Creating directory {}", parent.display()))?;
         }
-        let content = entry.to_ini_string();
-        fs::write(path, content).with_context(|| format!("Writing {}", path.display()))?;
+        let warning = HistoryStore::record_before_write(path);
+        write_atomic(path, &entry.to_ini_string())?;
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(path)?.permissions();
-            perms.set_mode(0o644);
-            fs::set_permissions(path, perms)?;
+            let mut perms = fs::metadata(path)
+                .with_context(|| format!("Reading {}", path.display()))?
+                .permissions();
+            perms.set_mode(perms.mode() | 0o111);
+            fs::set_permissions(path, perms)
+                .with_context(|| format!("Making {} executable", path.display()))?;
         }
-        if let Some(parent) = path.parent() {
-            refresh_desktop_database(parent);
+        Ok(Written {
+            path: path.to_path_buf(),
+            warning,
+        })
+    }
+}
+
+/// Replaces `path` with `content` without ever leaving a truncated file: the
+/// text goes to a temporary file in the same directory, is synced, and is
+/// renamed over the target. An existing file keeps its mode and ownership; a
+/// new one gets `0644`. A symlink is kept and the file it points to is
+/// replaced instead.
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let target = resolve_symlink(path)?;
+    let dir = match target.parent() {
+        Some(d) if !d.as_os_str().is_empty() => d.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let name = target
+        .file_name()
+        .ok_or_else(|| anyhow!("Not a file path: {}", target.display()))?
+        .to_string_lossy()
+        .to_string();
+    let tmp = dir.join(format!(".{}.{}.tmp", name, std::process::id()));
+    let previous = fs::metadata(&target).ok();
+
+    let result = (|| -> Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp)
+            .with_context(|| format!("Creating {}", tmp.display()))?;
+        file.write_all(content.as_bytes())
+            .with_context(|| format!("Writing {}", tmp.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{MetadataExt, PermissionsExt};
+            let mode = previous.as_ref().map_or(0o644, |m| m.mode() & 0o7777);
+            fs::set_permissions(&tmp, fs::Permissions::from_mode(mode))?;
+            // Only root can give a file away; for anyone else the owner is
+            // already right, so a failure here is expected and harmless.
+            if let Some(m) = &previous {
+                let _ = std::os::unix::fs::chown(&tmp, Some(m.uid()), Some(m.gid()));
+            }
+        }
+        file.sync_all()
+            .with_context(|| format!("Syncing {}", tmp.display()))?;
+        fs::rename(&tmp, &target).with_context(|| format!("Replacing {}", target.display()))
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+        return result;
+    }
+    // Make the rename itself durable.
+    if let Ok(d) = fs::File::open(&dir) {
+        let _ = d.sync_all();
+    }
+    Ok(())
+}
+
+// Follows a chain of symlinks to the file that should be rewritten. Relative
+// link targets are taken relative to the link's directory.
+fn resolve_symlink(path: &Path) -> Result<PathBuf> {
+    let mut current = path.to_path_buf();
+    for _ in 0..MAX_SYMLINKS {
+        match fs::symlink_metadata(&current) {
+            Ok(m) if m.file_type().is_symlink() => {
+                let link = fs::read_link(&current)
+                    .with_context(|| format!("Reading link {}", current.display()))?;
+                current = match current.parent() {
+                    Some(parent) => parent.join(link),
+                    None => link,
+                };
+            }
+            _ => return Ok(current),
         }
-        Ok(path.to_path_buf())
     }
+    Err(anyhow!(
+        "Too many levels of symbolic links: {}",
+        path.display()
+    ))
 }
 
 fn refresh_desktop_database(applications_dir: &Path) {
@@ -93,7 +189,9 @@ fn sanitize_file_name(input: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::sanitize_file_name;
+    use super::{DesktopWriter, sanitize_file_name, write_atomic};
+    use crate::domain::desktop_entry::DesktopEntry;
+    use std::fs;
 
     #[test]
     fn sanitize_file_name_replaces_unsafe_chars() {
@@ -106,4 +204,45 @@ mod tests {
         let value = sanitize_file_name("   ");
         assert_eq!(value, "desktop-entry");
     }
+
+    #[test]
+    fn saves_a_utf8_exec_path() {
+        let dir = std::env::temp_dir().join(format!("ls-utf8-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let entry = DesktopEntry::from_ini_string(
+            "[Desktop Entry]\nType=Application\nName=App\nExec=\"/home/u/Téléchargements/App.AppImage\" %U\nPath=/home/u/Téléchargements\n",
+        );
+        let written = DesktopWriter::write_to_path(&entry, &dir.join("app.desktop")).unwrap();
+        let saved = fs::read_to_string(&written.path).unwrap();
+        assert!(saved.contains("Exec=\"/home/u/Téléchargements/App.AppImage\" %U\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_atomic_keeps_mode_and_symlink() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("ls-atomic-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let real = dir.join("real.desktop");
+        fs::write(&real, "old").unwrap();
+        fs::set_permissions(&real, fs::Permissions::from_mode(0o755)).unwrap();
+        let link = dir.join("link.desktop");
+        std::os::unix::fs::symlink("real.desktop", &link).unwrap();
+
+        write_atomic(&link, "new").unwrap();
+
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&real).unwrap(), "new");
+        let mode = fs::metadata(&real).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
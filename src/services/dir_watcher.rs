@@ -0,0 +1,153 @@
+use gtk4::gio::{self, prelude::*};
+use gtk4::glib;
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+// Package managers touch many files at once; changes are reported in one
+// batch once things have been quiet for this long.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+const MAX_DEPTH: usize = 8;
+
+/// Watches directory trees, and single directories, and reports the paths
+/// that changed, debounced. Subdirectories created later in a tree are picked
+/// up on the next batch.
+pub struct DirWatcher {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    roots: RefCell<Vec<PathBuf>>,
+    flat: RefCell<Vec<PathBuf>>,
+    monitors: RefCell<Vec<(PathBuf, gio::FileMonitor)>>,
+    changed: RefCell<Vec<PathBuf>>,
+    // Restarted by every change, so a batch waits for things to go quiet.
+    timer: RefCell<Option<glib::SourceId>>,
+    on_change: Box<dyn Fn(Vec<PathBuf>)>,
+}
+
+impl DirWatcher {
+    pub fn new(on_change: impl Fn(Vec<PathBuf>) + 'static) -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                roots: RefCell::new(Vec::new()),
+                flat: RefCell::new(Vec::new()),
+                monitors: RefCell::new(Vec::new()),
+                changed: RefCell::new(Vec::new()),
+                timer: RefCell::new(None),
+                on_change: Box::new(on_change),
+            }),
+        }
+    }
+
+    /// Replaces the watched trees with `roots` and the directories watched
+    /// without their subdirectories with `flat`. Roots that do not exist yet
+    /// are watched too, so the first launcher created in them shows up.
+    pub fn watch(&self, roots: Vec<PathBuf>, flat: Vec<PathBuf>) {
+        *self.inner.roots.borrow_mut() = roots;
+        *self.inner.flat.borrow_mut() = flat;
+        arm(&self.inner);
+    }
+
+    pub fn stop(&self) {
+        self.inner.roots.borrow_mut().clear();
+        self.inner.flat.borrow_mut().clear();
+        arm(&self.inner);
+    }
+}
+
+// Monitors every directory under the roots plus the flat directories,
+// keeping the monitors that are still wanted and dropping the rest.
+fn arm(inner: &Rc<Inner>) {
+    let mut wanted = Vec::new();
+    for root in inner.roots.borrow().iter() {
+        wanted.push(root.clone());
+        collect_dirs(root, 0, &mut wanted);
+    }
+    for dir in inner.flat.borrow().iter() {
+        if !wanted.contains(dir) {
+            wanted.push(dir.clone());
+        }
+    }
+    let mut monitors = inner.monitors.borrow_mut();
+    monitors.retain(|(dir, monitor)| {
+        let keep = wanted.contains(dir);
+        if !keep {
+            monitor.cancel();
+        }
+        keep
+    });
+    for dir in wanted {
+        if monitors.iter().any(|(d, _)| *d == dir) {
+            continue;
+        }
+        let file = gio::File::for_path(&dir);
+        let Ok(monitor) =
+            file.monitor_directory(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE)
+        else {
+            continue;
+        };
+        let weak = Rc::downgrade(inner);
+        monitor.connect_changed(move |_, file, other, _| {
+            for path in [Some(file), other]
+                .into_iter()
+                .flatten()
+                .filter_map(|f| f.path())
+            {
+                record(&weak, path);
+            }
+        });
+        monitors.push((dir, monitor));
+    }
+}
+
+fn record(weak: &Weak<Inner>, path: PathBuf) {
+    let Some(inner) = weak.upgrade() else {
+        return;
+    };
+    // Temporary files from atomic saves are dot files.
+    if path
+        .file_name()
+        .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+    {
+        return;
+    }
+    {
+        let mut changed = inner.changed.borrow_mut();
+        if !changed.contains(&path) {
+            changed.push(path);
+        }
+    }
+    if let Some(timer) = inner.timer.take() {
+        timer.remove();
+    }
+    let weak = weak.clone();
+    let timer = glib::timeout_add_local_once(DEBOUNCE, move || {
+        let Some(inner) = weak.upgrade() else {
+            return;
+        };
+        // The source is done once this runs; it must not be removed again.
+        inner.timer.take();
+        let changed = inner.changed.take();
+        arm(&inner);
+        (inner.on_change)(changed);
+    });
+    *inner.timer.borrow_mut() = Some(timer);
+}
+
+fn collect_dirs(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    if depth >= MAX_DEPTH {
+        return;
+    }
+    let Ok(read) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in read.flatten().map(|e| e.path()) {
+        if path.is_dir() {
+            out.push(path.clone());
+            collect_dirs(&path, depth + 1, out);
+        }
+    }
+}
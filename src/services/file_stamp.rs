@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// What a file looked like when it was loaded: enough to tell later whether
+/// someone else rewrote it, and the text to merge against if they did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStamp {
+    pub modified: Option<SystemTime>,
+    pub len: u64,
+    pub hash: u64,
+    pub content: String,
+}
+
+impl FileStamp {
+    pub fn read(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        Ok(Self {
+            modified,
+            len: content.len() as u64,
+            hash: hash_of(&content),
+            content,
+        })
+    }
+
+    /// Whether `path` no longer holds what was stamped. Same mtime and size
+    /// means untouched; otherwise the content decides, so a tool that
+    /// rewrites the file unchanged does not count. A file that is gone has
+    /// changed.
+    pub fn changed_on_disk(&self, path: &Path) -> bool {
+        let Ok(meta) = fs::metadata(path) else {
+            return true;
+        };
+        let modified = meta.modified().ok();
+        if modified.is_some() && modified == self.modified && meta.len() == self.len {
+            return false;
+        }
+        match fs::read_to_string(path) {
+            Ok(text) => hash_of(&text) != self.hash,
+            Err(_) => true,
+        }
+    }
+}
+
+fn hash_of(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FileStamp;
+    use std::fs;
+    use std::time::Duration;
+
+    #[test]
+    fn detects_content_changes_only() {
+        let dir = std::env::temp_dir().join(format!("ls-stamp-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.desktop");
+        fs::write(&path, "[Desktop Entry]\nName=A\n").unwrap();
+        let stamp = FileStamp::read(&path).unwrap();
+        assert!(!stamp.changed_on_disk(&path));
+
+        let touch = |text: &str| {
+            fs::write(&path, text).unwrap();
+            let later = stamp.modified.unwrap() + Duration::from_secs(5);
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(later)
+                .unwrap();
+        };
+        touch("[Desktop Entry]\nName=A\n");
+        assert!(!stamp.changed_on_disk(&path));
+        touch("[Desktop Entry]\nName=B\n");
+        assert!(stamp.changed_on_disk(&path));
+        fs::remove_file(&path).unwrap();
+        assert!(stamp.changed_on_disk(&path));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
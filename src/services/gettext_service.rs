@@ -0,0 +1,40 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::locale::Locale;
+use crate::domain::po_catalog::{ImportReport, PoCatalog, apply_catalog, extract_template};
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::path::Path;
+
+pub struct GettextService;
+
+impl GettextService {
+    /// Writes a `.pot` template for `sources`, given as `(file name, entry)`.
+    pub fn export_template(sources: &[(String, DesktopEntry)], path: &Path) -> Result<()> {
+        let catalog = extract_template(sources);
+        fs::write(path, catalog.to_string()).with_context(|| format!("Writing {}", path.display()))
+    }
+
+    /// Merges a `.po` file into `entry`. The locale comes from the
+    /// `Language` header, or from the file name (`de.po`) when it is absent.
+    pub fn import(entry: &mut DesktopEntry, path: &Path) -> Result<(String, ImportReport)> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+        let catalog =
+            PoCatalog::parse(&content).map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+        let locale = catalog
+            .header_field("Language")
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .or_else(|| {
+                path.file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(str::to_string)
+            })
+            .ok_or_else(|| anyhow!("Cannot tell the language of {}", path.display()))?;
+        if Locale::parse(&locale).is_none() {
+            return Err(anyhow!("\"{}\" is not a valid locale", locale));
+        }
+        let report = apply_catalog(entry, &locale, &catalog);
+        Ok((locale, report))
+    }
+}
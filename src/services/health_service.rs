@@ -0,0 +1,174 @@
+use crate::domain::health::{HealthIssue, Probe, check_entry, relink};
+use crate::domain::locale::Locale;
+use crate::services::autostart_service::AutostartService;
+use crate::services::desktop_reader::DesktopReader;
+use crate::services::desktop_scanner::{DesktopScanner, Origin};
+use crate::services::desktop_shortcut_service::DesktopShortcutService;
+use crate::services::desktop_writer::{DesktopWriter, Written};
+use crate::services::override_service::OverrideService;
+use crate::services::trash_service::{TrashService, Trashed};
+use anyhow::Result;
+use gtk4::glib;
+use std::path::{Path, PathBuf};
+
+/// A launcher with at least one dangling key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    pub path: PathBuf,
+    pub name: String,
+    pub issues: Vec<HealthIssue>,
+}
+
+/// What removing a broken launcher did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Removed {
+    /// A user file, moved to the trash.
+    Trashed(Trashed),
+    /// A system file, hidden through this user file.
+    Hidden(Written),
+}
+
+/// Finds launchers whose program, icon or working directory is gone, and
+/// repairs them. System files are never touched: fixes go into a user
+/// override, the same as editing them by hand.
+pub struct HealthService;
+
+impl HealthService {
+    /// Checks every launcher, login item and desktop copy in effect.
+    /// `icon_in_theme` answers for icon names, which only the UI can look
+    /// up. Only launchers with issues are returned, sorted by name.
+    pub fn scan(icon_in_theme: impl Fn(&str) -> bool) -> Vec<HealthReport> {
+        let probe = SystemProbe { icon_in_theme };
+        let locale = Locale::current();
+        let mut paths: Vec<PathBuf> = DesktopScanner::scan()
+            .into_iter()
+            .chain(AutostartService::scan())
+            .map(|e| e.active.path)
+            .collect();
+        paths.extend(DesktopShortcutService::list().into_iter().map(|s| s.path));
+        let mut reports: Vec<HealthReport> = paths
+            .into_iter()
+            .filter_map(|path| {
+                let entry = DesktopReader::read_from_path(&path).ok()?;
+                let issues = check_entry(&entry, &probe);
+                (!issues.is_empty()).then(|| HealthReport {
+                    name: entry.localized_name(locale.as_ref()).to_string(),
+                    path,
+                    issues,
+                })
+            })
+            .collect();
+        reports.sort_by_key(|r| r.name.to_lowercase());
+        reports
+    }
+
+    /// Points the key of `issue` at `replacement` in the launcher at `path`
+    /// and returns the file that was written.
+    pub fn relink(path: &Path, issue: &HealthIssue, replacement: &str) -> Result<Written> {
+        let target = editable_copy(path)?;
+        let entry = relink(&DesktopReader::read_from_path(&target)?, issue, replacement);
+        DesktopWriter::write_to_path(&entry, &target)
+    }
+
+    /// Trashes a user launcher, or hides a system one.
+    pub fn remove(path: &Path) -> Result<Removed> {
+        if is_user_file(path) {
+            return TrashService::delete(path).map(Removed::Trashed);
+        }
+        if AutostartService::is_autostart(path) {
+            return AutostartService::set_enabled(path, false).map(Removed::Hidden);
+        }
+        OverrideService::hide(path).map(Removed::Hidden)
+    }
+}
+
+struct SystemProbe<F> {
+    icon_in_theme: F,
+}
+
+impl<F: Fn(&str) -> bool> Probe for SystemProbe<F> {
+    fn program_exists(&self, program: &str) -> bool {
+        if program.contains('/') {
+            return is_executable(Path::new(program));
+        }
+        glib::find_program_in_path(program).is_some()
+    }
+
+    fn icon_exists(&self, icon: &str) -> bool {
+        if icon.starts_with('/') {
+            return Path::new(icon).is_file();
+        }
+        (self.icon_in_theme)(icon)
+    }
+
+    fn is_dir(&self, path: &str) -> bool {
+        Path::new(path).is_dir()
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+fn is_user_file(path: &Path) -> bool {
+    let trees: Vec<PathBuf> = [
+        DesktopReader::user_applications_dir(),
+        AutostartService::user_dir().ok(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    owned_by_user(path, &trees, DesktopShortcutService::dir().ok().as_deref())
+}
+
+// Anything below one of the user's `trees`, or directly on the desktop. The
+// desktop may be `$HOME` itself, whose subdirectories hold Flatpak and Nix
+// exports that are not the user's to rewrite.
+fn owned_by_user(path: &Path, trees: &[PathBuf], desktop: Option<&Path>) -> bool {
+    trees.iter().any(|d| path.starts_with(d)) || desktop.is_some_and(|d| path.parent() == Some(d))
+}
+
+// The file to write a fix to: the launcher itself when it belongs to the
+// user, otherwise its user override, made now if there is none yet.
+fn editable_copy(path: &Path) -> Result<PathBuf> {
+    if is_user_file(path) {
+        return Ok(path.to_path_buf());
+    }
+    if AutostartService::is_autostart(path) {
+        return AutostartService::customize(path);
+    }
+    match DesktopScanner::find(path) {
+        Some(entry) if entry.active.origin == Origin::User => Ok(entry.active.path),
+        _ => OverrideService::customize(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::owned_by_user;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn desktop_in_home_only_owns_its_top_level() {
+        let home = Path::new("/home/u");
+        let trees = [PathBuf::from("/home/u/.local/share/applications")];
+        let owned = |p: &str| owned_by_user(Path::new(p), &trees, Some(home));
+        assert!(owned("/home/u/app.desktop"));
+        assert!(owned("/home/u/.local/share/applications/kde/app.desktop"));
+        assert!(!owned(
+            "/home/u/.local/share/flatpak/exports/share/applications/org.app.desktop"
+        ));
+        assert!(!owned(
+            "/home/u/.nix-profile/share/applications/app.desktop"
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::domain::ini_document::{IniDocument, IniGroup, render_groups};
+use crate::services::desktop_scanner::{DesktopScanner, desktop_file_id};
+use crate::services::desktop_writer::write_atomic;
+use crate::services::xdg;
+use anyhow::{Context, Result, anyhow};
+use directories::BaseDirs;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How many snapshots are kept per launcher. Zero means no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    pub max_snapshots: usize,
+    pub max_age_days: u64,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            max_snapshots: 20,
+            max_age_days: 90,
+        }
+    }
+}
+
+impl Retention {
+    /// The saved policy, or the default when none was saved.
+    pub fn load() -> Self {
+        let mut retention = Self::default();
+        let Some(text) = settings_path().and_then(|p| fs::read_to_string(p).ok()) else {
+            return retention;
+        };
+        for (key, value) in IniDocument::parse(&text).entries("History") {
+            match key {
+                "MaxSnapshots" => {
+                    retention.max_snapshots = value.parse().unwrap_or(retention.max_snapshots)
+                }
+                "MaxAgeDays" => {
+                    retention.max_age_days = value.parse().unwrap_or(retention.max_age_days)
+                }
+                _ => {}
+            }
+        }
+        retention
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = settings_path().ok_or_else(|| anyhow!("Cannot resolve config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Creating {}", dir.display()))?;
+        }
+        let mut group = IniGroup::new("History");
+        group.push("MaxSnapshots", self.max_snapshots.to_string());
+        group.push("MaxAgeDays", self.max_age_days.to_string());
+        write_atomic(&path, &render_groups(&[group]))
+    }
+}
+
+/// One saved version of a launcher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub taken: SystemTime,
+}
+
+/// Earlier versions of launchers, kept per desktop-file ID under the app's
+/// data directory instead of `.bak` files next to them.
+pub struct HistoryStore;
+
+impl HistoryStore {
+    /// Saves what is at `path` now, before it gets overwritten. Nothing is
+    /// recorded for a new file or when the newest snapshot is identical.
+    pub fn record(path: &Path) -> Result<()> {
+        let Ok(content) = fs::read_to_string(path) else {
+            return Ok(());
+        };
+        let dir = history_dir(path)?;
+        let snapshots = Self::list(path);
+        if let Some(newest) = snapshots.first()
+            && fs::read_to_string(&newest.path).is_ok_and(|c| c == content)
+        {
+            return Ok(());
+        }
+        fs::create_dir_all(&dir).with_context(|| format!("Creating {}", dir.display()))?;
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        // Two saves within the same millisecond still get their own file.
+        let mut stamp = millis;
+        while dir.join(format!("{}.desktop", stamp)).exists() {
+            stamp += 1;
+        }
+        write_atomic(&dir.join(format!("{}.desktop", stamp)), &content)?;
+        Self::prune(path, Retention::load())
+    }
+
+    /// `record` for writers: a snapshot that cannot be taken, e.g. on a full
+    /// disk, does not stop the write. The reason is returned instead, to be
+    /// shown once the write is done.
+    pub fn record_before_write(path: &Path) -> Option<String> {
+        Self::record(path).err().map(|e| {
+            format!(
+                "no history snapshot of {} was kept: {:#}",
+                path.display(),
+                e
+            )
+        })
+    }
+
+    /// Snapshots of the launcher at `path`, newest first.
+    pub fn list(path: &Path) -> Vec<Snapshot> {
+        let Ok(dir) = history_dir(path) else {
+            return Vec::new();
+        };
+        let mut snapshots: Vec<(u128, PathBuf)> = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter_map(|p| {
+                let millis = p.file_stem()?.to_str()?.parse().ok()?;
+                Some((millis, p))
+            })
+            .collect();
+        snapshots.sort_by_key(|(millis, _)| std::cmp::Reverse(*millis));
+        snapshots
+            .into_iter()
+            .map(|(millis, path)| Snapshot {
+                path,
+                taken: UNIX_EPOCH + Duration::from_millis(millis as u64),
+            })
+            .collect()
+    }
+
+    /// Puts `snapshot` back at `path`. The version it replaces is recorded
+    /// first, so a restore can itself be undone.
+    pub fn restore(snapshot: &Snapshot, path: &Path) -> Result<()> {
+        let content = fs::read_to_string(&snapshot.path)
+            .with_context(|| format!("Reading {}", snapshot.path.display()))?;
+        Self::record(path)?;
+        write_atomic(path, &content)
+    }
+
+    /// Applies `retention` to the history of `path`.
+    pub fn prune(path: &Path, retention: Retention) -> Result<()> {
+        let snapshots = Self::list(path);
+        let taken: Vec<SystemTime> = snapshots.iter().map(|s| s.taken).collect();
+        for idx in expired(&taken, SystemTime::now(), retention) {
+            let file = &snapshots[idx].path;
+            fs::remove_file(file).with_context(|| format!("Removing {}", file.display()))?;
+        }
+        Ok(())
+    }
+}
+
+// Indexes into `taken` (newest first) that fall outside the policy. The
+// newest snapshot is always kept, however old.
+fn expired(taken: &[SystemTime], now: SystemTime, retention: Retention) -> Vec<usize> {
+    taken
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(idx, time)| {
+            let too_many = retention.max_snapshots > 0 && *idx >= retention.max_snapshots;
+            let too_old = retention.max_age_days > 0
+                && now
+                    .duration_since(**time)
+                    .is_ok_and(|age| age > DAY * retention.max_age_days as u32);
+            too_many || too_old
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+// Launchers inside an applications directory are keyed by desktop-file ID,
+// so an override and its restored copies share one history. Login items
+// and desktop copies get their own namespaces; anything else is keyed by file
+// name.
+fn history_key(path: &Path) -> PathBuf {
+    if let Some(id) = DesktopScanner::applications_dirs()
+        .iter()
+        .find_map(|root| desktop_file_id(root, path))
+    {
+        return PathBuf::from(id);
+    }
+    if let Some(id) = xdg::autostart_dirs()
+        .iter()
+        .find_map(|root| desktop_file_id(root, path))
+    {
+        return Path::new("autostart").join(id);
+    }
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unnamed.desktop".to_string());
+    if xdg::desktop_dir().is_some_and(|d| path.parent() == Some(d.as_path())) {
+        return Path::new("desktop").join(name);
+    }
+    PathBuf::from(name)
+}
+
+fn history_dir(path: &Path) -> Result<PathBuf> {
+    let base = BaseDirs::new().ok_or_else(|| anyhow!("Cannot resolve user data directory"))?;
+    Ok(base
+        .data_dir()
+        .join("launcher_studio")
+        .join("history")
+        .join(history_key(path)))
+}
+
+fn settings_path() -> Option<PathBuf> {
+    BaseDirs::new().map(|b| b.config_dir().join("launcher_studio").join("history.conf"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DAY, Retention, expired};
+    use std::time::SystemTime;
+
+    #[test]
+    fn keeps_newest_within_limits() {
+        let now = SystemTime::now();
+        let taken: Vec<SystemTime> = (0..5).map(|d| now - DAY * d).collect();
+        let count_only = Retention {
+            max_snapshots: 3,
+            max_age_days: 0,
+        };
+        assert_eq!(expired(&taken, now, count_only), vec![3, 4]);
+        let age_only = Retention {
+            max_snapshots: 0,
+            max_age_days: 2,
+        };
+        assert_eq!(expired(&taken, now, age_only), vec![3, 4]);
+        let unlimited = Retention {
+            max_snapshots: 0,
+            max_age_days: 0,
+        };
+        assert!(expired(&taken, now, unlimited).is_empty());
+    }
+
+    #[test]
+    fn never_drops_the_only_snapshot() {
+        let now = SystemTime::now();
+        let old = [now - DAY * 400];
+        assert!(expired(&old, now, Retention::default()).is_empty());
+    }
+}
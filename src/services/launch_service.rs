@@ -0,0 +1,224 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::exec_command::{LaunchContext, LaunchNote};
+use crate::domain::locale::Locale;
+use anyhow::{Result, anyhow};
+use gtk4::gio::{self, prelude::*};
+use gtk4::glib;
+use std::cell::Cell;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// Terminals tried for `Terminal=true`, with the option that makes them run
+// the rest of the command line.
+const TERMINALS: &[(&str, &[&str])] = &[
+    ("xdg-terminal-exec", &[]),
+    ("x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", &["--"]),
+    ("kgx", &["-e"]),
+    ("konsole", &["-e"]),
+    ("xfce4-terminal", &["-x"]),
+    ("alacritty", &["-e"]),
+    ("kitty", &[]),
+    ("xterm", &["-e"]),
+];
+
+/// What a test launch reports, for the process with the given index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchEvent {
+    Started {
+        process: usize,
+        argv: Vec<String>,
+    },
+    Stdout {
+        process: usize,
+        line: String,
+    },
+    Stderr {
+        process: usize,
+        line: String,
+    },
+    /// The process ended with this exit code, or this signal.
+    Exited {
+        process: usize,
+        code: Option<i32>,
+        signal: Option<i32>,
+    },
+    Failed {
+        process: usize,
+        error: String,
+    },
+}
+
+/// What starting a launcher would do, worked out without starting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchPlan {
+    /// The argument vector of each process, after field-code substitution
+    /// and quote removal.
+    pub processes: Vec<Vec<String>>,
+    /// From `Path`; `None` keeps this app's working directory.
+    pub cwd: Option<PathBuf>,
+    /// Where the program was found; `None` when it is not on `PATH`.
+    pub program: Option<PathBuf>,
+    /// The terminal command put in front of each process for `Terminal=true`.
+    pub terminal: Option<Vec<String>>,
+    pub notes: Vec<LaunchNote>,
+}
+
+/// Starts launchers the way a desktop would, capturing their output.
+pub struct LaunchService;
+
+impl LaunchService {
+    /// Expands `entry` for `files`, with `%c` and `%i` in `locale`, and
+    /// resolves its program, `Path` and terminal.
+    pub fn plan(
+        entry: &DesktopEntry,
+        desktop_file: Option<&Path>,
+        files: &[String],
+        locale: Option<&Locale>,
+    ) -> Result<LaunchPlan> {
+        if entry.type_field != "Application" {
+            return Err(anyhow!("Only Application launchers can be run"));
+        }
+        let command = entry.exec_command().map_err(|e| anyhow!("Exec: {}", e))?;
+        let cwd = entry.path.as_deref().filter(|p| !p.trim().is_empty());
+        if let Some(dir) = cwd
+            && !Path::new(dir).is_dir()
+        {
+            return Err(anyhow!("Path {} is not a directory", dir));
+        }
+        let desktop_file = desktop_file.map(|p| p.to_string_lossy().to_string());
+        let icon = locale
+            .and_then(|l| entry.icon_localized.lookup(l))
+            .or(entry.icon.as_ref());
+        let ctx = LaunchContext {
+            name: entry.localized_name(locale),
+            icon: icon.map(String::as_str),
+            desktop_file: desktop_file.as_deref(),
+            files,
+        };
+        let processes = command.expand(&ctx);
+        let program =
+            processes
+                .first()
+                .and_then(|argv| argv.first())
+                .and_then(|p| match Path::new(p) {
+                    path if path.is_absolute() => path.is_file().then(|| path.to_path_buf()),
+                    _ => glib::find_program_in_path(p),
+                });
+        let terminal = if entry.terminal {
+            Some(find_terminal().ok_or_else(|| anyhow!("Terminal=true but no terminal found"))?)
+        } else {
+            None
+        };
+        Ok(LaunchPlan {
+            processes,
+            cwd: cwd.map(PathBuf::from),
+            program,
+            terminal,
+            notes: command.launch_notes(files),
+        })
+    }
+
+    /// Starts every process of `plan`. Events arrive on the main loop.
+    pub fn run(plan: &LaunchPlan, on_event: impl Fn(LaunchEvent) + 'static) {
+        let on_event: Rc<dyn Fn(LaunchEvent)> = Rc::new(on_event);
+        for (process, mut argv) in plan.processes.iter().cloned().enumerate() {
+            if let Some(prefix) = &plan.terminal {
+                argv.splice(0..0, prefix.iter().cloned());
+            }
+            on_event(LaunchEvent::Started {
+                process,
+                argv: argv.clone(),
+            });
+            // A terminal shows the output itself.
+            let flags = if plan.terminal.is_some() {
+                gio::SubprocessFlags::NONE
+            } else {
+                gio::SubprocessFlags::STDOUT_PIPE | gio::SubprocessFlags::STDERR_PIPE
+            };
+            let launcher = gio::SubprocessLauncher::new(flags);
+            if let Some(dir) = &plan.cwd {
+                launcher.set_cwd(dir);
+            }
+            let args: Vec<&OsStr> = argv.iter().map(OsStr::new).collect();
+            match launcher.spawn(&args) {
+                Ok(child) => watch(process, child, on_event.clone()),
+                Err(e) => on_event(LaunchEvent::Failed {
+                    process,
+                    error: e.to_string(),
+                }),
+            }
+        }
+    }
+}
+
+fn find_terminal() -> Option<Vec<String>> {
+    TERMINALS.iter().find_map(|(name, args)| {
+        let program = glib::find_program_in_path(name)?;
+        let mut argv = vec![program.to_string_lossy().to_string()];
+        argv.extend(args.iter().map(|a| a.to_string()));
+        Some(argv)
+    })
+}
+
+// Forwards the output lines, then the exit status once both pipes are
+// drained, so the status is always the last event of a process.
+fn watch(process: usize, child: gio::Subprocess, on_event: Rc<dyn Fn(LaunchEvent)>) {
+    let open = Rc::new(Cell::new(1));
+    let finish = {
+        let (open, child, on_event) = (open.clone(), child.clone(), on_event.clone());
+        Rc::new(move || {
+            open.set(open.get() - 1);
+            if open.get() > 0 {
+                return;
+            }
+            let (code, signal) = if child.has_exited() {
+                (Some(child.exit_status()), None)
+            } else if child.has_signaled() {
+                (None, Some(child.term_sig()))
+            } else {
+                (None, None)
+            };
+            on_event(LaunchEvent::Exited {
+                process,
+                code,
+                signal,
+            });
+        })
+    };
+    for (pipe, is_stderr) in [(child.stdout_pipe(), false), (child.stderr_pipe(), true)] {
+        if let Some(pipe) = pipe {
+            open.set(open.get() + 1);
+            let stream = gio::DataInputStream::new(&pipe);
+            read_lines(stream, process, is_stderr, on_event.clone(), finish.clone());
+        }
+    }
+    child.wait_async(gio::Cancellable::NONE, move |_| finish());
+}
+
+fn read_lines(
+    stream: gio::DataInputStream,
+    process: usize,
+    is_stderr: bool,
+    on_event: Rc<dyn Fn(LaunchEvent)>,
+    done: Rc<dyn Fn()>,
+) {
+    let next = stream.clone();
+    stream.read_line_async(
+        glib::Priority::DEFAULT,
+        gio::Cancellable::NONE,
+        move |result| match result {
+            Ok(Some(bytes)) => {
+                let line = String::from_utf8_lossy(&bytes).into_owned();
+                on_event(if is_stderr {
+                    LaunchEvent::Stderr { process, line }
+                } else {
+                    LaunchEvent::Stdout { process, line }
+                });
+                read_lines(next, process, is_stderr, on_event, done);
+            }
+            _ => done(),
+        },
+    );
+}
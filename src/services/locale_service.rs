@@ -0,0 +1,55 @@
+use crate::domain::locale::Locale;
+use std::fs;
+use std::process::Command;
+
+const SUPPORTED_LOCALES: &str = "/usr/share/i18n/SUPPORTED";
+
+pub struct LocaleService;
+
+impl LocaleService {
+    /// Locale codes offered when adding a translation: the system's supported
+    /// list (or `locale -a` as a fallback), without encodings, plus the bare
+    /// language of each, sorted and deduplicated.
+    pub fn available_locales() -> Vec<String> {
+        let raw = fs::read_to_string(SUPPORTED_LOCALES)
+            .ok()
+            .or_else(|| {
+                Command::new("locale")
+                    .arg("-a")
+                    .output()
+                    .ok()
+                    .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+            })
+            .unwrap_or_default();
+        parse_locale_list(&raw)
+    }
+}
+
+fn parse_locale_list(raw: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    for token in raw.lines().filter_map(|l| l.split_whitespace().next()) {
+        let Some(mut locale) = Locale::parse(token) else {
+            continue;
+        };
+        locale.encoding = None;
+        out.push(locale.lang.clone());
+        out.push(locale.to_string());
+    }
+    out.sort();
+    out.dedup();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_locale_list;
+
+    #[test]
+    fn parses_supported_and_locale_a_formats() {
+        let raw = "C.UTF-8 UTF-8\nde_DE.UTF-8 UTF-8\nde_DE ISO-8859-1\nsr_RS@latin UTF-8\nen_US.utf8\nPOSIX\n";
+        assert_eq!(
+            parse_locale_list(raw),
+            vec!["de", "de_DE", "en", "en_US", "sr", "sr_RS@latin"]
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use crate::domain::locale::Locale;
+use crate::domain::mime_apps::{self, MimeApps, is_associated, resolve_default};
+use crate::services::desktop_reader::DesktopReader;
+use crate::services::desktop_scanner::DesktopScanner;
+use crate::services::desktop_writer::write_atomic;
+use crate::services::xdg;
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Who opens one MIME type or URI scheme, and how a launcher stands with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Handler {
+    pub mime: String,
+    /// The desktop-file ID of the default, if any is installed.
+    pub default: Option<String>,
+    /// The default's name, read from its launcher.
+    pub default_name: Option<String>,
+    /// The `mimeapps.list` that sets the default.
+    pub source: Option<PathBuf>,
+    /// Whether the launcher asked about is still offered for the type, or
+    /// taken out by `Removed Associations`.
+    pub associated: bool,
+}
+
+/// Reads and writes the `mimeapps.list` files that pick default
+/// applications. Only the user's own files are ever written.
+pub struct MimeAppsService;
+
+impl MimeAppsService {
+    /// The file defaults are written to: `$XDG_CONFIG_HOME/mimeapps.list`.
+    pub fn user_file() -> Option<PathBuf> {
+        xdg::config_home().map(|c| c.join("mimeapps.list"))
+    }
+
+    /// Every `mimeapps.list` in lookup order, highest priority first: per
+    /// config dir, then per data dir's `applications`, the files of the
+    /// current desktops before the generic one.
+    pub fn lookup_files() -> Vec<PathBuf> {
+        let desktops: Vec<String> = xdg::current_desktops()
+            .iter()
+            .map(|d| d.to_lowercase())
+            .collect();
+        let dirs = xdg::config_dirs()
+            .into_iter()
+            .chain(xdg::data_dirs().into_iter().map(|d| d.join("applications")));
+        let mut files = Vec::new();
+        for dir in dirs {
+            for desktop in &desktops {
+                files.push(dir.join(format!("{}-mimeapps.list", desktop)));
+            }
+            files.push(dir.join("mimeapps.list"));
+        }
+        files
+    }
+
+    /// The desktop-file ID of the launcher at `path`, which only launchers
+    /// in an applications directory have.
+    pub fn desktop_id(path: &Path) -> Result<String> {
+        DesktopScanner::find(path).map(|e| e.id).ok_or_else(|| {
+            anyhow!(
+                "{} is not in an applications folder, so it cannot be a default application",
+                path.display()
+            )
+        })
+    }
+
+    /// The current default of each of `mimes`, and whether `id` is
+    /// associated with it.
+    pub fn handlers(mimes: &[String], id: &str) -> Vec<Handler> {
+        let paths = Self::lookup_files();
+        let files: Vec<MimeApps> = paths
+            .iter()
+            .map(|p| MimeApps::parse(&fs::read_to_string(p).unwrap_or_default()))
+            .collect();
+        let installed: HashMap<String, PathBuf> = DesktopScanner::scan()
+            .into_iter()
+            .map(|e| (e.id, e.active.path))
+            .collect();
+        let locale = Locale::current();
+        mimes
+            .iter()
+            .map(|mime| {
+                let found = resolve_default(&files, mime, |i| installed.contains_key(i));
+                let default_name = found
+                    .as_ref()
+                    .and_then(|(_, i)| DesktopReader::read_from_path(&installed[i]).ok())
+                    .map(|e| e.localized_name(locale.as_ref()).to_string());
+                Handler {
+                    mime: mime.clone(),
+                    source: found.as_ref().map(|(idx, _)| paths[*idx].clone()),
+                    default: found.map(|(_, i)| i),
+                    default_name,
+                    associated: is_associated(&files, mime, id),
+                }
+            })
+            .collect()
+    }
+
+    /// Makes `id` the default for every type in `mimes`, and returns the
+    /// files written. A desktop-specific file of the user's that already
+    /// sets one of the types is updated too, since it would otherwise win.
+    pub fn set_default(mimes: &[String], id: &str) -> Result<Vec<PathBuf>> {
+        Self::edit(mimes, true, |content, mime| {
+            mime_apps::set_default(content, mime, id)
+        })
+    }
+
+    /// Stops `id` being the user's default for `mimes`, so the system
+    /// default applies again.
+    pub fn unset_default(mimes: &[String], id: &str) -> Result<Vec<PathBuf>> {
+        Self::edit(mimes, false, |content, mime| {
+            mime_apps::unset_default(content, mime, id)
+        })
+    }
+
+    // Applies `change` to the user's generic file (created when `create`)
+    // and to each of their desktop-specific files that names the type.
+    fn edit(
+        mimes: &[String],
+        create: bool,
+        change: impl Fn(&str, &str) -> String,
+    ) -> Result<Vec<PathBuf>> {
+        let config = xdg::config_home().ok_or_else(|| anyhow!("No config directory"))?;
+        let mut paths: Vec<PathBuf> = xdg::current_desktops()
+            .iter()
+            .map(|d| config.join(format!("{}-mimeapps.list", d.to_lowercase())))
+            .collect();
+        paths.push(config.join("mimeapps.list"));
+        let generic = paths.len() - 1;
+        let mut written = Vec::new();
+        for (idx, path) in paths.iter().enumerate() {
+            let exists = path.exists();
+            if !(exists || create && idx == generic) {
+                continue;
+            }
+            let content = if exists {
+                fs::read_to_string(path)?
+            } else {
+                String::new()
+            };
+            let parsed = MimeApps::parse(&content);
+            let mut updated = content.clone();
+            for mime in mimes {
+                if idx == generic || parsed.defaults.contains_key(mime) {
+                    updated = change(&updated, mime);
+                }
+            }
+            if updated != content {
+                fs::create_dir_all(&config)?;
+                write_atomic(path, &updated)?;
+                written.push(path.clone());
+            }
+        }
+        Ok(written)
+    }
+}
@@ -0,0 +1,200 @@
+use crate::domain::validation::{MimeLookup, SCHEME_HANDLER_PREFIX};
+use crate::services::xdg;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MimeType {
+    pub name: String,
+    pub comment: Option<String>,
+    pub globs: Vec<String>,
+    pub parents: Vec<String>,
+}
+
+/// The shared-mime-info database as installed: every `mime` directory under
+/// the XDG data dirs, with the user's own taking precedence.
+#[derive(Debug, Default)]
+pub struct MimeDatabase {
+    types: BTreeMap<String, MimeType>,
+    aliases: HashMap<String, String>,
+}
+
+impl MimeDatabase {
+    pub fn load() -> Self {
+        let mut db = Self::default();
+        // Lowest priority first, so later directories override earlier ones.
+        for dir in xdg::data_dirs().into_iter().rev() {
+            let dir = dir.join("mime");
+            let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+            if let Some(text) = read("types") {
+                for name in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                    db.entry(name);
+                }
+            }
+            if let Some(text) = read("globs2") {
+                for (name, glob) in parse_globs2(&text) {
+                    let globs = &mut db.entry(&name).globs;
+                    if !globs.contains(&glob) {
+                        globs.push(glob);
+                    }
+                }
+            }
+            if let Some(text) = read("aliases") {
+                db.aliases.extend(parse_pairs(&text));
+            }
+            if let Some(text) = read("subclasses") {
+                for (child, parent) in parse_pairs(&text) {
+                    let parents = &mut db.entry(&child).parents;
+                    if !parents.contains(&parent) {
+                        parents.push(parent);
+                    }
+                }
+            }
+            let packages = fs::read_dir(dir.join("packages"))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|e| e == "xml"));
+            for path in packages {
+                let Ok(text) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                for (name, comment) in parse_package_comments(&text) {
+                    db.entry(&name).comment = Some(comment);
+                }
+            }
+        }
+        db
+    }
+
+    fn entry(&mut self, name: &str) -> &mut MimeType {
+        self.types
+            .entry(name.to_string())
+            .or_insert_with(|| MimeType {
+                name: name.to_string(),
+                ..MimeType::default()
+            })
+    }
+
+    /// Without an installed database nothing can be judged, so every type
+    /// counts as known, as do the scheme handlers it never lists.
+    pub fn lookup(&self, name: &str) -> MimeLookup {
+        if self.types.is_empty()
+            || self.types.contains_key(name)
+            || name.starts_with(SCHEME_HANDLER_PREFIX)
+        {
+            MimeLookup::Known
+        } else if let Some(canonical) = self.aliases.get(name) {
+            MimeLookup::Alias(canonical.clone())
+        } else {
+            MimeLookup::Unknown
+        }
+    }
+
+    /// The description of `name` or of the type it is an alias of.
+    pub fn comment(&self, name: &str) -> Option<&str> {
+        let name = self.aliases.get(name).map_or(name, String::as_str);
+        self.types.get(name)?.comment.as_deref()
+    }
+
+    /// Types whose name, description or glob patterns contain `query`.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<&MimeType> {
+        let query = query.trim().to_lowercase();
+        self.types
+            .values()
+            .filter(|t| {
+                query.is_empty()
+                    || t.name.contains(&query)
+                    || t.comment
+                        .as_ref()
+                        .is_some_and(|c| c.to_lowercase().contains(&query))
+                    || t.globs.iter().any(|g| g.contains(&query))
+            })
+            .take(limit)
+            .collect()
+    }
+}
+
+// `weight:type:glob[:flags]` lines.
+fn parse_globs2(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(|l| {
+            let mut parts = l.splitn(4, ':');
+            let (_, name, glob) = (parts.next()?, parts.next()?, parts.next()?);
+            Some((name.to_string(), glob.to_string()))
+        })
+        .collect()
+}
+
+// `aliases` and `subclasses`: two whitespace-separated types per line.
+fn parse_pairs(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|l| {
+            let mut parts = l.split_whitespace();
+            Some((parts.next()?.to_string(), parts.next()?.to_string()))
+        })
+        .collect()
+}
+
+// Untranslated `<comment>` of each `<mime-type>` in a packages XML file. The
+// files are generated one element per line, which keeps this a line scan.
+fn parse_package_comments(xml: &str) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    let mut current: Option<String> = None;
+    for line in xml.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("<mime-type type=\"") {
+            current = rest.split('"').next().map(str::to_string);
+        } else if line.starts_with("</mime-type>") {
+            current = None;
+        } else if let Some(rest) = line.strip_prefix("<comment>")
+            && let Some(text) = rest.strip_suffix("</comment>")
+            && let Some(name) = current.take()
+        {
+            out.push((name, unescape_xml(text)));
+        }
+    }
+    out
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_globs2, parse_package_comments, parse_pairs};
+
+    #[test]
+    fn parses_generated_files() {
+        let globs = "# generated\n80:text/html:*.html\n50:text/x-csrc:*.c:cs\n";
+        assert_eq!(
+            parse_globs2(globs),
+            vec![
+                ("text/html".to_string(), "*.html".to_string()),
+                ("text/x-csrc".to_string(), "*.c".to_string())
+            ]
+        );
+        assert_eq!(
+            parse_pairs("image/jpg image/jpeg\n\n"),
+            vec![("image/jpg".to_string(), "image/jpeg".to_string())]
+        );
+    }
+
+    #[test]
+    fn takes_untranslated_comment() {
+        let xml = "<mime-info>\n  <mime-type type=\"image/png\">\n    <comment xml:lang=\"de\">PNG-Bild</comment>\n    <comment>PNG image</comment>\n    <comment xml:lang=\"fr\">image PNG</comment>\n  </mime-type>\n  <mime-type type=\"text/x-c\">\n    <comment>C source &amp; header</comment>\n  </mime-type>\n</mime-info>\n";
+        assert_eq!(
+            parse_package_comments(xml),
+            vec![
+                ("image/png".to_string(), "PNG image".to_string()),
+                ("text/x-c".to_string(), "C source & header".to_string())
+            ]
+        );
+    }
+}
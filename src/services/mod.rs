@@ -1,3 +1,18 @@
+pub mod autostart_service;
 pub mod desktop_reader;
+pub mod desktop_scanner;
+pub mod desktop_shortcut_service;
 pub mod desktop_writer;
+pub mod dir_watcher;
+pub mod file_stamp;
+pub mod gettext_service;
+pub mod health_service;
+pub mod history_store;
+pub mod launch_service;
+pub mod locale_service;
 pub mod lucide_service;
+pub mod mime_apps_service;
+pub mod mime_database;
+pub mod override_service;
+pub mod trash_service;
+pub mod xdg;
@@ -0,0 +1,144 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::entry_diff::{KeyChange, diff_entries};
+use crate::services::desktop_reader::DesktopReader;
+use crate::services::desktop_scanner::{DesktopScanner, Origin, ScannedEntry};
+use crate::services::desktop_writer::{DesktopWriter, Written, write_atomic};
+use crate::services::history_store::HistoryStore;
+use anyhow::{Context, Result, anyhow};
+use directories::BaseDirs;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The system launcher behind a user override.
+pub struct SystemVersion {
+    pub id: String,
+    pub path: PathBuf,
+    pub entry: DesktopEntry,
+    /// What changed in the system file since the override was made, when a
+    /// snapshot of the original was recorded.
+    pub upstream: Vec<KeyChange>,
+}
+
+/// User copies of system launchers. A file in the user applications
+/// directory with the same desktop-file ID shadows the system one, so
+/// removing it brings the original back.
+pub struct OverrideService;
+
+impl OverrideService {
+    /// Copies the system launcher at `path` into the user directory under the
+    /// same ID and returns the copy.
+    pub fn customize(path: &Path) -> Result<PathBuf> {
+        let entry = active_entry(path)?;
+        if entry.active.origin == Origin::User {
+            return Err(anyhow!("{} is already a user launcher", entry.id));
+        }
+        let dir = DesktopWriter::user_applications_dir()?;
+        fs::create_dir_all(&dir).context("Creating applications directory")?;
+        let target = dir.join(&entry.id);
+        // The base goes first: a copy without one could not be customized
+        // again, as it already shadows the system file.
+        Self::record_base(&entry.id, path)?;
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+        write_atomic(&target, &content)?;
+        Ok(target)
+    }
+
+    /// Where changes to the launcher at `path` are written: the file itself,
+    /// unless it is a system launcher, which gets a user override first.
+    pub fn writable_copy(path: &Path) -> Result<PathBuf> {
+        match DesktopScanner::find(path) {
+            Some(e) if e.active.path == path && e.active.origin != Origin::User => {
+                Self::customize(path)
+            }
+            _ => Ok(path.to_path_buf()),
+        }
+    }
+
+    /// Hides the launcher with `Hidden=true`, which the spec treats as
+    /// deleted. System launchers get an override; user ones are edited.
+    pub fn hide(path: &Path) -> Result<Written> {
+        let entry = active_entry(path)?;
+        let target = if entry.active.origin == Origin::User {
+            path.to_path_buf()
+        } else {
+            Self::customize(path)?
+        };
+        let mut de = DesktopReader::read_from_path(&target)?;
+        de.hidden = true;
+        DesktopWriter::write_to_path(&de, &target)
+    }
+
+    /// Deletes the user override at `path` and returns the system launcher
+    /// that takes its place. The override is snapshotted first, so the
+    /// customizations can be brought back from its history.
+    pub fn revert(path: &Path) -> Result<PathBuf> {
+        let entry = active_entry(path)?;
+        let original = match (entry.active.origin, entry.shadowed.first()) {
+            (Origin::User, Some(original)) => original.path.clone(),
+            _ => return Err(anyhow!("{} does not override a system launcher", entry.id)),
+        };
+        HistoryStore::record(path)?;
+        fs::remove_file(path).with_context(|| format!("Removing {}", path.display()))?;
+        if let Ok(base) = base_path(&entry.id) {
+            let _ = fs::remove_file(base);
+        }
+        Ok(original)
+    }
+
+    /// The system launcher that the user override at `path` shadows.
+    pub fn system_version(path: &Path) -> Result<SystemVersion> {
+        let entry = active_entry(path)?;
+        let original = match (entry.active.origin, entry.shadowed.first()) {
+            (Origin::User, Some(original)) => original.path.clone(),
+            _ => return Err(anyhow!("{} does not override a system launcher", entry.id)),
+        };
+        let system = DesktopReader::read_from_path(&original)?;
+        let upstream = base_path(&entry.id)
+            .ok()
+            .and_then(|base| fs::read_to_string(base).ok())
+            .map(|base| diff_entries(&DesktopEntry::from_ini_string(&base), &system))
+            .unwrap_or_default();
+        Ok(SystemVersion {
+            id: entry.id,
+            path: original,
+            entry: system,
+            upstream,
+        })
+    }
+
+    /// Snapshots the system file `original` as the version the override of
+    /// `id` is based on, so later package updates can be spotted.
+    pub fn record_base(id: &str, original: &Path) -> Result<()> {
+        let base = base_path(id)?;
+        if let Some(dir) = base.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Creating directory {}", dir.display()))?;
+        }
+        fs::copy(original, &base).with_context(|| format!("Writing {}", base.display()))?;
+        Ok(())
+    }
+}
+
+// The scanned entry for `path`, which must be the file the menu uses.
+fn active_entry(path: &Path) -> Result<ScannedEntry> {
+    let entry = DesktopScanner::find(path)
+        .ok_or_else(|| anyhow!("{} is not in an applications directory", path.display()))?;
+    if entry.active.path != path {
+        return Err(anyhow!(
+            "{} is shadowed by {}",
+            path.display(),
+            entry.active.path.display()
+        ));
+    }
+    Ok(entry)
+}
+
+fn base_path(id: &str) -> Result<PathBuf> {
+    let base = BaseDirs::new().ok_or_else(|| anyhow!("Cannot resolve user data directory"))?;
+    Ok(base
+        .data_dir()
+        .join("launcher_studio")
+        .join("bases")
+        .join(id))
+}
@@ -0,0 +1,138 @@
+use crate::domain::ini_document::IniDocument;
+use crate::domain::uri::percent_decode;
+use crate::services::xdg;
+use anyhow::{Context, Result, anyhow};
+use gtk4::gio::{self, prelude::*};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where a deleted launcher went, so it can be put back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Trashed {
+    /// In the freedesktop trash, as `file` with its `.trashinfo`.
+    Trash {
+        original: PathBuf,
+        file: PathBuf,
+        info: PathBuf,
+    },
+    /// Moved aside to the app's data dir because no trash was available.
+    Stash { original: PathBuf, stashed: PathBuf },
+    /// Trashed, but somewhere this app cannot find to undo, such as the
+    /// trash of another mount.
+    Untracked { original: PathBuf },
+}
+
+impl Trashed {
+    pub fn original(&self) -> &Path {
+        match self {
+            Trashed::Trash { original, .. }
+            | Trashed::Stash { original, .. }
+            | Trashed::Untracked { original } => original,
+        }
+    }
+}
+
+pub struct TrashService;
+
+impl TrashService {
+    /// Moves `path` to the trash, or aside into the app's data dir when the
+    /// file system has no trash.
+    pub fn delete(path: &Path) -> Result<Trashed> {
+        match gio::File::for_path(path).trash(gio::Cancellable::NONE) {
+            Ok(()) => Ok(
+                find_in_home_trash(path).unwrap_or_else(|| Trashed::Untracked {
+                    original: path.to_path_buf(),
+                }),
+            ),
+            Err(e) if e.matches(gio::IOErrorEnum::NotSupported) => stash(path),
+            Err(e) => Err(anyhow!("Moving {} to the trash: {}", path.display(), e)),
+        }
+    }
+
+    /// Puts a deleted launcher back where it was.
+    pub fn restore(trashed: &Trashed) -> Result<PathBuf> {
+        let original = trashed.original();
+        if original.exists() {
+            return Err(anyhow!("{} already exists", original.display()));
+        }
+        match trashed {
+            Trashed::Trash { file, info, .. } => {
+                move_file(file, original)?;
+                let _ = fs::remove_file(info);
+            }
+            Trashed::Stash { stashed, .. } => move_file(stashed, original)?,
+            Trashed::Untracked { .. } => {
+                return Err(anyhow!(
+                    "{} is in a trash this app cannot reach; restore it from the file manager",
+                    original.display()
+                ));
+            }
+        }
+        Ok(original.to_path_buf())
+    }
+}
+
+// The newest entry of `$XDG_DATA_HOME/Trash` that came from `original`.
+fn find_in_home_trash(original: &Path) -> Option<Trashed> {
+    let trash = xdg::data_home()?.join("Trash");
+    let mut best: Option<(String, PathBuf, PathBuf)> = None;
+    for info in fs::read_dir(trash.join("info")).ok()?.flatten() {
+        let info = info.path();
+        let Ok(text) = fs::read_to_string(&info) else {
+            continue;
+        };
+        let document = IniDocument::parse(&text);
+        let entries = document.entries("Trash Info");
+        let value = |key: &str| entries.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+        // `.trashinfo` paths are URL-escaped.
+        if value("Path").map(percent_decode).as_deref() != Some(&*original.to_string_lossy()) {
+            continue;
+        }
+        let date = value("DeletionDate").unwrap_or_default().to_string();
+        let Some(name) = info.file_stem() else {
+            continue;
+        };
+        let file = trash.join("files").join(name);
+        if best.as_ref().is_none_or(|(d, _, _)| date >= *d) {
+            best = Some((date, file, info));
+        }
+    }
+    best.map(|(_, file, info)| Trashed::Trash {
+        original: original.to_path_buf(),
+        file,
+        info,
+    })
+}
+
+fn stash(path: &Path) -> Result<Trashed> {
+    let dir = xdg::data_home()
+        .ok_or_else(|| anyhow!("Cannot resolve user data directory"))?
+        .join("launcher_studio")
+        .join("deleted");
+    fs::create_dir_all(&dir).with_context(|| format!("Creating {}", dir.display()))?;
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stashed = dir.join(format!("{}-{}", millis, name));
+    move_file(path, &stashed)?;
+    Ok(Trashed::Stash {
+        original: path.to_path_buf(),
+        stashed,
+    })
+}
+
+// `rename`, falling back to copy and remove across file systems.
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)
+        .with_context(|| format!("Copying {} to {}", from.display(), to.display()))?;
+    fs::remove_file(from).with_context(|| format!("Removing {}", from.display()))
+}
@@ -0,0 +1,126 @@
+use directories::BaseDirs;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `$XDG_DATA_HOME`, usually `~/.local/share`.
+pub fn data_home() -> Option<PathBuf> {
+    BaseDirs::new().map(|b| b.data_dir().to_path_buf())
+}
+
+/// `$XDG_DATA_HOME` followed by `$XDG_DATA_DIRS`, highest priority first.
+/// The latter is where system, Flatpak, Snap and Nix exports are listed.
+pub fn data_dirs() -> Vec<PathBuf> {
+    let system = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    let mut dirs: Vec<PathBuf> = data_home().into_iter().collect();
+    for dir in system
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+    {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// `$XDG_CONFIG_HOME`, usually `~/.config`.
+pub fn config_home() -> Option<PathBuf> {
+    BaseDirs::new().map(|b| b.config_dir().to_path_buf())
+}
+
+/// `$XDG_CONFIG_HOME` followed by `$XDG_CONFIG_DIRS`, highest priority first.
+pub fn config_dirs() -> Vec<PathBuf> {
+    let system = env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".into());
+    let mut dirs: Vec<PathBuf> = config_home().into_iter().collect();
+    for dir in system
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+    {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// The `autostart` directory of each config dir, highest priority first.
+pub fn autostart_dirs() -> Vec<PathBuf> {
+    config_dirs()
+        .into_iter()
+        .map(|d| d.join("autostart"))
+        .collect()
+}
+
+/// The desktops named in `$XDG_CURRENT_DESKTOP`, e.g. `ubuntu:GNOME`.
+pub fn current_desktops() -> Vec<String> {
+    env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// `XDG_DESKTOP_DIR` from `$XDG_CONFIG_HOME/user-dirs.dirs`, or `~/Desktop`
+/// when it is not set there.
+pub fn desktop_dir() -> Option<PathBuf> {
+    let home = BaseDirs::new()?.home_dir().to_path_buf();
+    let configured = config_home()
+        .and_then(|c| fs::read_to_string(c.join("user-dirs.dirs")).ok())
+        .and_then(|text| parse_user_dir(&text, "XDG_DESKTOP_DIR", &home));
+    Some(configured.unwrap_or_else(|| home.join("Desktop")))
+}
+
+// `user-dirs.dirs` lines look like `XDG_DESKTOP_DIR="$HOME/Desktop"`: a
+// quoted path that is either absolute or starts with `$HOME`.
+fn parse_user_dir(text: &str, key: &str, home: &Path) -> Option<PathBuf> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .filter(|(k, _)| k.trim() == key)
+        .filter_map(|(_, v)| {
+            let value = v.trim().strip_prefix('"')?.strip_suffix('"')?;
+            let value = value.replace("\\\"", "\"");
+            match value.strip_prefix("$HOME") {
+                Some(rest) => Some(home.join(rest.trim_start_matches('/'))),
+                None if value.starts_with('/') => Some(PathBuf::from(value)),
+                None => None,
+            }
+        })
+        .next_back()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_user_dir;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn reads_user_dirs_file() {
+        let home = Path::new("/home/u");
+        let text = "# written by xdg-user-dirs-update\nXDG_DESKTOP_DIR=\"$HOME/Bureau\"\nXDG_MUSIC_DIR=\"/srv/music\"\n";
+        assert_eq!(
+            parse_user_dir(text, "XDG_DESKTOP_DIR", home),
+            Some(PathBuf::from("/home/u/Bureau"))
+        );
+        assert_eq!(
+            parse_user_dir(text, "XDG_MUSIC_DIR", home),
+            Some(PathBuf::from("/srv/music"))
+        );
+        assert_eq!(parse_user_dir(text, "XDG_VIDEOS_DIR", home), None);
+        assert_eq!(
+            parse_user_dir("XDG_DESKTOP_DIR=\"$HOME/\"", "XDG_DESKTOP_DIR", home),
+            Some(PathBuf::from("/home/u/"))
+        );
+    }
+}
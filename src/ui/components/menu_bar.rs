@@ -2,7 +2,7 @@ use gtk4::Application;
 use gtk4::PopoverMenuBar;
 use gtk4::gio::Menu;
 
-// Builds the application menu bar with File/View/Tools/Help/Credits.
+// Builds the application menu bar with File/View/Launcher/Tools/Help/Credits.
 // It wires no actions itself; it only defines the action names expected by the main window.
 pub fn build_menu_bar(_app: &Application) -> PopoverMenuBar {
     let menu_model = Menu::new();
@@ -13,6 +13,7 @@ pub fn build_menu_bar(_app: &Application) -> PopoverMenuBar {
     file_menu.append(Some("Open"), Some("app.open"));
     file_menu.append(Some("Save"), Some("app.save"));
     file_menu.append(Some("Save As"), Some("app.save_as"));
+    file_menu.append(Some("Version History"), Some("app.show_history"));
     file_menu.append(Some("Refresh"), Some("app.refresh"));
     file_menu.append(Some("Quit"), Some("app.quit"));
     menu_model.append_submenu(Some("File"), &file_menu);
@@ -22,6 +23,28 @@ pub fn build_menu_bar(_app: &Application) -> PopoverMenuBar {
     view_menu.append(Some("Toggle Fullscreen"), Some("win.toggle_fullscreen"));
     menu_model.append_submenu(Some("View"), &view_menu);
 
+    // Launcher menu
+    let launcher_menu = Menu::new();
+    launcher_menu.append(Some("Run"), Some("app.run_launcher"));
+    launcher_menu.append(
+        Some("Customize System Launcher"),
+        Some("app.customize_launcher"),
+    );
+    launcher_menu.append(Some("Hide Launcher"), Some("app.hide_launcher"));
+    launcher_menu.append(
+        Some("Compare with System Version"),
+        Some("app.compare_launcher"),
+    );
+    launcher_menu.append(
+        Some("Revert to System Default"),
+        Some("app.revert_launcher"),
+    );
+    launcher_menu.append(Some("Add to Autostart"), Some("app.add_to_autostart"));
+    launcher_menu.append(Some("Autostart Delay"), Some("app.autostart_delay"));
+    launcher_menu.append(Some("Place on Desktop"), Some("app.place_on_desktop"));
+    launcher_menu.append(Some("Default Applications..."), Some("app.default_apps"));
+    menu_model.append_submenu(Some("Launcher"), &launcher_menu);
+
     // Tools menu
     let tools_menu = Menu::new();
     tools_menu.append(
@@ -29,6 +52,16 @@ pub fn build_menu_bar(_app: &Application) -> PopoverMenuBar {
         Some("app.open_system_dir"),
     );
     tools_menu.append(Some("Open User Applications"), Some("app.open_user_dir"));
+    tools_menu.append(Some("Check Launcher Health..."), Some("app.check_health"));
+    tools_menu.append(
+        Some("Export Translation Template..."),
+        Some("app.export_pot"),
+    );
+    tools_menu.append(
+        Some("Export Template for All Launchers..."),
+        Some("app.export_pot_all"),
+    );
+    tools_menu.append(Some("Import Translations (.po)..."), Some("app.import_po"));
     menu_model.append_submenu(Some("Tools"), &tools_menu);
 
     // Help menu
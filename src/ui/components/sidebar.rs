@@ -14,7 +14,8 @@ pub fn build_sidebar() -> Sidebar {
     let listbox = ListBox::new();
     // Apply Adwaita navigation sidebar styling
     listbox.add_css_class("navigation-sidebar");
-    listbox.set_selection_mode(gtk4::SelectionMode::Single);
+    // Several launchers can be selected for deletion with Ctrl or Shift.
+    listbox.set_selection_mode(gtk4::SelectionMode::Multiple);
     container.set_child(Some(&listbox));
     Sidebar { container, listbox }
 }
@@ -1,3 +1,4 @@
+use crate::domain::po_catalog::ImportReport;
 use adw::{AboutDialog, prelude::*};
 use gtk4::{ResponseType, Window};
 
@@ -50,19 +51,90 @@ pub fn show_credits<W: IsA<gtk4::Widget>>(parent: &W) {
     about.present(Some(parent));
 }
 
-pub fn confirm_delete<W: IsA<Window>, F>(parent: &W, path: &std::path::Path, on_confirm: F)
+pub fn confirm_revert<W: IsA<Window>, F>(parent: &W, path: &std::path::Path, on_confirm: F)
 where
     F: Fn() + 'static,
 {
     let dialog = gtk4::MessageDialog::builder()
         .transient_for(parent)
         .modal(true)
-        .title("Confirm deletion")
-        .text("Delete selected .desktop file?")
-        .secondary_text(format!("This will permanently remove:\n{}", path.display()))
+        .title("Revert to system default")
+        .text("Remove your customized copy?")
+        .secondary_text(format!(
+            "The system launcher will be used again. This removes, keeping a copy in History:\n{}",
+            path.display()
+        ))
+        .build();
+    dialog.add_button("Cancel", ResponseType::Cancel);
+    dialog.add_button("Revert", ResponseType::Accept);
+    dialog.connect_response(move |d, resp| {
+        if resp == ResponseType::Accept {
+            on_confirm();
+        }
+        d.close();
+    });
+    dialog.show();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictChoice {
+    Overwrite,
+    Reload,
+    Merge,
+}
+
+pub fn resolve_conflict<W: IsA<Window>, F>(parent: &W, path: &std::path::Path, on_choice: F)
+where
+    F: Fn(ConflictChoice) + 'static,
+{
+    let dialog = gtk4::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("File changed on disk")
+        .text("The launcher was modified since you opened it")
+        .secondary_text(format!(
+            "Another program changed:\n{}\n\nOverwrite discards its changes, Reload discards yours, Merge combines both.",
+            path.display()
+        ))
         .build();
     dialog.add_button("Cancel", ResponseType::Cancel);
-    dialog.add_button("Delete", ResponseType::Accept);
+    dialog.add_button("Reload", ResponseType::Other(1));
+    dialog.add_button("Overwrite", ResponseType::Other(2));
+    dialog.add_button("Merge", ResponseType::Accept);
+    dialog.connect_response(move |d, resp| {
+        d.close();
+        match resp {
+            ResponseType::Other(1) => on_choice(ConflictChoice::Reload),
+            ResponseType::Other(2) => on_choice(ConflictChoice::Overwrite),
+            ResponseType::Accept => on_choice(ConflictChoice::Merge),
+            _ => {}
+        }
+    });
+    dialog.show();
+}
+
+pub fn confirm_reload<W: IsA<Window>, F>(
+    parent: &W,
+    path: &std::path::Path,
+    has_edits: bool,
+    on_confirm: F,
+) where
+    F: Fn() + 'static,
+{
+    let detail = if has_edits {
+        "Reloading discards your unsaved edits. Keep them to merge when you save."
+    } else {
+        "Reload to see the new version."
+    };
+    let dialog = gtk4::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("File changed on disk")
+        .text("The open launcher was changed by another program")
+        .secondary_text(format!("{}\n\n{}", path.display(), detail))
+        .build();
+    dialog.add_button("Keep Mine", ResponseType::Cancel);
+    dialog.add_button("Reload", ResponseType::Accept);
     dialog.connect_response(move |d, resp| {
         if resp == ResponseType::Accept {
             on_confirm();
@@ -72,6 +144,35 @@ where
     dialog.show();
 }
 
+pub fn ask_autostart_delay<W: IsA<Window>, F>(parent: &W, current: Option<u32>, on_confirm: F)
+where
+    F: Fn(Option<u32>) + 'static,
+{
+    let dialog = gtk4::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("Autostart delay")
+        .text("Seconds to wait after login")
+        .secondary_text("0 starts the item right away.")
+        .build();
+    let spin = gtk4::SpinButton::with_range(0.0, 600.0, 1.0);
+    spin.set_value(current.unwrap_or(0) as f64);
+    spin.set_halign(gtk4::Align::Center);
+    if let Some(area) = dialog.message_area().downcast_ref::<gtk4::Box>() {
+        area.append(&spin);
+    }
+    dialog.add_button("Cancel", ResponseType::Cancel);
+    dialog.add_button("Set Delay", ResponseType::Accept);
+    dialog.connect_response(move |d, resp| {
+        if resp == ResponseType::Accept {
+            let seconds = spin.value() as u32;
+            on_confirm((seconds > 0).then_some(seconds));
+        }
+        d.close();
+    });
+    dialog.show();
+}
+
 pub fn confirm_discard_changes<W: IsA<Window>, F>(parent: &W, on_confirm: F)
 where
     F: Fn() + 'static,
@@ -94,7 +195,12 @@ where
     dialog.show();
 }
 
-pub fn show_save_success<W: IsA<Window>>(parent: &W, path: std::path::PathBuf, is_update: bool) {
+pub fn show_save_success<W: IsA<Window>>(
+    parent: &W,
+    path: std::path::PathBuf,
+    is_update: bool,
+    warning: Option<&str>,
+) {
     let (title, text) = if is_update {
         ("Saved", ".desktop file updated")
     } else {
@@ -106,9 +212,12 @@ pub fn show_save_success<W: IsA<Window>>(parent: &W, path: std::path::PathBuf, i
         .title(title)
         .text(text)
         .secondary_text(format!(
-            "{} {}",
+            "{} {}{}",
             if is_update { "Updated" } else { "Saved to" },
-            path.display()
+            path.display(),
+            warning
+                .map(|w| format!("\n\nNote: {}", w))
+                .unwrap_or_default()
         ))
         .build();
     dialog.add_button("Open Folder", ResponseType::Accept);
@@ -137,3 +246,23 @@ pub fn show_preview<W: IsA<Window>>(parent: &W, content: &str) {
     dialog.connect_response(|d, _| d.close());
     dialog.show();
 }
+
+pub fn show_import_report<W: IsA<Window>>(parent: &W, locale: &str, report: &ImportReport) {
+    let mut lines = vec![format!("Translated: {}", report.translated.len())];
+    if !report.missing.is_empty() {
+        lines.push(format!("Missing: {}", report.missing.join(", ")));
+    }
+    if !report.fuzzy.is_empty() {
+        lines.push(format!("Fuzzy (not applied): {}", report.fuzzy.join(", ")));
+    }
+    let dialog = gtk4::MessageDialog::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("Translations imported")
+        .text(format!("Imported translations for {}", locale))
+        .secondary_text(lines.join("\n"))
+        .build();
+    dialog.add_button("Close", ResponseType::Close);
+    dialog.connect_response(|d, _| d.close());
+    dialog.show();
+}
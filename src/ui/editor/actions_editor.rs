@@ -0,0 +1,241 @@
+use crate::domain::desktop_entry::DesktopAction;
+use crate::domain::locale::Translations;
+use crate::ui::components::labeled_entry_with;
+use crate::ui::editor::entry_form::quote_typed_program;
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Entry, Frame, Label, Orientation};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Structured editor for `[Desktop Action <id>]` groups. Each action is a small
+// frame with its own fields; rows can be added, moved up/down and removed.
+#[derive(Clone)]
+pub struct ActionsEditor {
+    pub container: GtkBox,
+    inner: Rc<Inner>,
+}
+
+// The rows' own handlers hold this weakly: the rows are stored in it, so a
+// strong reference would keep the editor alive forever.
+struct Inner {
+    list: GtkBox,
+    rows: RefCell<Vec<ActionRow>>,
+    on_changed: RefCell<Option<Rc<dyn Fn()>>>,
+}
+
+#[derive(Clone)]
+struct ActionRow {
+    frame: Frame,
+    id_entry: Entry,
+    name_entry: Entry,
+    icon_entry: Entry,
+    exec_entry: Entry,
+    // Translations are not edited here but must survive a round-trip.
+    name_localized: Translations<String>,
+    icon_localized: Translations<String>,
+}
+
+impl ActionsEditor {
+    pub fn new() -> Self {
+        let container = GtkBox::new(Orientation::Vertical, 6);
+        let header = GtkBox::new(Orientation::Horizontal, 8);
+        let title = Label::new(Some("Actions"));
+        title.set_xalign(0.0);
+        title.set_hexpand(true);
+        title.add_css_class("heading");
+        let add_btn = Button::from_icon_name("list-add-symbolic");
+        add_btn.set_tooltip_text(Some("Add action"));
+        header.append(&title);
+        header.append(&add_btn);
+        let list = GtkBox::new(Orientation::Vertical, 6);
+        container.append(&header);
+        container.append(&list);
+
+        let inner = Rc::new(Inner {
+            list,
+            rows: RefCell::new(Vec::new()),
+            on_changed: RefCell::new(None),
+        });
+        {
+            let weak = Rc::downgrade(&inner);
+            add_btn.connect_clicked(move |_| {
+                let Some(inner) = weak.upgrade() else {
+                    return;
+                };
+                let mut actions = inner.actions();
+                actions.push(DesktopAction {
+                    id: next_action_id(&actions),
+                    ..DesktopAction::default()
+                });
+                inner.set_actions(&actions);
+                inner.notify();
+            });
+        }
+        Self { container, inner }
+    }
+
+    pub fn connect_changed(&self, f: impl Fn() + 'static) {
+        *self.inner.on_changed.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn set_actions(&self, actions: &[DesktopAction]) {
+        self.inner.set_actions(actions);
+    }
+
+    pub fn actions(&self) -> Vec<DesktopAction> {
+        self.inner.actions()
+    }
+
+    pub fn set_sensitive(&self, sensitive: bool) {
+        self.container.set_sensitive(sensitive);
+    }
+}
+
+impl Inner {
+    fn set_actions(self: &Rc<Self>, actions: &[DesktopAction]) {
+        for row in self.rows.borrow_mut().drain(..) {
+            self.list.remove(&row.frame);
+        }
+        for (idx, action) in actions.iter().enumerate() {
+            let row = self.build_row(action, idx, actions.len());
+            self.list.append(&row.frame);
+            self.rows.borrow_mut().push(row);
+        }
+    }
+
+    fn actions(&self) -> Vec<DesktopAction> {
+        self.rows
+            .borrow()
+            .iter()
+            .map(|row| DesktopAction {
+                id: row.id_entry.text().trim().to_string(),
+                name: row.name_entry.text().to_string(),
+                icon: opt_text(&row.icon_entry),
+                exec: row.exec_entry.text().trim().to_string(),
+                name_localized: row.name_localized.clone(),
+                icon_localized: row.icon_localized.clone(),
+            })
+            .collect()
+    }
+
+    fn notify(&self) {
+        let cb = self.on_changed.borrow().clone();
+        if let Some(cb) = cb {
+            cb();
+        }
+    }
+
+    fn build_row(self: &Rc<Self>, action: &DesktopAction, idx: usize, count: usize) -> ActionRow {
+        let frame = Frame::new(None);
+        let body = GtkBox::new(Orientation::Vertical, 6);
+        body.set_margin_top(6);
+        body.set_margin_bottom(6);
+        body.set_margin_start(6);
+        body.set_margin_end(6);
+
+        let (id_entry, name_entry, icon_entry, exec_entry) =
+            (Entry::new(), Entry::new(), Entry::new(), Entry::new());
+        id_entry.set_text(&action.id);
+        name_entry.set_text(&action.name);
+        icon_entry.set_text(action.icon.as_deref().unwrap_or(""));
+        exec_entry.set_text(&action.exec);
+        quote_typed_program(&exec_entry);
+        for e in [&id_entry, &name_entry, &icon_entry, &exec_entry] {
+            e.set_hexpand(true);
+            let weak = Rc::downgrade(self);
+            e.connect_changed(move |_| {
+                if let Some(inner) = weak.upgrade() {
+                    inner.notify();
+                }
+            });
+        }
+
+        let buttons = GtkBox::new(Orientation::Horizontal, 4);
+        buttons.set_halign(gtk4::Align::End);
+        let up_btn = Button::from_icon_name("go-up-symbolic");
+        up_btn.set_tooltip_text(Some("Move up"));
+        up_btn.set_sensitive(idx > 0);
+        let down_btn = Button::from_icon_name("go-down-symbolic");
+        down_btn.set_tooltip_text(Some("Move down"));
+        down_btn.set_sensitive(idx + 1 < count);
+        let remove_btn = Button::from_icon_name("user-trash-symbolic");
+        remove_btn.set_tooltip_text(Some("Remove action"));
+        remove_btn.add_css_class("destructive-action");
+        buttons.append(&up_btn);
+        buttons.append(&down_btn);
+        buttons.append(&remove_btn);
+
+        body.append(&labeled_entry_with("Identifier*", &id_entry));
+        body.append(&labeled_entry_with("Name*", &name_entry));
+        body.append(&labeled_entry_with("Icon", &icon_entry));
+        body.append(&labeled_entry_with("Exec", &exec_entry));
+        body.append(&buttons);
+        frame.set_child(Some(&body));
+
+        {
+            let weak = Rc::downgrade(self);
+            up_btn.connect_clicked(move |_| {
+                if let Some(inner) = weak.upgrade() {
+                    inner.move_action(idx, -1);
+                }
+            });
+        }
+        {
+            let weak = Rc::downgrade(self);
+            down_btn.connect_clicked(move |_| {
+                if let Some(inner) = weak.upgrade() {
+                    inner.move_action(idx, 1);
+                }
+            });
+        }
+        {
+            let weak = Rc::downgrade(self);
+            remove_btn.connect_clicked(move |_| {
+                let Some(inner) = weak.upgrade() else {
+                    return;
+                };
+                let mut actions = inner.actions();
+                if idx < actions.len() {
+                    actions.remove(idx);
+                    inner.set_actions(&actions);
+                    inner.notify();
+                }
+            });
+        }
+
+        ActionRow {
+            frame,
+            id_entry,
+            name_entry,
+            icon_entry,
+            exec_entry,
+            name_localized: action.name_localized.clone(),
+            icon_localized: action.icon_localized.clone(),
+        }
+    }
+
+    fn move_action(self: &Rc<Self>, idx: usize, delta: isize) {
+        let mut actions = self.actions();
+        let Some(target) = idx.checked_add_signed(delta) else {
+            return;
+        };
+        if target >= actions.len() {
+            return;
+        }
+        actions.swap(idx, target);
+        self.set_actions(&actions);
+        self.notify();
+    }
+}
+
+fn next_action_id(actions: &[DesktopAction]) -> String {
+    (1..)
+        .map(|n| format!("action-{}", n))
+        .find(|id| actions.iter().all(|a| &a.id != id))
+        .unwrap_or_default()
+}
+
+fn opt_text(e: &Entry) -> Option<String> {
+    let s = e.text().trim().to_string();
+    if s.is_empty() { None } else { Some(s) }
+}
@@ -0,0 +1,242 @@
+use crate::domain::categories::{CATEGORIES, Category, check_categories};
+use adw::{
+    HeaderBar as AdwHeaderBar, ToolbarView as AdwToolbarView, Window as AdwWindow,
+    WindowTitle as AdwWindowTitle, prelude::*,
+};
+use gtk4 as gtk;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const CONTENT_PADDING: i32 = 16;
+const SECTION_SPACING: i32 = 12;
+
+// Searchable checklist of the registered categories. Categories the registry
+// does not know (X- extensions, typos) are kept as they are.
+pub struct CategoryPickerDialog {
+    window: AdwWindow,
+    search_entry: gtk::SearchEntry,
+    lists: [gtk::ListBox; 2],
+    status_label: gtk::Label,
+    use_button: gtk::Button,
+    cancel_button: gtk::Button,
+    selected: Rc<RefCell<Vec<String>>>,
+}
+
+impl CategoryPickerDialog {
+    pub fn new(parent: Option<&impl IsA<gtk::Window>>, selected: &[String]) -> Self {
+        let window = AdwWindow::builder()
+            .title("Categories")
+            .modal(true)
+            .default_width(560)
+            .default_height(640)
+            .resizable(true)
+            .build();
+
+        if let Some(p) = parent {
+            window.set_transient_for(Some(p));
+            if let Some(app) = p.application() {
+                window.set_application(Some(&app));
+            }
+        }
+
+        let header = AdwHeaderBar::new();
+        let header_title = AdwWindowTitle::new("Categories", "Desktop Menu Specification");
+        header.set_title_widget(Some(&header_title));
+
+        let cancel_button = gtk::Button::with_label("Cancel");
+        let use_button = gtk::Button::with_label("Use Categories");
+        use_button.add_css_class("suggested-action");
+
+        let toolbar_view = AdwToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        main_box.set_margin_top(CONTENT_PADDING);
+        main_box.set_margin_bottom(CONTENT_PADDING);
+        main_box.set_margin_start(CONTENT_PADDING);
+        main_box.set_margin_end(CONTENT_PADDING);
+
+        let search_entry = gtk::SearchEntry::new();
+        search_entry.set_placeholder_text(Some("Search categories (audio, editor, game...)"));
+        search_entry.set_hexpand(true);
+        main_box.append(&search_entry);
+
+        let status_label = gtk::Label::new(None);
+        status_label.set_xalign(0.0);
+        status_label.set_wrap(true);
+        status_label.add_css_class("dim-label");
+
+        let sections = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        let lists = [gtk::ListBox::new(), gtk::ListBox::new()];
+        let selected = Rc::new(RefCell::new(selected.to_vec()));
+        for (list, (title, main)) in lists
+            .iter()
+            .zip([("Main Categories", true), ("Additional Categories", false)])
+        {
+            let lbl = gtk::Label::new(Some(title));
+            lbl.set_xalign(0.0);
+            lbl.add_css_class("heading");
+            list.add_css_class("boxed-list");
+            list.set_selection_mode(gtk::SelectionMode::None);
+            for category in CATEGORIES.iter().filter(|c| c.main == main) {
+                list.append(&build_row(category, &selected, &status_label));
+            }
+            sections.append(&lbl);
+            sections.append(list);
+        }
+        let scroll = gtk::ScrolledWindow::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        scroll.set_child(Some(&sections));
+        main_box.append(&scroll);
+
+        main_box.append(&status_label);
+
+        let actions_row = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+        actions_row.set_halign(gtk::Align::Center);
+        actions_row.append(&cancel_button);
+        actions_row.append(&use_button);
+        main_box.append(&actions_row);
+
+        toolbar_view.set_content(Some(&main_box));
+        window.set_content(Some(&toolbar_view));
+
+        let dialog = Self {
+            window,
+            search_entry,
+            lists,
+            status_label,
+            use_button,
+            cancel_button,
+            selected,
+        };
+        dialog.setup_events();
+        dialog.update_status();
+        dialog
+    }
+
+    fn setup_events(&self) {
+        for list in &self.lists {
+            let search = self.search_entry.clone();
+            list.set_filter_func(move |row| {
+                let query = search.text().trim().to_lowercase();
+                query.is_empty() || row.widget_name().to_lowercase().contains(&query)
+            });
+        }
+        {
+            let lists = self.lists.clone();
+            self.search_entry.connect_search_changed(move |_| {
+                for list in &lists {
+                    list.invalidate_filter();
+                }
+            });
+        }
+        for list in &self.lists {
+            list.connect_row_activated(|_, row| {
+                if let Some(check) = row
+                    .child()
+                    .and_then(|body| body.first_child())
+                    .and_downcast::<gtk::CheckButton>()
+                {
+                    check.set_active(!check.is_active());
+                }
+            });
+        }
+    }
+
+    fn update_status(&self) {
+        show_status(&self.status_label, &self.selected.borrow());
+    }
+
+    pub fn run<F: FnOnce(Option<Vec<String>>) + 'static>(self, callback: F) {
+        let callback_cell = Rc::new(RefCell::new(Some(callback)));
+        let finish = {
+            let callback_cell = callback_cell.clone();
+            let window = self.window.clone();
+            Rc::new(move |result: Option<Vec<String>>| {
+                if let Some(cb) = callback_cell.borrow_mut().take() {
+                    cb(result);
+                }
+                window.close();
+            })
+        };
+
+        {
+            let finish = finish.clone();
+            self.cancel_button.connect_clicked(move |_| {
+                finish(None);
+            });
+        }
+
+        {
+            let finish = finish.clone();
+            let selected = self.selected.clone();
+            self.use_button.connect_clicked(move |_| {
+                let result = selected.borrow().clone();
+                finish(Some(result));
+            });
+        }
+
+        self.window.present();
+    }
+}
+
+fn build_row(
+    category: &'static Category,
+    selected: &Rc<RefCell<Vec<String>>>,
+    status: &gtk::Label,
+) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    // The search filter matches against the name and description.
+    row.set_widget_name(&format!("{} {}", category.name, category.description));
+    let body = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    body.set_margin_top(4);
+    body.set_margin_bottom(4);
+    body.set_margin_start(8);
+    body.set_margin_end(8);
+    let check = gtk::CheckButton::new();
+    check.set_active(selected.borrow().iter().any(|c| c == category.name));
+    let text = gtk::Box::new(gtk::Orientation::Vertical, 2);
+    let name = gtk::Label::new(Some(category.name));
+    name.set_xalign(0.0);
+    let description = gtk::Label::new(Some(category.description));
+    description.set_xalign(0.0);
+    description.set_wrap(true);
+    description.add_css_class("dim-label");
+    description.add_css_class("caption");
+    text.append(&name);
+    text.append(&description);
+    body.append(&check);
+    body.append(&text);
+    row.set_child(Some(&body));
+    {
+        let selected = selected.clone();
+        let status = status.clone();
+        check.connect_toggled(move |c| {
+            let mut selected = selected.borrow_mut();
+            selected.retain(|s| s != category.name);
+            if c.is_active() {
+                selected.push(category.name.to_string());
+            }
+            show_status(&status, &selected);
+        });
+    }
+    row
+}
+
+// Reserved categories are not offered, so OnlyShowIn does not matter here.
+fn show_status(label: &gtk::Label, selected: &[String]) {
+    let issues: Vec<String> = check_categories(selected, true)
+        .iter()
+        .map(|i| i.to_string())
+        .collect();
+    if issues.is_empty() {
+        label.set_text(&format!("Selected: {}", selected.join(";")));
+        label.remove_css_class("warning");
+    } else {
+        label.set_text(&issues.join("\n"));
+        label.add_css_class("warning");
+    }
+}
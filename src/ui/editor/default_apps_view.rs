@@ -0,0 +1,262 @@
+use crate::domain::validation::SCHEME_HANDLER_PREFIX;
+use crate::services::mime_apps_service::{Handler, MimeAppsService};
+use crate::services::mime_database::MimeDatabase;
+use crate::services::xdg;
+use adw::{
+    HeaderBar as AdwHeaderBar, ToolbarView as AdwToolbarView, Window as AdwWindow,
+    WindowTitle as AdwWindowTitle, prelude::*,
+};
+use gtk4 as gtk;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const CONTENT_PADDING: i32 = 16;
+const SECTION_SPACING: i32 = 12;
+
+// The MIME types and URI schemes a launcher declares, each with the
+// application that opens it now and a button to make the launcher the
+// default. Changes go to the user's mimeapps.list and the list is reread
+// after each one.
+pub struct DefaultAppsDialog {
+    inner: Rc<Inner>,
+    all_button: gtk::Button,
+    close_button: gtk::Button,
+}
+
+struct Inner {
+    window: AdwWindow,
+    list: gtk::ListBox,
+    status: gtk::Label,
+    id: String,
+    mimes: Vec<String>,
+    database: Rc<MimeDatabase>,
+}
+
+impl DefaultAppsDialog {
+    pub fn new(
+        parent: Option<&impl IsA<gtk::Window>>,
+        name: &str,
+        id: &str,
+        mimes: Vec<String>,
+        database: Rc<MimeDatabase>,
+    ) -> Self {
+        let window = AdwWindow::builder()
+            .title("Default Applications")
+            .modal(true)
+            .default_width(760)
+            .default_height(560)
+            .resizable(true)
+            .build();
+
+        if let Some(p) = parent {
+            window.set_transient_for(Some(p));
+            if let Some(app) = p.application() {
+                window.set_application(Some(&app));
+            }
+        }
+
+        let header = AdwHeaderBar::new();
+        let title = AdwWindowTitle::new("Default Applications", &format!("{} ({})", name, id));
+        header.set_title_widget(Some(&title));
+
+        let toolbar_view = AdwToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        main_box.set_margin_top(CONTENT_PADDING);
+        main_box.set_margin_bottom(CONTENT_PADDING);
+        main_box.set_margin_start(CONTENT_PADDING);
+        main_box.set_margin_end(CONTENT_PADDING);
+
+        let intro = gtk::Label::new(Some(&match MimeAppsService::user_file() {
+            Some(file) => format!("Defaults are saved to {}", file.display()),
+            None => "No config directory to save defaults to".to_string(),
+        }));
+        intro.set_xalign(0.0);
+        intro.set_wrap(true);
+        intro.add_css_class("dim-label");
+        main_box.append(&intro);
+
+        let list = gtk::ListBox::new();
+        list.add_css_class("boxed-list");
+        list.set_selection_mode(gtk::SelectionMode::None);
+        let placeholder = gtk::Label::new(Some(
+            "This launcher declares no MIME types. Add them under MimeType, \
+             with x-scheme-handler/<scheme> for links, and save.",
+        ));
+        placeholder.add_css_class("dim-label");
+        placeholder.set_wrap(true);
+        placeholder.set_margin_top(24);
+        placeholder.set_margin_bottom(24);
+        list.set_placeholder(Some(&placeholder));
+        let scroll = gtk::ScrolledWindow::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        scroll.set_child(Some(&list));
+        main_box.append(&scroll);
+
+        let status = gtk::Label::new(None);
+        status.set_xalign(0.0);
+        status.set_wrap(true);
+        status.add_css_class("dim-label");
+        main_box.append(&status);
+
+        let all_button = gtk::Button::with_label("Make Default for All");
+        all_button.add_css_class("suggested-action");
+        all_button.set_sensitive(!mimes.is_empty());
+        let close_button = gtk::Button::with_label("Close");
+        let actions_row = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+        actions_row.set_halign(gtk::Align::End);
+        actions_row.append(&all_button);
+        actions_row.append(&close_button);
+        main_box.append(&actions_row);
+
+        toolbar_view.set_content(Some(&main_box));
+        window.set_content(Some(&toolbar_view));
+
+        let inner = Rc::new(Inner {
+            window,
+            list,
+            status,
+            id: id.to_string(),
+            mimes,
+            database,
+        });
+        rebuild(&inner);
+
+        Self {
+            inner,
+            all_button,
+            close_button,
+        }
+    }
+
+    pub fn present(self) {
+        {
+            let window = self.inner.window.clone();
+            self.close_button.connect_clicked(move |_| window.close());
+        }
+        {
+            let inner = self.inner.clone();
+            self.all_button.connect_clicked(move |_| {
+                let result = MimeAppsService::set_default(&inner.mimes, &inner.id);
+                finish(&inner, result, "Made the default for every type");
+            });
+        }
+        self.inner.window.present();
+    }
+}
+
+fn rebuild(inner: &Rc<Inner>) {
+    while let Some(child) = inner.list.first_child() {
+        inner.list.remove(&child);
+    }
+    for handler in MimeAppsService::handlers(&inner.mimes, &inner.id) {
+        inner.list.append(&build_row(inner, &handler));
+    }
+}
+
+fn build_row(inner: &Rc<Inner>, handler: &Handler) -> gtk::ListBoxRow {
+    let hb = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+    hb.set_margin_top(6);
+    hb.set_margin_bottom(6);
+    hb.set_margin_start(8);
+    hb.set_margin_end(8);
+
+    let text = gtk::Box::new(gtk::Orientation::Vertical, 2);
+    text.set_hexpand(true);
+    let description = match handler.mime.strip_prefix(SCHEME_HANDLER_PREFIX) {
+        Some(scheme) => format!("{}: links", scheme),
+        None => inner
+            .database
+            .comment(&handler.mime)
+            .unwrap_or(&handler.mime)
+            .to_string(),
+    };
+    let name = gtk::Label::new(Some(&description));
+    name.set_xalign(0.0);
+    name.add_css_class("heading");
+    let mime = gtk::Label::new(Some(&handler.mime));
+    mime.set_xalign(0.0);
+    mime.add_css_class("monospace");
+    mime.add_css_class("dim-label");
+    let is_default = handler.default.as_deref() == Some(inner.id.as_str());
+    let mut current = match (&handler.default, &handler.default_name) {
+        _ if is_default => "This launcher is the default".to_string(),
+        (Some(id), Some(name)) => format!("Opens with {} ({})", name, id),
+        (Some(id), None) => format!("Opens with {}", id),
+        (None, _) => "No default set".to_string(),
+    };
+    if !handler.associated {
+        current.push_str("; this launcher is removed from its associations");
+    }
+    let state = gtk::Label::new(Some(&current));
+    state.set_xalign(0.0);
+    state.set_wrap(true);
+    state.add_css_class("dim-label");
+    text.append(&name);
+    text.append(&mime);
+    text.append(&state);
+    hb.append(&text);
+
+    // Only the user's own files can be changed; a default that comes from a
+    // system file stays until another application is picked.
+    let user_set = handler
+        .source
+        .as_ref()
+        .zip(xdg::config_home())
+        .is_some_and(|(source, config)| source.starts_with(config));
+    let mimes = vec![handler.mime.clone()];
+    if !is_default {
+        let make = gtk::Button::with_label("Make Default");
+        make.set_valign(gtk::Align::Center);
+        let inner = inner.clone();
+        make.connect_clicked(move |_| {
+            let result = MimeAppsService::set_default(&mimes, &inner.id);
+            finish(
+                &inner,
+                result,
+                &format!("Made the default for {}", mimes[0]),
+            );
+        });
+        hb.append(&make);
+    } else if user_set {
+        let unset = gtk::Button::with_label("Unset");
+        unset.set_valign(gtk::Align::Center);
+        let inner = inner.clone();
+        unset.connect_clicked(move |_| {
+            let result = MimeAppsService::unset_default(&mimes, &inner.id);
+            finish(
+                &inner,
+                result,
+                &format!("No longer the default for {}", mimes[0]),
+            );
+        });
+        hb.append(&unset);
+    }
+
+    let row = gtk::ListBoxRow::new();
+    row.set_child(Some(&hb));
+    row.set_activatable(false);
+    if let Some(source) = &handler.source {
+        row.set_tooltip_text(Some(&format!("Default set in {}", source.display())));
+    }
+    row
+}
+
+fn finish(inner: &Rc<Inner>, result: anyhow::Result<Vec<PathBuf>>, done: &str) {
+    let result = result.map_err(|e| e.to_string());
+    rebuild(inner);
+    match result {
+        Ok(written) if written.is_empty() => inner.status.set_text("Nothing to change"),
+        Ok(written) => {
+            let files: Vec<String> = written.iter().map(|p| p.display().to_string()).collect();
+            inner
+                .status
+                .set_text(&format!("{} in {}", done, files.join(", ")));
+        }
+        Err(e) => inner.status.set_text(&format!("Failed: {}", e)),
+    }
+}
@@ -0,0 +1,114 @@
+use crate::domain::validation::{Diagnostic, Severity};
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Image, Label, ListBox, ListBoxRow, Orientation, ScrolledWindow};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type LineHandler = Rc<RefCell<Option<Rc<dyn Fn(usize)>>>>;
+
+// Lists validator findings under the editor. Activating a row that carries a
+// line number reports it through `connect_line_activated`.
+#[derive(Clone)]
+pub struct DiagnosticsPanel {
+    pub container: GtkBox,
+    summary: Label,
+    list: ListBox,
+    on_line: LineHandler,
+}
+
+impl DiagnosticsPanel {
+    pub fn new() -> Self {
+        let container = GtkBox::new(Orientation::Vertical, 4);
+        let summary = Label::new(None);
+        summary.set_xalign(0.0);
+        summary.add_css_class("dim-label");
+        let list = ListBox::new();
+        list.add_css_class("boxed-list");
+        list.set_selection_mode(gtk4::SelectionMode::None);
+        let scroller = ScrolledWindow::builder()
+            .hexpand(true)
+            .min_content_height(60)
+            .max_content_height(140)
+            .propagate_natural_height(true)
+            .build();
+        scroller.set_child(Some(&list));
+        container.append(&summary);
+        container.append(&scroller);
+
+        let panel = Self {
+            container,
+            summary,
+            list,
+            on_line: Rc::new(RefCell::new(None)),
+        };
+        {
+            let on_line = panel.on_line.clone();
+            panel.list.connect_row_activated(move |_, row| {
+                let line = row.widget_name().parse::<usize>().ok();
+                let cb = on_line.borrow().clone();
+                if let (Some(line), Some(cb)) = (line, cb) {
+                    cb(line);
+                }
+            });
+        }
+        panel
+    }
+
+    pub fn connect_line_activated(&self, f: impl Fn(usize) + 'static) {
+        *self.on_line.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn set_diagnostics(&self, diagnostics: &[Diagnostic]) {
+        while let Some(child) = self.list.first_child() {
+            self.list.remove(&child);
+        }
+        let count = |s: Severity| diagnostics.iter().filter(|d| d.severity == s).count();
+        self.summary.set_text(&if diagnostics.is_empty() {
+            "No problems found".to_string()
+        } else {
+            format!(
+                "{} error(s), {} warning(s), {} hint(s)",
+                count(Severity::Error),
+                count(Severity::Warning),
+                count(Severity::Hint)
+            )
+        });
+        self.list.set_visible(!diagnostics.is_empty());
+        for d in diagnostics {
+            self.list.append(&build_row(d));
+        }
+    }
+}
+
+fn build_row(d: &Diagnostic) -> ListBoxRow {
+    let row = ListBoxRow::new();
+    let body = GtkBox::new(Orientation::Horizontal, 8);
+    body.set_margin_top(4);
+    body.set_margin_bottom(4);
+    body.set_margin_start(8);
+    body.set_margin_end(8);
+    let (icon, css) = match d.severity {
+        Severity::Error => ("dialog-error-symbolic", "error"),
+        Severity::Warning => ("dialog-warning-symbolic", "warning"),
+        Severity::Hint => ("dialog-information-symbolic", "accent"),
+    };
+    let image = Image::from_icon_name(icon);
+    image.add_css_class(css);
+    let message = Label::new(Some(&d.message));
+    message.set_xalign(0.0);
+    message.set_hexpand(true);
+    message.set_wrap(true);
+    let location = Label::new(d.line.map(|l| format!("line {}", l)).as_deref());
+    location.add_css_class("dim-label");
+    body.append(&image);
+    body.append(&message);
+    body.append(&location);
+    row.set_child(Some(&body));
+    row.set_tooltip_text(Some(d.code));
+    // The line number rides along in the widget name, like the sidebar's paths.
+    if let Some(line) = d.line {
+        row.set_widget_name(&line.to_string());
+    }
+    row.set_activatable(d.line.is_some());
+    row
+}
@@ -0,0 +1,191 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::entry_diff::{ChangeKind, KeyChange, diff_entries, take_old};
+use adw::{
+    HeaderBar as AdwHeaderBar, ToolbarView as AdwToolbarView, Window as AdwWindow,
+    WindowTitle as AdwWindowTitle, prelude::*,
+};
+use gtk4 as gtk;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const CONTENT_PADDING: i32 = 16;
+const SECTION_SPACING: i32 = 12;
+
+// Side-by-side view of the keys where the override differs from the system
+// launcher it shadows. Checked rows take the system value.
+pub struct DiffDialog {
+    window: AdwWindow,
+    apply_button: gtk::Button,
+    cancel_button: gtk::Button,
+    ours: DesktopEntry,
+    picks: Vec<(KeyChange, gtk::CheckButton)>,
+}
+
+impl DiffDialog {
+    pub fn new(
+        parent: Option<&impl IsA<gtk::Window>>,
+        system: &DesktopEntry,
+        ours: &DesktopEntry,
+        upstream: &[KeyChange],
+    ) -> Self {
+        let window = AdwWindow::builder()
+            .title("Compare with System Version")
+            .modal(true)
+            .default_width(900)
+            .default_height(600)
+            .resizable(true)
+            .build();
+
+        if let Some(p) = parent {
+            window.set_transient_for(Some(p));
+            if let Some(app) = p.application() {
+                window.set_application(Some(&app));
+            }
+        }
+
+        let header = AdwHeaderBar::new();
+        let header_title = AdwWindowTitle::new("Compare with System Version", "Your override");
+        header.set_title_widget(Some(&header_title));
+
+        let cancel_button = gtk::Button::with_label("Cancel");
+        let apply_button = gtk::Button::with_label("Take Selected from System");
+        apply_button.add_css_class("suggested-action");
+
+        let toolbar_view = AdwToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        main_box.set_margin_top(CONTENT_PADDING);
+        main_box.set_margin_bottom(CONTENT_PADDING);
+        main_box.set_margin_start(CONTENT_PADDING);
+        main_box.set_margin_end(CONTENT_PADDING);
+
+        if !upstream.is_empty() {
+            let banner = gtk::Label::new(Some(&format!(
+                "The system launcher changed in {} key(s) since you customized it. Highlighted rows come from that update; applying marks it as reviewed.",
+                upstream.len()
+            )));
+            banner.set_wrap(true);
+            banner.set_xalign(0.0);
+            banner.add_css_class("warning");
+            main_box.append(&banner);
+        }
+
+        let changes = diff_entries(system, ours);
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(6);
+        grid.set_column_spacing(12);
+        for (col, title) in ["", "Key", "System", "Yours"].iter().enumerate() {
+            let lbl = gtk::Label::new(Some(title));
+            lbl.set_xalign(0.0);
+            lbl.add_css_class("heading");
+            grid.attach(&lbl, col as i32, 0, 1, 1);
+        }
+        let mut picks = Vec::new();
+        for (idx, change) in changes.into_iter().enumerate() {
+            let y = idx as i32 + 1;
+            let check = gtk::CheckButton::new();
+            check.set_tooltip_text(Some("Use the system value"));
+            let key = if change.group == "Desktop Entry" {
+                change.key.clone()
+            } else {
+                format!("[{}] {}", change.group, change.key)
+            };
+            let key_lbl = gtk::Label::new(Some(&key));
+            key_lbl.set_xalign(0.0);
+            let system_lbl = value_label(change.old.as_deref());
+            let ours_lbl = value_label(change.new.as_deref());
+            match change.kind {
+                ChangeKind::Added => ours_lbl.add_css_class("success"),
+                ChangeKind::Removed => system_lbl.add_css_class("error"),
+                ChangeKind::Changed => {}
+            }
+            if upstream.iter().any(|u| u.is_same_key(&change)) {
+                system_lbl.add_css_class("warning");
+                system_lbl.set_tooltip_text(Some("Changed by a system update"));
+            }
+            grid.attach(&check, 0, y, 1, 1);
+            grid.attach(&key_lbl, 1, y, 1, 1);
+            grid.attach(&system_lbl, 2, y, 1, 1);
+            grid.attach(&ours_lbl, 3, y, 1, 1);
+            picks.push((change, check));
+        }
+        if picks.is_empty() {
+            let lbl = gtk::Label::new(Some("Your override matches the system launcher."));
+            lbl.add_css_class("dim-label");
+            grid.attach(&lbl, 0, 1, 4, 1);
+            apply_button.set_sensitive(false);
+        }
+        let scroll = gtk::ScrolledWindow::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .build();
+        scroll.set_child(Some(&grid));
+        main_box.append(&scroll);
+
+        let actions_row = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+        actions_row.set_halign(gtk::Align::Center);
+        actions_row.append(&cancel_button);
+        actions_row.append(&apply_button);
+        main_box.append(&actions_row);
+
+        toolbar_view.set_content(Some(&main_box));
+        window.set_content(Some(&toolbar_view));
+
+        Self {
+            window,
+            apply_button,
+            cancel_button,
+            ours: ours.clone(),
+            picks,
+        }
+    }
+
+    pub fn run<F: FnOnce(Option<DesktopEntry>) + 'static>(self, callback: F) {
+        let callback_cell = Rc::new(RefCell::new(Some(callback)));
+        let finish = {
+            let callback_cell = callback_cell.clone();
+            let window = self.window.clone();
+            Rc::new(move |result: Option<DesktopEntry>| {
+                if let Some(cb) = callback_cell.borrow_mut().take() {
+                    cb(result);
+                }
+                window.close();
+            })
+        };
+
+        {
+            let finish = finish.clone();
+            self.cancel_button.connect_clicked(move |_| {
+                finish(None);
+            });
+        }
+
+        {
+            let finish = finish.clone();
+            let ours = self.ours.clone();
+            let picks = self.picks.clone();
+            self.apply_button.connect_clicked(move |_| {
+                let merged = picks
+                    .iter()
+                    .filter(|(_, check)| check.is_active())
+                    .fold(ours.clone(), |entry, (change, _)| take_old(&entry, change));
+                finish(Some(merged));
+            });
+        }
+
+        self.window.present();
+    }
+}
+
+fn value_label(value: Option<&str>) -> gtk::Label {
+    let lbl = gtk::Label::new(Some(value.unwrap_or("(not set)")));
+    lbl.set_xalign(0.0);
+    lbl.set_wrap(true);
+    lbl.set_selectable(true);
+    lbl.set_hexpand(true);
+    if value.is_none() {
+        lbl.add_css_class("dim-label");
+    }
+    lbl
+}
@@ -1,4 +1,14 @@
-use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::categories::suggest_categories;
+use crate::domain::desktop_entry::{DesktopEntry, join_list, split_list};
+use crate::domain::exec_command::{ExecCommand, normalize_command_line};
+use crate::domain::ini_document::IniDocument;
+use crate::domain::validation::{Diagnostic, check_mime_types, validate_document};
+use crate::services::mime_database::MimeDatabase;
+use crate::ui::editor::actions_editor::ActionsEditor;
+use crate::ui::editor::category_picker::CategoryPickerDialog;
+use crate::ui::editor::diagnostics_panel::DiagnosticsPanel;
+use crate::ui::editor::mime_picker::MimePickerDialog;
+use crate::ui::editor::translation_table::TranslationTable;
 pub use crate::ui::editor::widgets::EntryWidgets;
 use gtk4::gdk;
 use gtk4::gio::File;
@@ -14,6 +24,7 @@ pub struct Editor {
     pub notebook: Notebook,
     pub source_view: TextView,
     pub widgets: EntryWidgets,
+    pub diagnostics: DiagnosticsPanel,
 }
 pub fn build_editor() -> Editor {
     let notebook = Notebook::new();
@@ -30,19 +41,20 @@ pub fn build_editor() -> Editor {
     );
     let (categories_entry, mimetype_entry, keywords_entry) =
         (Entry::new(), Entry::new(), Entry::new());
-    let (onlyshowin_entry, notshowin_entry, tryexec_entry, path_entry, actions_entry) = (
-        Entry::new(),
-        Entry::new(),
-        Entry::new(),
-        Entry::new(),
-        Entry::new(),
-    );
-    let (localized_name, localized_gname, localized_comment, extra_kv) = (
-        create_text_view(60),
-        create_text_view(60),
-        create_text_view(60),
-        create_text_view(120),
+    let (onlyshowin_entry, notshowin_entry, tryexec_entry, path_entry) =
+        (Entry::new(), Entry::new(), Entry::new(), Entry::new());
+    let (version_entry, wmclass_entry, implements_entry) =
+        (Entry::new(), Entry::new(), Entry::new());
+    let (hidden_check, dbus_check, gpu_check, single_window_check) = (
+        CheckButton::with_label("Hidden (treat as deleted)"),
+        CheckButton::with_label("DBusActivatable"),
+        CheckButton::with_label("PrefersNonDefaultGPU"),
+        CheckButton::with_label("SingleMainWindow"),
     );
+    let actions_editor = ActionsEditor::new();
+    let mime_db = Rc::new(MimeDatabase::load());
+    let translations = TranslationTable::new();
+    let extra_kv = create_text_view(120);
     for e in [
         &name_entry,
         &generic_name_entry,
@@ -57,10 +69,15 @@ pub fn build_editor() -> Editor {
         &notshowin_entry,
         &tryexec_entry,
         &path_entry,
-        &actions_entry,
+        &version_entry,
+        &wmclass_entry,
+        &implements_entry,
     ] {
         e.set_hexpand(true);
     }
+    version_entry.set_placeholder_text(Some("1.5"));
+    // Electron and Java apps often need this to group their windows in docks.
+    wmclass_entry.set_placeholder_text(Some("Window class, e.g. from xprop WM_CLASS"));
     exec_entry.set_icon_from_icon_name(
         EntryIconPosition::Primary,
         Some("application-x-executable-symbolic"),
@@ -70,6 +87,8 @@ pub fn build_editor() -> Editor {
         build_exec_row(&exec_entry, &url_entry, &type_combo);
     let exec_row = build_dynamic_exec_row(&exec_lbl, &exec_app_box, &exec_link_box);
     setup_icon_preview(&icon_entry);
+    setup_exec_feedback(&exec_entry);
+    quote_typed_program(&exec_entry);
     setup_path_url_buttons(&path_entry, &url_entry);
     let icon_row = build_icon_row(&icon_entry);
     basic_box.append(&build_type_row(&type_combo));
@@ -90,14 +109,8 @@ pub fn build_editor() -> Editor {
     ));
     advanced_box.append(&build_check_row(&nodisplay_check));
     advanced_box.append(&build_check_row(&startup_check));
-    advanced_box.append(&crate::ui::components::labeled_entry_with(
-        "Categories (;)",
-        &categories_entry,
-    ));
-    advanced_box.append(&crate::ui::components::labeled_entry_with(
-        "MimeType (;)",
-        &mimetype_entry,
-    ));
+    advanced_box.append(&build_categories_row(&categories_entry, &mimetype_entry));
+    advanced_box.append(&build_mimetype_row(&mimetype_entry, &exec_entry, &mime_db));
     advanced_box.append(&crate::ui::components::labeled_entry_with(
         "Keywords (;)",
         &keywords_entry,
@@ -118,15 +131,24 @@ pub fn build_editor() -> Editor {
         "Working Dir",
         &path_entry,
     ));
-    advanced_box.append(&build_localized_section(
-        &localized_name,
-        &localized_gname,
-        &localized_comment,
+    advanced_box.append(&crate::ui::components::labeled_entry_with(
+        "StartupWMClass",
+        &wmclass_entry,
+    ));
+    advanced_box.append(&crate::ui::components::labeled_entry_with(
+        "Implements (;)",
+        &implements_entry,
     ));
     advanced_box.append(&crate::ui::components::labeled_entry_with(
-        "Actions (;)",
-        &actions_entry,
+        "Spec Version",
+        &version_entry,
     ));
+    advanced_box.append(&build_check_row(&dbus_check));
+    advanced_box.append(&build_check_row(&gpu_check));
+    advanced_box.append(&build_check_row(&single_window_check));
+    advanced_box.append(&build_check_row(&hidden_check));
+    advanced_box.append(&translations.container);
+    advanced_box.append(&actions_editor.container);
     advanced_box.append(&Label::new(Some("Extra key=value lines")));
     advanced_box.append(&wrap_scrolled(&extra_kv));
     let basic_scroll = wrap_scroll_vexpand(&basic_box);
@@ -154,16 +176,23 @@ pub fn build_editor() -> Editor {
         tryexec_entry,
         path_entry,
         url_entry,
-        actions_entry,
-        localized_name,
-        localized_gname,
-        localized_comment,
+        version_entry,
+        wmclass_entry,
+        implements_entry,
+        hidden_check,
+        dbus_check,
+        gpu_check,
+        single_window_check,
+        actions_editor,
+        translations,
         extra_kv,
         exec_lbl,
         exec_app_box,
         exec_link_box,
         exec_btn,
         url_btn,
+        document: Rc::new(RefCell::new(None)),
+        mime_db,
     };
     apply_type_rules(&widgets);
     {
@@ -172,10 +201,28 @@ pub fn build_editor() -> Editor {
             .type_combo
             .connect_changed(move |_| apply_type_rules(&w));
     }
+    let diagnostics = DiagnosticsPanel::new();
+    {
+        let nb = notebook.clone();
+        let sv = source_view.clone();
+        diagnostics.connect_line_activated(move |line| {
+            nb.set_current_page(Some(2));
+            reveal_source_line(&sv, line);
+        });
+    }
     Editor {
         notebook,
         source_view,
         widgets,
+        diagnostics,
+    }
+}
+fn reveal_source_line(tv: &TextView, line: usize) {
+    let buf = tv.buffer();
+    if let Some(mut iter) = buf.iter_at_line(line.saturating_sub(1) as i32) {
+        buf.place_cursor(&iter);
+        tv.scroll_to_iter(&mut iter, 0.1, false, 0.0, 0.0);
+        tv.grab_focus();
     }
 }
 fn create_tab_box() -> GtkBox {
@@ -282,6 +329,69 @@ fn build_dynamic_exec_row(lbl: &Label, app_box: &GtkBox, link_box: &GtkBox) -> G
 }
 use crate::ui::editor::icon_picker::IconPickerDialog;
 
+// Categories entry with the registry picker, plus a suggestion while empty:
+// menus file uncategorized applications under "Other".
+fn build_categories_row(categories_entry: &Entry, mimetype_entry: &Entry) -> GtkBox {
+    let column = GtkBox::new(Orientation::Vertical, 4);
+    let row = crate::ui::components::labeled_entry_with("Categories (;)", categories_entry);
+    let btn_choose = Button::with_label("Choose...");
+    {
+        let e = categories_entry.clone();
+        btn_choose.connect_clicked(move |btn| {
+            let picker = CategoryPickerDialog::new(
+                btn.root().and_downcast_ref::<gtk4::Window>(),
+                &split_semicolon(&e),
+            );
+            let e_clone = e.clone();
+            picker.run(move |selected| {
+                if let Some(categories) = selected {
+                    set_list_text(&e_clone, &categories);
+                }
+            });
+        });
+    }
+    row.append(&btn_choose);
+
+    let hint_row = GtkBox::new(Orientation::Horizontal, 8);
+    hint_row.set_halign(gtk4::Align::End);
+    let hint = Label::new(None);
+    hint.add_css_class("dim-label");
+    let btn_use = Button::with_label("Use Suggestion");
+    btn_use.add_css_class("flat");
+    hint_row.append(&hint);
+    hint_row.append(&btn_use);
+    column.append(&row);
+    column.append(&hint_row);
+
+    let refresh: Rc<dyn Fn()> = {
+        let (ce, me, hint_row) = (
+            categories_entry.clone(),
+            mimetype_entry.clone(),
+            hint_row.clone(),
+        );
+        Rc::new(move || {
+            let suggestion = suggest_categories(&split_semicolon(&me)).join(";");
+            hint.set_text(&format!(
+                "No categories, menus will show this under Other. Suggested: {}",
+                suggestion
+            ));
+            hint_row.set_visible(split_semicolon(&ce).is_empty());
+        })
+    };
+    refresh();
+    for e in [categories_entry, mimetype_entry] {
+        let r = refresh.clone();
+        e.connect_changed(move |_| r());
+    }
+    {
+        let (ce, me) = (categories_entry.clone(), mimetype_entry.clone());
+        btn_use.connect_clicked(move |_| {
+            ce.set_text(&suggest_categories(&split_semicolon(&me)).join(";"));
+        });
+    }
+    column
+}
+
 fn build_icon_row(icon_entry: &Entry) -> GtkBox {
     let row = GtkBox::new(Orientation::Horizontal, 8);
     let lbl = Label::new(Some("Icon"));
@@ -317,6 +427,31 @@ fn build_icon_row(icon_entry: &Entry) -> GtkBox {
 
     row
 }
+fn build_mimetype_row(mimetype_entry: &Entry, exec_entry: &Entry, db: &Rc<MimeDatabase>) -> GtkBox {
+    let row = crate::ui::components::labeled_entry_with("MimeType (;)", mimetype_entry);
+    let btn_choose = Button::with_label("Choose...");
+    {
+        let (me, ee, db) = (mimetype_entry.clone(), exec_entry.clone(), db.clone());
+        btn_choose.connect_clicked(move |btn| {
+            let takes_files = ExecCommand::parse(&ee.text())
+                .is_ok_and(|c| c.field_codes().any(|code| code.takes_files()));
+            let picker = MimePickerDialog::new(
+                btn.root().and_downcast_ref::<gtk4::Window>(),
+                db.clone(),
+                &split_semicolon(&me),
+                takes_files,
+            );
+            let me_clone = me.clone();
+            picker.run(move |selected| {
+                if let Some(types) = selected {
+                    set_list_text(&me_clone, &types);
+                }
+            });
+        });
+    }
+    row.append(&btn_choose);
+    row
+}
 fn show_file_chooser(entry: &Entry, title: &str, action: FileChooserAction, prefix_file: bool) {
     let parent_window = entry.root().and_downcast::<gtk4::Window>();
     let dialog = FileChooserDialog::new(
@@ -376,7 +511,9 @@ fn show_exec_file_chooser(entry: &Entry) {
             && let Some(path) = file.path()
         {
             ensure_executable_if_appimage(&path);
-            e.set_text(&path.to_string_lossy());
+            // Quote the path when needed so spaces survive in Exec.
+            let program = path.to_string_lossy();
+            e.set_text(&ExecCommand::from_argv(&[program]).to_exec_string());
         }
         d.close();
     });
@@ -411,6 +548,49 @@ fn setup_icon_preview(entry: &Entry) {
         refresh_icon_preview(e);
     });
 }
+// Live feedback on the command line: parse errors and spec issues are shown
+// on the entry, otherwise the resolved program is shown as a tooltip.
+fn setup_exec_feedback(entry: &Entry) {
+    entry.connect_changed(|e| {
+        let text = e.text().to_string();
+        e.remove_css_class("error");
+        e.remove_css_class("warning");
+        if text.trim().is_empty() {
+            e.set_tooltip_text(None);
+            return;
+        }
+        match ExecCommand::check(&text) {
+            Err(err) => {
+                e.add_css_class("error");
+                e.set_tooltip_text(Some(&format!("Invalid command line: {}", err)));
+            }
+            Ok(issues) if !issues.is_empty() => {
+                e.add_css_class("warning");
+                let lines: Vec<String> = issues.iter().map(|i| format!("• {}", i)).collect();
+                e.set_tooltip_text(Some(&lines.join("\n")));
+            }
+            Ok(_) => {
+                let program = ExecCommand::parse(&text).ok().and_then(|c| c.program());
+                e.set_tooltip_text(program.map(|p| format!("Runs: {}", p)).as_deref());
+            }
+        }
+    });
+}
+// When the field is left, a typed program path containing spaces is quoted
+// if that file exists, e.g. `/opt/My App/run --x` becomes
+// `"/opt/My App/run" --x`. Saving does not look at the disk itself.
+pub fn quote_typed_program(entry: &Entry) {
+    let focus = gtk4::EventControllerFocus::new();
+    let e = entry.clone();
+    focus.connect_leave(move |_| {
+        let text = e.text();
+        let quoted = normalize_command_line(&text, |p| std::path::Path::new(p).is_file());
+        if quoted != text.trim() {
+            e.set_text(&quoted);
+        }
+    });
+    entry.add_controller(focus);
+}
 fn setup_path_url_buttons(path_entry: &Entry, url_entry: &Entry) {
     path_entry.set_icon_from_icon_name(EntryIconPosition::Secondary, Some("folder-open-symbolic"));
     path_entry.set_icon_activatable(EntryIconPosition::Secondary, true);
@@ -433,17 +613,6 @@ fn setup_path_url_buttons(path_entry: &Entry, url_entry: &Entry) {
         }
     });
 }
-fn build_localized_section(name: &TextView, gname: &TextView, comment: &TextView) -> GtkBox {
-    let b = GtkBox::new(Orientation::Vertical, 4);
-    b.append(&Label::new(Some("Localized fields (lang=value per line)")));
-    b.append(&Label::new(Some("Name[lang]")));
-    b.append(&wrap_scrolled(name));
-    b.append(&Label::new(Some("GenericName[lang]")));
-    b.append(&wrap_scrolled(gname));
-    b.append(&Label::new(Some("Comment[lang]")));
-    b.append(&wrap_scrolled(comment));
-    b
-}
 fn wrap_scrolled(widget: &impl IsA<gtk4::Widget>) -> ScrolledWindow {
     let sw = ScrolledWindow::builder()
         .hexpand(true)
@@ -485,7 +654,11 @@ pub fn apply_type_rules(w: &EntryWidgets) {
     w.terminal_check.set_sensitive(is_app);
     w.path_entry.set_sensitive(is_app);
     w.startup_check.set_sensitive(is_app);
-    w.actions_entry.set_sensitive(is_app);
+    w.wmclass_entry.set_sensitive(is_app);
+    w.dbus_check.set_sensitive(is_app);
+    w.gpu_check.set_sensitive(is_app);
+    w.single_window_check.set_sensitive(is_app);
+    w.actions_editor.set_sensitive(is_app);
     w.url_entry.set_sensitive(!is_app);
     if !is_app {
         w.exec_entry.set_text("");
@@ -493,10 +666,15 @@ pub fn apply_type_rules(w: &EntryWidgets) {
         w.path_entry.set_text("");
         w.terminal_check.set_active(false);
         w.startup_check.set_active(false);
-        w.actions_entry.set_text("");
+        w.wmclass_entry.set_text("");
+        w.dbus_check.set_active(false);
+        w.gpu_check.set_active(false);
+        w.single_window_check.set_active(false);
+        w.actions_editor.set_actions(&[]);
     }
 }
 pub fn set_form_from_entry(w: &EntryWidgets, de: &DesktopEntry) {
+    *w.document.borrow_mut() = de.document.clone();
     let idx = match de.type_field.as_str() {
         "Application" => 0,
         "Link" => 1,
@@ -515,19 +693,26 @@ pub fn set_form_from_entry(w: &EntryWidgets, de: &DesktopEntry) {
     w.terminal_check.set_active(de.terminal);
     w.nodisplay_check.set_active(de.no_display);
     w.startup_check.set_active(de.startup_notify);
-    w.categories_entry.set_text(&de.categories.join(";"));
-    w.mimetype_entry.set_text(&de.mime_type.join(";"));
-    w.keywords_entry.set_text(&de.keywords.join(";"));
-    w.onlyshowin_entry.set_text(&de.only_show_in.join(";"));
-    w.notshowin_entry.set_text(&de.not_show_in.join(";"));
+    set_list_text(&w.categories_entry, &de.categories);
+    set_list_text(&w.mimetype_entry, &de.mime_type);
+    set_list_text(&w.keywords_entry, &de.keywords);
+    set_list_text(&w.onlyshowin_entry, &de.only_show_in);
+    set_list_text(&w.notshowin_entry, &de.not_show_in);
     w.tryexec_entry
         .set_text(de.try_exec.as_deref().unwrap_or(""));
     w.path_entry.set_text(de.path.as_deref().unwrap_or(""));
     w.url_entry.set_text(de.url.as_deref().unwrap_or(""));
-    w.actions_entry.set_text(&de.actions.join(";"));
-    set_localized_text(&w.localized_name, &de.name_localized);
-    set_localized_text(&w.localized_gname, &de.generic_name_localized);
-    set_localized_text(&w.localized_comment, &de.comment_localized);
+    w.version_entry
+        .set_text(de.version.as_deref().unwrap_or(""));
+    w.wmclass_entry
+        .set_text(de.startup_wm_class.as_deref().unwrap_or(""));
+    set_list_text(&w.implements_entry, &de.implements);
+    w.hidden_check.set_active(de.hidden);
+    w.dbus_check.set_active(de.dbus_activatable);
+    w.gpu_check.set_active(de.prefers_non_default_gpu);
+    w.single_window_check.set_active(de.single_main_window);
+    w.actions_editor.set_actions(&de.actions);
+    w.translations.set_entry(de);
     set_extra_text(&w.extra_kv, &de.extra);
     apply_type_rules(w);
 }
@@ -542,15 +727,15 @@ fn refresh_icon_preview(e: &Entry) {
     } else if txt.contains('/') {
         // Try to load as image file (SVG, PNG, etc.)
         let path = &txt;
-        if path.ends_with(".svg") || path.ends_with(".png") || path.ends_with(".jpg")
-            || path.ends_with(".jpeg") || path.ends_with(".gif") {
-            match gdk::Texture::from_file(&File::for_path(path)) {
-                Ok(tex) => {
-                    e.set_icon_from_paintable(EntryIconPosition::Primary, Some(&tex));
-                    return;
-                }
-                Err(_) => {}
-            }
+        if (path.ends_with(".svg")
+            || path.ends_with(".png")
+            || path.ends_with(".jpg")
+            || path.ends_with(".jpeg")
+            || path.ends_with(".gif"))
+            && let Ok(tex) = gdk::Texture::from_file(&File::for_path(path))
+        {
+            e.set_icon_from_paintable(EntryIconPosition::Primary, Some(&tex));
+            return;
         }
         // Fall back to icon theme if file loading fails
         e.set_icon_from_icon_name(EntryIconPosition::Primary, Some("image-missing"));
@@ -559,16 +744,18 @@ fn refresh_icon_preview(e: &Entry) {
         e.set_icon_from_icon_name(EntryIconPosition::Primary, Some(&txt));
     }
 }
-fn set_localized_text(tv: &TextView, data: &[(String, String)]) {
-    let lines: Vec<String> = data.iter().map(|(l, v)| format!("{}={}", l, v)).collect();
-    tv.buffer().set_text(&lines.join("\n"));
-}
 fn set_extra_text(tv: &TextView, data: &[(String, String)]) {
     let lines: Vec<String> = data.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
     tv.buffer().set_text(&lines.join("\n"));
 }
 pub fn collect_entry(w: &EntryWidgets) -> Result<DesktopEntry, String> {
-    let de = DesktopEntry {
+    let de = read_form(w);
+    de.validate()?;
+    Ok(de)
+}
+// The form as an entry, without validation; used for the live source view.
+pub fn read_form(w: &EntryWidgets) -> DesktopEntry {
+    let mut de = DesktopEntry {
         type_field: w
             .type_combo
             .active_text()
@@ -590,21 +777,28 @@ pub fn collect_entry(w: &EntryWidgets) -> Result<DesktopEntry, String> {
         try_exec: opt_text(&w.tryexec_entry),
         path: opt_text(&w.path_entry),
         url: opt_text(&w.url_entry),
-        actions: split_semicolon(&w.actions_entry),
-        name_localized: parse_lang_lines(&buffer_text(&w.localized_name)),
-        generic_name_localized: parse_lang_lines(&buffer_text(&w.localized_gname)),
-        comment_localized: parse_lang_lines(&buffer_text(&w.localized_comment)),
+        version: opt_text(&w.version_entry),
+        hidden: w.hidden_check.is_active(),
+        dbus_activatable: w.dbus_check.is_active(),
+        startup_wm_class: opt_text(&w.wmclass_entry),
+        prefers_non_default_gpu: w.gpu_check.is_active(),
+        single_main_window: w.single_window_check.is_active(),
+        implements: split_semicolon(&w.implements_entry),
+        actions: w.actions_editor.actions(),
         extra: parse_kv_lines(&buffer_text(&w.extra_kv)),
+        document: w.document.borrow().clone(),
+        ..DesktopEntry::default()
     };
-    de.validate()?;
-    Ok(de)
+    w.translations.apply_to(&mut de);
+    de
 }
 fn split_semicolon(e: &Entry) -> Vec<String> {
-    e.text()
-        .split(';')
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect()
+    split_list(&e.text())
+}
+// List fields show the on-disk syntax (`\;` escapes) without the trailing `;`.
+fn set_list_text(e: &Entry, items: &[String]) {
+    let joined = join_list(items);
+    e.set_text(joined.strip_suffix(';').unwrap_or(&joined));
 }
 fn opt_text(e: &Entry) -> Option<String> {
     let s = e.text().trim().to_string();
@@ -615,24 +809,6 @@ fn buffer_text(tv: &TextView) -> String {
     buf.text(&buf.start_iter(), &buf.end_iter(), true)
         .to_string()
 }
-fn parse_lang_lines(s: &str) -> Vec<(String, String)> {
-    s.lines()
-        .filter_map(|line| {
-            let line = line.trim();
-            if line.is_empty() {
-                return None;
-            }
-            line.split_once('=').and_then(|(l, v)| {
-                let (l, v) = (l.trim().to_string(), v.trim().to_string());
-                if l.is_empty() || v.is_empty() {
-                    None
-                } else {
-                    Some((l, v))
-                }
-            })
-        })
-        .collect()
-}
 fn parse_kv_lines(s: &str) -> Vec<(String, String)> {
     s.lines()
         .filter_map(|line| {
@@ -655,6 +831,15 @@ pub fn parse_desktop_source(content: &str) -> DesktopEntry {
     DesktopEntry::from_ini_string(content)
 }
 
+// Spec checks plus the MIME types known to the installed database.
+fn diagnose(text: &str, db: &MimeDatabase) -> Vec<Diagnostic> {
+    let doc = IniDocument::parse(text);
+    let mut diagnostics = validate_document(&doc);
+    diagnostics.extend(check_mime_types(&doc, |name| db.lookup(name)));
+    diagnostics.sort_by_key(|d| (d.line.unwrap_or(usize::MAX), d.severity));
+    diagnostics
+}
+
 pub fn wire_source_sync(editor: &Editor, on_user_change: impl Fn() + 'static) {
     let widgets = &editor.widgets;
     let source_view = editor.source_view.clone();
@@ -665,16 +850,20 @@ pub fn wire_source_sync(editor: &Editor, on_user_change: impl Fn() + 'static) {
         let sv = source_view.clone();
         let g = guard.clone();
         let c = change_cb.clone();
+        let dp = editor.diagnostics.clone();
+        let db = editor.widgets.mime_db.clone();
         move || {
             if *g.borrow() {
                 return;
             }
             c();
-            if let Ok(de) = collect_entry(&w) {
-                *g.borrow_mut() = true;
-                sv.buffer().set_text(&de.to_ini_string());
-                *g.borrow_mut() = false;
-            }
+            let de = read_form(&w);
+            w.translations.highlight_missing(&de);
+            let text = de.to_ini_string();
+            dp.set_diagnostics(&diagnose(&text, &db));
+            *g.borrow_mut() = true;
+            sv.buffer().set_text(&text);
+            *g.borrow_mut() = false;
         }
     };
     let connect_entry = |e: &Entry, cb: &Rc<dyn Fn()>| {
@@ -708,29 +897,38 @@ pub fn wire_source_sync(editor: &Editor, on_user_change: impl Fn() + 'static) {
         &widgets.tryexec_entry,
         &widgets.path_entry,
         &widgets.url_entry,
-        &widgets.actions_entry,
+        &widgets.version_entry,
+        &widgets.wmclass_entry,
+        &widgets.implements_entry,
     ] {
         connect_entry(e, &cb);
     }
+    {
+        let c = cb.clone();
+        widgets.actions_editor.connect_changed(move || c());
+    }
     for c in [
         &widgets.terminal_check,
         &widgets.nodisplay_check,
         &widgets.startup_check,
+        &widgets.hidden_check,
+        &widgets.dbus_check,
+        &widgets.gpu_check,
+        &widgets.single_window_check,
     ] {
         connect_check(c, &cb);
     }
-    for tv in [
-        &widgets.localized_name,
-        &widgets.localized_gname,
-        &widgets.localized_comment,
-        &widgets.extra_kv,
-    ] {
-        connect_tv(tv, &cb);
+    {
+        let c = cb.clone();
+        widgets.translations.connect_changed(move || c());
     }
+    connect_tv(&widgets.extra_kv, &cb);
     {
         let w = widgets.clone_all();
         let g = guard.clone();
         let c = change_cb.clone();
+        let dp = editor.diagnostics.clone();
+        let db = editor.widgets.mime_db.clone();
         source_view.buffer().connect_changed(move |buf| {
             if *g.borrow() {
                 return;
@@ -739,6 +937,8 @@ pub fn wire_source_sync(editor: &Editor, on_user_change: impl Fn() + 'static) {
             let text = buf
                 .text(&buf.start_iter(), &buf.end_iter(), true)
                 .to_string();
+            // Line numbers refer to the text as typed, not as re-serialized.
+            dp.set_diagnostics(&diagnose(&text, &db));
             let de = parse_desktop_source(&text);
             *g.borrow_mut() = true;
             set_form_from_entry(&w, &de);
@@ -0,0 +1,417 @@
+use crate::domain::health::{HealthIssue, HealthKey};
+use crate::services::health_service::{HealthReport, HealthService, Removed};
+use crate::ui::editor::icon_picker::IconPickerDialog;
+use adw::{
+    HeaderBar as AdwHeaderBar, ToolbarView as AdwToolbarView, Window as AdwWindow,
+    WindowTitle as AdwWindowTitle, prelude::*,
+};
+use gtk4 as gtk;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const CONTENT_PADDING: i32 = 16;
+const SECTION_SPACING: i32 = 12;
+
+// Launchers whose program, icon or working directory is gone, one row per
+// problem. Each row has its own fixes; checked rows can be fixed together.
+// The list is rescanned after every fix.
+pub struct HealthDialog {
+    inner: Rc<Inner>,
+    close_button: gtk::Button,
+    relink_button: gtk::Button,
+    icon_button: gtk::Button,
+    remove_button: gtk::Button,
+    select_all: gtk::CheckButton,
+}
+
+struct Inner {
+    window: AdwWindow,
+    title: AdwWindowTitle,
+    list: gtk::ListBox,
+    status: gtk::Label,
+    scan: Box<dyn Fn() -> Vec<HealthReport>>,
+    rows: RefCell<Vec<(PathBuf, HealthIssue, gtk::CheckButton)>>,
+    // Every file written or removed, for the main window to catch up with.
+    touched: RefCell<Vec<PathBuf>>,
+}
+
+impl HealthDialog {
+    pub fn new(
+        parent: Option<&impl IsA<gtk::Window>>,
+        scan: impl Fn() -> Vec<HealthReport> + 'static,
+    ) -> Self {
+        let window = AdwWindow::builder()
+            .title("Launcher Health")
+            .modal(true)
+            .default_width(900)
+            .default_height(620)
+            .resizable(true)
+            .build();
+
+        if let Some(p) = parent {
+            window.set_transient_for(Some(p));
+            if let Some(app) = p.application() {
+                window.set_application(Some(&app));
+            }
+        }
+
+        let header = AdwHeaderBar::new();
+        let title = AdwWindowTitle::new("Launcher Health", "");
+        header.set_title_widget(Some(&title));
+
+        let toolbar_view = AdwToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        main_box.set_margin_top(CONTENT_PADDING);
+        main_box.set_margin_bottom(CONTENT_PADDING);
+        main_box.set_margin_start(CONTENT_PADDING);
+        main_box.set_margin_end(CONTENT_PADDING);
+
+        let list = gtk::ListBox::new();
+        list.add_css_class("boxed-list");
+        list.set_selection_mode(gtk::SelectionMode::None);
+        let placeholder = gtk::Label::new(Some("Every launcher points at things that exist."));
+        placeholder.add_css_class("dim-label");
+        placeholder.set_margin_top(24);
+        placeholder.set_margin_bottom(24);
+        list.set_placeholder(Some(&placeholder));
+        let scroll = gtk::ScrolledWindow::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        scroll.set_child(Some(&list));
+        main_box.append(&scroll);
+
+        let status = gtk::Label::new(None);
+        status.set_xalign(0.0);
+        status.set_wrap(true);
+        status.add_css_class("dim-label");
+        main_box.append(&status);
+
+        let select_all = gtk::CheckButton::with_label("Select all");
+        let relink_button = gtk::Button::with_label("Relink Selected...");
+        let icon_button = gtk::Button::with_label("Set Icon for Selected...");
+        let remove_button = gtk::Button::with_label("Remove Selected");
+        remove_button.add_css_class("destructive-action");
+        let close_button = gtk::Button::with_label("Close");
+        let actions_row = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+        actions_row.append(&select_all);
+        let spacer = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        spacer.set_hexpand(true);
+        actions_row.append(&spacer);
+        actions_row.append(&relink_button);
+        actions_row.append(&icon_button);
+        actions_row.append(&remove_button);
+        actions_row.append(&close_button);
+        main_box.append(&actions_row);
+
+        toolbar_view.set_content(Some(&main_box));
+        window.set_content(Some(&toolbar_view));
+
+        let inner = Rc::new(Inner {
+            window,
+            title,
+            list,
+            status,
+            scan: Box::new(scan),
+            rows: RefCell::new(Vec::new()),
+            touched: RefCell::new(Vec::new()),
+        });
+        rebuild(&inner);
+
+        Self {
+            inner,
+            close_button,
+            relink_button,
+            icon_button,
+            remove_button,
+            select_all,
+        }
+    }
+
+    /// Shows the report; `callback` gets every file that was changed or
+    /// removed once the window closes.
+    pub fn run<F: FnOnce(Vec<PathBuf>) + 'static>(self, callback: F) {
+        let callback_cell = Rc::new(RefCell::new(Some(callback)));
+        {
+            let inner = self.inner.clone();
+            self.inner.window.connect_close_request(move |_| {
+                if let Some(cb) = callback_cell.borrow_mut().take() {
+                    cb(inner.touched.take());
+                }
+                gtk4::glib::Propagation::Proceed
+            });
+        }
+        {
+            let window = self.inner.window.clone();
+            self.close_button.connect_clicked(move |_| window.close());
+        }
+        {
+            let inner = self.inner.clone();
+            self.select_all.connect_toggled(move |check| {
+                for (_, _, row_check) in inner.rows.borrow().iter() {
+                    row_check.set_active(check.is_active());
+                }
+            });
+        }
+        {
+            let inner = self.inner.clone();
+            self.relink_button.connect_clicked(move |_| {
+                let items = selected(&inner, |key| {
+                    matches!(key, HealthKey::Exec | HealthKey::TryExec)
+                });
+                if items.is_empty() {
+                    inner
+                        .status
+                        .set_text("Select Exec or TryExec problems to relink them together");
+                    return;
+                }
+                let inner2 = inner.clone();
+                choose_replacement(&inner, HealthKey::Exec, move |replacement| {
+                    relink_items(&inner2, &items, &replacement);
+                });
+            });
+        }
+        {
+            let inner = self.inner.clone();
+            self.icon_button.connect_clicked(move |_| {
+                let items = selected(&inner, |key| key == HealthKey::Icon);
+                if items.is_empty() {
+                    inner
+                        .status
+                        .set_text("Select Icon problems to give them a new icon");
+                    return;
+                }
+                let inner2 = inner.clone();
+                choose_replacement(&inner, HealthKey::Icon, move |replacement| {
+                    relink_items(&inner2, &items, &replacement);
+                });
+            });
+        }
+        {
+            let inner = self.inner.clone();
+            self.remove_button.connect_clicked(move |_| {
+                let mut paths: Vec<PathBuf> = selected(&inner, |_| true)
+                    .into_iter()
+                    .map(|(path, _)| path)
+                    .collect();
+                paths.dedup();
+                if paths.is_empty() {
+                    inner.status.set_text("Select the launchers to remove");
+                    return;
+                }
+                remove_items(&inner, &paths);
+            });
+        }
+        self.inner.window.present();
+    }
+}
+
+fn rebuild(inner: &Rc<Inner>) {
+    while let Some(child) = inner.list.first_child() {
+        inner.list.remove(&child);
+    }
+    inner.rows.borrow_mut().clear();
+    let reports = (inner.scan)();
+    let problems: usize = reports.iter().map(|r| r.issues.len()).sum();
+    inner.title.set_subtitle(&match problems {
+        0 => "No problems found".to_string(),
+        _ => format!("{} problems in {} launchers", problems, reports.len()),
+    });
+    for report in &reports {
+        for issue in &report.issues {
+            let check = gtk::CheckButton::new();
+            inner.list.append(&build_row(inner, report, issue, &check));
+            inner
+                .rows
+                .borrow_mut()
+                .push((report.path.clone(), issue.clone(), check));
+        }
+    }
+}
+
+fn build_row(
+    inner: &Rc<Inner>,
+    report: &HealthReport,
+    issue: &HealthIssue,
+    check: &gtk::CheckButton,
+) -> gtk::ListBoxRow {
+    let hb = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+    hb.set_margin_top(6);
+    hb.set_margin_bottom(6);
+    hb.set_margin_start(8);
+    hb.set_margin_end(8);
+    hb.append(check);
+
+    let text = gtk::Box::new(gtk::Orientation::Vertical, 2);
+    text.set_hexpand(true);
+    let name = gtk::Label::new(Some(&report.name));
+    name.set_xalign(0.0);
+    name.add_css_class("heading");
+    let what = gtk::Label::new(Some(&issue.to_string()));
+    what.set_xalign(0.0);
+    what.set_wrap(true);
+    what.add_css_class("dim-label");
+    text.append(&name);
+    text.append(&what);
+    hb.append(&text);
+
+    let fix_label = match issue.key {
+        HealthKey::Icon => "Choose Icon...",
+        _ => "Relink...",
+    };
+    let fix = gtk::Button::with_label(fix_label);
+    fix.set_valign(gtk::Align::Center);
+    {
+        let inner = inner.clone();
+        let items = vec![(report.path.clone(), issue.clone())];
+        let key = issue.key;
+        fix.connect_clicked(move |_| {
+            let (inner2, items) = (inner.clone(), items.clone());
+            choose_replacement(&inner, key, move |replacement| {
+                relink_items(&inner2, &items, &replacement);
+            });
+        });
+    }
+    hb.append(&fix);
+
+    let remove = gtk::Button::with_label("Remove");
+    remove.set_valign(gtk::Align::Center);
+    remove.add_css_class("destructive-action");
+    {
+        let inner = inner.clone();
+        let path = report.path.clone();
+        remove.connect_clicked(move |_| remove_items(&inner, std::slice::from_ref(&path)));
+    }
+    hb.append(&remove);
+
+    let row = gtk::ListBoxRow::new();
+    row.set_child(Some(&hb));
+    row.set_activatable(false);
+    row.set_tooltip_text(Some(&report.path.to_string_lossy()));
+    row
+}
+
+fn selected(inner: &Inner, wanted: impl Fn(HealthKey) -> bool) -> Vec<(PathBuf, HealthIssue)> {
+    inner
+        .rows
+        .borrow()
+        .iter()
+        .filter(|(_, issue, check)| check.is_active() && wanted(issue.key))
+        .map(|(path, issue, _)| (path.clone(), issue.clone()))
+        .collect()
+}
+
+// Asks for the new program, folder or icon, depending on the key.
+fn choose_replacement(inner: &Rc<Inner>, key: HealthKey, then: impl FnOnce(String) + 'static) {
+    if key == HealthKey::Icon {
+        IconPickerDialog::new(Some(&inner.window)).run(move |icon| {
+            if let Some(icon) = icon {
+                then(icon);
+            }
+        });
+        return;
+    }
+    let (title, action) = match key {
+        HealthKey::Path => (
+            "Select Working Directory",
+            gtk::FileChooserAction::SelectFolder,
+        ),
+        _ => ("Select Program", gtk::FileChooserAction::Open),
+    };
+    let dialog = gtk::FileChooserDialog::new(
+        Some(title),
+        Some(&inner.window),
+        action,
+        &[
+            ("Cancel", gtk::ResponseType::Cancel),
+            ("Select", gtk::ResponseType::Accept),
+        ],
+    );
+    let then = RefCell::new(Some(then));
+    dialog.connect_response(move |d, resp| {
+        if resp == gtk::ResponseType::Accept
+            && let Some(path) = d.file().and_then(|f| f.path())
+            && let Some(then) = then.borrow_mut().take()
+        {
+            then(path.to_string_lossy().to_string());
+        }
+        d.close();
+    });
+    dialog.show();
+}
+
+fn relink_items(inner: &Rc<Inner>, items: &[(PathBuf, HealthIssue)], replacement: &str) {
+    let mut fixed = 0;
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    for (path, issue) in items {
+        match HealthService::relink(path, issue, replacement) {
+            Ok(written) => {
+                fixed += 1;
+                warnings.extend(written.warning);
+                inner.touched.borrow_mut().push(written.path);
+            }
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    finish(
+        inner,
+        &with_warnings(format!("Fixed {}", fixed), &warnings),
+        &errors,
+    );
+}
+
+fn remove_items(inner: &Rc<Inner>, paths: &[PathBuf]) {
+    let mut removed = 0;
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    for path in paths {
+        match HealthService::remove(path) {
+            Ok(how) => {
+                removed += 1;
+                if let Removed::Hidden(written) = how {
+                    warnings.extend(written.warning);
+                }
+                inner.touched.borrow_mut().push(path.clone());
+            }
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    finish(
+        inner,
+        &with_warnings(
+            format!(
+                "Removed {} (user launchers went to the trash, system ones were hidden)",
+                removed
+            ),
+            &warnings,
+        ),
+        &errors,
+    );
+}
+
+// `done` followed by the first warning of the writes, if any had one.
+fn with_warnings(done: String, warnings: &[String]) -> String {
+    match warnings {
+        [] => done,
+        [only] => format!("{}; {}", done, only),
+        [first, rest @ ..] => format!("{}; {} (and {} more)", done, first, rest.len()),
+    }
+}
+
+fn finish(inner: &Rc<Inner>, done: &str, errors: &[String]) {
+    rebuild(inner);
+    match errors {
+        [] => inner.status.set_text(done),
+        [first, ..] => inner.status.set_text(&format!(
+            "{}; {} failed, first: {}",
+            done,
+            errors.len(),
+            first
+        )),
+    }
+}
@@ -0,0 +1,296 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::entry_diff::{ChangeKind, diff_entries};
+use crate::services::history_store::{HistoryStore, Retention, Snapshot};
+use adw::{
+    HeaderBar as AdwHeaderBar, ToolbarView as AdwToolbarView, Window as AdwWindow,
+    WindowTitle as AdwWindowTitle, prelude::*,
+};
+use gtk4 as gtk;
+use gtk4::glib;
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::UNIX_EPOCH;
+
+const CONTENT_PADDING: i32 = 16;
+const SECTION_SPACING: i32 = 12;
+
+// Saved versions of one launcher. Selecting a version shows how it differs
+// from the file on disk; Restore puts it back.
+pub struct HistoryDialog {
+    window: AdwWindow,
+    list: gtk::ListBox,
+    restore_button: gtk::Button,
+    cancel_button: gtk::Button,
+    snapshots: Vec<Snapshot>,
+}
+
+impl HistoryDialog {
+    pub fn new(parent: Option<&impl IsA<gtk::Window>>, path: &Path) -> Self {
+        let window = AdwWindow::builder()
+            .title("Version History")
+            .modal(true)
+            .default_width(960)
+            .default_height(620)
+            .resizable(true)
+            .build();
+
+        if let Some(p) = parent {
+            window.set_transient_for(Some(p));
+            if let Some(app) = p.application() {
+                window.set_application(Some(&app));
+            }
+        }
+
+        let header = AdwHeaderBar::new();
+        let header_title = AdwWindowTitle::new("Version History", &path.to_string_lossy());
+        header.set_title_widget(Some(&header_title));
+
+        let cancel_button = gtk::Button::with_label("Close");
+        let restore_button = gtk::Button::with_label("Restore This Version");
+        restore_button.add_css_class("suggested-action");
+        restore_button.set_sensitive(false);
+
+        let toolbar_view = AdwToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        main_box.set_margin_top(CONTENT_PADDING);
+        main_box.set_margin_bottom(CONTENT_PADDING);
+        main_box.set_margin_start(CONTENT_PADDING);
+        main_box.set_margin_end(CONTENT_PADDING);
+
+        let snapshots = HistoryStore::list(path);
+        let list = gtk::ListBox::new();
+        list.add_css_class("boxed-list");
+        for snapshot in &snapshots {
+            let lbl = gtk::Label::new(Some(&format_time(snapshot)));
+            lbl.set_xalign(0.0);
+            lbl.set_margin_top(6);
+            lbl.set_margin_bottom(6);
+            lbl.set_margin_start(8);
+            lbl.set_margin_end(8);
+            list.append(&lbl);
+        }
+        if snapshots.is_empty() {
+            let lbl = gtk::Label::new(Some("No earlier versions yet"));
+            lbl.add_css_class("dim-label");
+            list.set_placeholder(Some(&lbl));
+        }
+        let list_scroll = gtk::ScrolledWindow::builder()
+            .vexpand(true)
+            .min_content_width(220)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        list_scroll.set_child(Some(&list));
+
+        let diff_box = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        let diff_scroll = gtk::ScrolledWindow::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .build();
+        diff_scroll.set_child(Some(&diff_box));
+        show_hint(
+            &diff_box,
+            "Select a version to compare it with the current file.",
+        );
+
+        let panes = gtk::Paned::new(gtk::Orientation::Horizontal);
+        panes.set_start_child(Some(&list_scroll));
+        panes.set_end_child(Some(&diff_scroll));
+        panes.set_shrink_start_child(false);
+        main_box.append(&panes);
+
+        main_box.append(&build_retention_row());
+
+        let actions_row = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+        actions_row.set_halign(gtk::Align::Center);
+        actions_row.append(&cancel_button);
+        actions_row.append(&restore_button);
+        main_box.append(&actions_row);
+
+        toolbar_view.set_content(Some(&main_box));
+        window.set_content(Some(&toolbar_view));
+
+        {
+            let current = fs::read_to_string(path)
+                .map(|text| DesktopEntry::from_ini_string(&text))
+                .ok();
+            let (snapshots, restore) = (snapshots.clone(), restore_button.clone());
+            list.connect_row_selected(move |_, row| {
+                let snapshot = row.and_then(|r| snapshots.get(r.index() as usize));
+                restore.set_sensitive(snapshot.is_some());
+                if let Some(snapshot) = snapshot {
+                    show_diff(&diff_box, snapshot, current.as_ref());
+                }
+            });
+        }
+
+        Self {
+            window,
+            list,
+            restore_button,
+            cancel_button,
+            snapshots,
+        }
+    }
+
+    pub fn run<F: FnOnce(Option<Snapshot>) + 'static>(self, callback: F) {
+        let callback_cell = Rc::new(RefCell::new(Some(callback)));
+        let finish = {
+            let callback_cell = callback_cell.clone();
+            let window = self.window.clone();
+            Rc::new(move |result: Option<Snapshot>| {
+                if let Some(cb) = callback_cell.borrow_mut().take() {
+                    cb(result);
+                }
+                window.close();
+            })
+        };
+
+        {
+            let finish = finish.clone();
+            self.cancel_button.connect_clicked(move |_| {
+                finish(None);
+            });
+        }
+
+        {
+            let finish = finish.clone();
+            let list = self.list.clone();
+            let snapshots = self.snapshots.clone();
+            self.restore_button.connect_clicked(move |_| {
+                let selected = list
+                    .selected_row()
+                    .and_then(|r| snapshots.get(r.index() as usize).cloned());
+                finish(selected);
+            });
+        }
+
+        self.window.present();
+    }
+}
+
+fn format_time(snapshot: &Snapshot) -> String {
+    let secs = snapshot
+        .taken
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    glib::DateTime::from_unix_local(secs)
+        .and_then(|t| t.format("%Y-%m-%d %H:%M:%S"))
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| secs.to_string())
+}
+
+fn show_hint(container: &gtk::Box, text: &str) {
+    while let Some(child) = container.first_child() {
+        container.remove(&child);
+    }
+    let lbl = gtk::Label::new(Some(text));
+    lbl.set_wrap(true);
+    lbl.add_css_class("dim-label");
+    container.append(&lbl);
+}
+
+fn show_diff(container: &gtk::Box, snapshot: &Snapshot, current: Option<&DesktopEntry>) {
+    let old = match fs::read_to_string(&snapshot.path) {
+        Ok(text) => DesktopEntry::from_ini_string(&text),
+        Err(e) => return show_hint(container, &format!("Cannot read this version: {}", e)),
+    };
+    let Some(current) = current else {
+        return show_hint(
+            container,
+            "The launcher no longer exists; restoring recreates it.",
+        );
+    };
+    let changes = diff_entries(&old, current);
+    if changes.is_empty() {
+        return show_hint(container, "This version is identical to the current file.");
+    }
+    while let Some(child) = container.first_child() {
+        container.remove(&child);
+    }
+    let grid = gtk::Grid::new();
+    grid.set_row_spacing(6);
+    grid.set_column_spacing(12);
+    for (col, title) in ["Key", "This Version", "Current"].iter().enumerate() {
+        let lbl = gtk::Label::new(Some(title));
+        lbl.set_xalign(0.0);
+        lbl.add_css_class("heading");
+        grid.attach(&lbl, col as i32, 0, 1, 1);
+    }
+    for (idx, change) in changes.iter().enumerate() {
+        let y = idx as i32 + 1;
+        let key = if change.group == "Desktop Entry" {
+            change.key.clone()
+        } else {
+            format!("[{}] {}", change.group, change.key)
+        };
+        let key_lbl = gtk::Label::new(Some(&key));
+        key_lbl.set_xalign(0.0);
+        let old_lbl = value_label(change.old.as_deref());
+        let new_lbl = value_label(change.new.as_deref());
+        match change.kind {
+            ChangeKind::Added => new_lbl.add_css_class("success"),
+            ChangeKind::Removed => old_lbl.add_css_class("error"),
+            ChangeKind::Changed => {}
+        }
+        grid.attach(&key_lbl, 0, y, 1, 1);
+        grid.attach(&old_lbl, 1, y, 1, 1);
+        grid.attach(&new_lbl, 2, y, 1, 1);
+    }
+    container.append(&grid);
+}
+
+fn value_label(value: Option<&str>) -> gtk::Label {
+    let lbl = gtk::Label::new(Some(value.unwrap_or("(not set)")));
+    lbl.set_xalign(0.0);
+    lbl.set_wrap(true);
+    lbl.set_selectable(true);
+    lbl.set_hexpand(true);
+    if value.is_none() {
+        lbl.add_css_class("dim-label");
+    }
+    lbl
+}
+
+// Retention applies to every launcher and is saved as soon as it changes.
+fn build_retention_row() -> gtk::Box {
+    let retention = Retention::load();
+    let row = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    let keep = gtk::SpinButton::with_range(0.0, 1000.0, 1.0);
+    keep.set_value(retention.max_snapshots as f64);
+    keep.set_tooltip_text(Some("0 keeps every version"));
+    let days = gtk::SpinButton::with_range(0.0, 3650.0, 1.0);
+    days.set_value(retention.max_age_days as f64);
+    days.set_tooltip_text(Some("0 never expires versions"));
+    let status = gtk::Label::new(None);
+    status.add_css_class("dim-label");
+    row.append(&gtk::Label::new(Some("Keep at most")));
+    row.append(&keep);
+    row.append(&gtk::Label::new(Some("versions, for up to")));
+    row.append(&days);
+    row.append(&gtk::Label::new(Some("days")));
+    row.append(&status);
+    let save = {
+        let (keep, days, status) = (keep.clone(), days.clone(), status.clone());
+        move || {
+            let retention = Retention {
+                max_snapshots: keep.value() as usize,
+                max_age_days: days.value() as u64,
+            };
+            match retention.save() {
+                Ok(()) => status.set_text("Applies from the next save"),
+                Err(e) => status.set_text(&format!("Not saved: {}", e)),
+            }
+        }
+    };
+    {
+        let save = save.clone();
+        keep.connect_value_changed(move |_| save());
+    }
+    days.connect_value_changed(move |_| save());
+    row
+}
@@ -0,0 +1,189 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::entry_diff::{Merge, MergeConflict};
+use adw::{
+    HeaderBar as AdwHeaderBar, ToolbarView as AdwToolbarView, Window as AdwWindow,
+    WindowTitle as AdwWindowTitle, prelude::*,
+};
+use gtk4 as gtk;
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+const CONTENT_PADDING: i32 = 16;
+const SECTION_SPACING: i32 = 12;
+
+// Three-way merge of the form with a file that changed on disk. Changes only
+// made on disk are already applied; each conflict picks a side.
+pub struct MergeDialog {
+    window: AdwWindow,
+    save_button: gtk::Button,
+    cancel_button: gtk::Button,
+    merged: DesktopEntry,
+    choices: Vec<(MergeConflict, gtk::CheckButton)>,
+}
+
+impl MergeDialog {
+    pub fn new(parent: Option<&impl IsA<gtk::Window>>, path: &Path, merge: Merge) -> Self {
+        let window = AdwWindow::builder()
+            .title("Merge Changes")
+            .modal(true)
+            .default_width(900)
+            .default_height(600)
+            .resizable(true)
+            .build();
+
+        if let Some(p) = parent {
+            window.set_transient_for(Some(p));
+            if let Some(app) = p.application() {
+                window.set_application(Some(&app));
+            }
+        }
+
+        let header = AdwHeaderBar::new();
+        let header_title = AdwWindowTitle::new("Merge Changes", &path.to_string_lossy());
+        header.set_title_widget(Some(&header_title));
+
+        let cancel_button = gtk::Button::with_label("Cancel");
+        let save_button = gtk::Button::with_label("Save Merged");
+        save_button.add_css_class("suggested-action");
+
+        let toolbar_view = AdwToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        main_box.set_margin_top(CONTENT_PADDING);
+        main_box.set_margin_bottom(CONTENT_PADDING);
+        main_box.set_margin_start(CONTENT_PADDING);
+        main_box.set_margin_end(CONTENT_PADDING);
+
+        let summary = gtk::Label::new(Some(&format!(
+            "{} change(s) from disk merged automatically, {} conflict(s) to resolve.",
+            merge.applied.len(),
+            merge.conflicts.len()
+        )));
+        summary.set_xalign(0.0);
+        summary.set_wrap(true);
+        main_box.append(&summary);
+
+        if !merge.applied.is_empty() {
+            let keys: Vec<String> = merge
+                .applied
+                .iter()
+                .map(|c| key_label(&c.group, &c.key))
+                .collect();
+            let applied = gtk::Label::new(Some(&format!("Taken from disk: {}", keys.join(", "))));
+            applied.set_xalign(0.0);
+            applied.set_wrap(true);
+            applied.add_css_class("dim-label");
+            main_box.append(&applied);
+        }
+
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(6);
+        grid.set_column_spacing(12);
+        for (col, title) in ["Key", "Original", "Yours", "On Disk"].iter().enumerate() {
+            let lbl = gtk::Label::new(Some(title));
+            lbl.set_xalign(0.0);
+            lbl.add_css_class("heading");
+            grid.attach(&lbl, col as i32, 0, 1, 1);
+        }
+        let mut choices = Vec::new();
+        for (idx, conflict) in merge.conflicts.into_iter().enumerate() {
+            let y = idx as i32 + 1;
+            let key_lbl = gtk::Label::new(Some(&key_label(&conflict.group, &conflict.key)));
+            key_lbl.set_xalign(0.0);
+            let base_lbl = value_label(conflict.base.as_deref());
+            base_lbl.add_css_class("dim-label");
+            let ours =
+                gtk::CheckButton::with_label(conflict.ours.as_deref().unwrap_or("(not set)"));
+            ours.set_active(true);
+            let theirs =
+                gtk::CheckButton::with_label(conflict.theirs.as_deref().unwrap_or("(not set)"));
+            theirs.set_group(Some(&ours));
+            grid.attach(&key_lbl, 0, y, 1, 1);
+            grid.attach(&base_lbl, 1, y, 1, 1);
+            grid.attach(&ours, 2, y, 1, 1);
+            grid.attach(&theirs, 3, y, 1, 1);
+            choices.push((conflict, theirs));
+        }
+        if !choices.is_empty() {
+            let scroll = gtk::ScrolledWindow::builder()
+                .hexpand(true)
+                .vexpand(true)
+                .build();
+            scroll.set_child(Some(&grid));
+            main_box.append(&scroll);
+        }
+
+        let actions_row = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+        actions_row.set_halign(gtk::Align::Center);
+        actions_row.append(&cancel_button);
+        actions_row.append(&save_button);
+        main_box.append(&actions_row);
+
+        toolbar_view.set_content(Some(&main_box));
+        window.set_content(Some(&toolbar_view));
+
+        Self {
+            window,
+            save_button,
+            cancel_button,
+            merged: merge.entry,
+            choices,
+        }
+    }
+
+    pub fn run<F: FnOnce(Option<DesktopEntry>) + 'static>(self, callback: F) {
+        let callback_cell = Rc::new(RefCell::new(Some(callback)));
+        let finish = {
+            let callback_cell = callback_cell.clone();
+            let window = self.window.clone();
+            Rc::new(move |result: Option<DesktopEntry>| {
+                if let Some(cb) = callback_cell.borrow_mut().take() {
+                    cb(result);
+                }
+                window.close();
+            })
+        };
+
+        {
+            let finish = finish.clone();
+            self.cancel_button.connect_clicked(move |_| {
+                finish(None);
+            });
+        }
+
+        {
+            let finish = finish.clone();
+            let merged = self.merged.clone();
+            let choices = self.choices.clone();
+            self.save_button.connect_clicked(move |_| {
+                let resolved = choices
+                    .iter()
+                    .filter(|(_, theirs)| theirs.is_active())
+                    .fold(merged.clone(), |entry, (conflict, _)| {
+                        conflict.take_theirs(&entry)
+                    });
+                finish(Some(resolved));
+            });
+        }
+
+        self.window.present();
+    }
+}
+
+fn key_label(group: &str, key: &str) -> String {
+    if group == "Desktop Entry" {
+        key.to_string()
+    } else {
+        format!("[{}] {}", group, key)
+    }
+}
+
+fn value_label(value: Option<&str>) -> gtk::Label {
+    let lbl = gtk::Label::new(Some(value.unwrap_or("(not set)")));
+    lbl.set_xalign(0.0);
+    lbl.set_wrap(true);
+    lbl.set_selectable(true);
+    lbl
+}
@@ -0,0 +1,290 @@
+use crate::domain::validation::MimeLookup;
+use crate::services::mime_database::{MimeDatabase, MimeType};
+use adw::{
+    HeaderBar as AdwHeaderBar, ToolbarView as AdwToolbarView, Window as AdwWindow,
+    WindowTitle as AdwWindowTitle, prelude::*,
+};
+use gtk4 as gtk;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const CONTENT_PADDING: i32 = 16;
+const SECTION_SPACING: i32 = 12;
+const SEARCH_LIMIT: usize = 200;
+
+type Selection = Rc<RefCell<Vec<String>>>;
+
+// Searchable list of the installed MIME types. Types that are not in the
+// database stay selected so nothing is dropped silently.
+pub struct MimePickerDialog {
+    window: AdwWindow,
+    search_entry: gtk::SearchEntry,
+    list: gtk::ListBox,
+    status_label: gtk::Label,
+    use_button: gtk::Button,
+    cancel_button: gtk::Button,
+    database: Rc<MimeDatabase>,
+    selected: Selection,
+}
+
+impl MimePickerDialog {
+    pub fn new(
+        parent: Option<&impl IsA<gtk::Window>>,
+        database: Rc<MimeDatabase>,
+        selected: &[String],
+        exec_takes_files: bool,
+    ) -> Self {
+        let window = AdwWindow::builder()
+            .title("MIME Types")
+            .modal(true)
+            .default_width(640)
+            .default_height(680)
+            .resizable(true)
+            .build();
+
+        if let Some(p) = parent {
+            window.set_transient_for(Some(p));
+            if let Some(app) = p.application() {
+                window.set_application(Some(&app));
+            }
+        }
+
+        let header = AdwHeaderBar::new();
+        let header_title = AdwWindowTitle::new("MIME Types", "shared-mime-info database");
+        header.set_title_widget(Some(&header_title));
+
+        let cancel_button = gtk::Button::with_label("Cancel");
+        let use_button = gtk::Button::with_label("Use MIME Types");
+        use_button.add_css_class("suggested-action");
+
+        let toolbar_view = AdwToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        main_box.set_margin_top(CONTENT_PADDING);
+        main_box.set_margin_bottom(CONTENT_PADDING);
+        main_box.set_margin_start(CONTENT_PADDING);
+        main_box.set_margin_end(CONTENT_PADDING);
+
+        if !exec_takes_files {
+            let warning = gtk::Label::new(Some(
+                "Exec has no %f, %F, %u or %U: the application will be started without the files it is asked to open.",
+            ));
+            warning.set_wrap(true);
+            warning.set_xalign(0.0);
+            warning.add_css_class("warning");
+            main_box.append(&warning);
+        }
+
+        let search_entry = gtk::SearchEntry::new();
+        search_entry.set_placeholder_text(Some(
+            "Search types, descriptions or patterns (png, *.md...)",
+        ));
+        search_entry.set_hexpand(true);
+        main_box.append(&search_entry);
+
+        let list = gtk::ListBox::new();
+        list.add_css_class("boxed-list");
+        list.set_selection_mode(gtk::SelectionMode::None);
+        let scroll = gtk::ScrolledWindow::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        scroll.set_child(Some(&list));
+        main_box.append(&scroll);
+
+        let status_label = gtk::Label::new(None);
+        status_label.set_xalign(0.0);
+        status_label.set_wrap(true);
+        status_label.add_css_class("dim-label");
+        main_box.append(&status_label);
+
+        let actions_row = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+        actions_row.set_halign(gtk::Align::Center);
+        actions_row.append(&cancel_button);
+        actions_row.append(&use_button);
+        main_box.append(&actions_row);
+
+        toolbar_view.set_content(Some(&main_box));
+        window.set_content(Some(&toolbar_view));
+
+        let dialog = Self {
+            window,
+            search_entry,
+            list,
+            status_label,
+            use_button,
+            cancel_button,
+            database,
+            selected: Rc::new(RefCell::new(selected.to_vec())),
+        };
+        dialog.setup_events();
+        dialog.refresh_results();
+        show_status(
+            &dialog.status_label,
+            &dialog.database,
+            &dialog.selected.borrow(),
+        );
+        dialog
+    }
+
+    fn setup_events(&self) {
+        {
+            let (list, status, database, selected) = (
+                self.list.clone(),
+                self.status_label.clone(),
+                self.database.clone(),
+                self.selected.clone(),
+            );
+            self.search_entry.connect_search_changed(move |entry| {
+                fill_list(&list, &status, &database, &selected, &entry.text());
+            });
+        }
+        self.list.connect_row_activated(|_, row| {
+            if let Some(check) = row
+                .child()
+                .and_then(|body| body.first_child())
+                .and_downcast::<gtk::CheckButton>()
+            {
+                check.set_active(!check.is_active());
+            }
+        });
+    }
+
+    fn refresh_results(&self) {
+        fill_list(
+            &self.list,
+            &self.status_label,
+            &self.database,
+            &self.selected,
+            &self.search_entry.text(),
+        );
+    }
+
+    pub fn run<F: FnOnce(Option<Vec<String>>) + 'static>(self, callback: F) {
+        let callback_cell = Rc::new(RefCell::new(Some(callback)));
+        let finish = {
+            let callback_cell = callback_cell.clone();
+            let window = self.window.clone();
+            Rc::new(move |result: Option<Vec<String>>| {
+                if let Some(cb) = callback_cell.borrow_mut().take() {
+                    cb(result);
+                }
+                window.close();
+            })
+        };
+
+        {
+            let finish = finish.clone();
+            self.cancel_button.connect_clicked(move |_| {
+                finish(None);
+            });
+        }
+
+        {
+            let finish = finish.clone();
+            let selected = self.selected.clone();
+            self.use_button.connect_clicked(move |_| {
+                let result = selected.borrow().clone();
+                finish(Some(result));
+            });
+        }
+
+        self.window.present();
+    }
+}
+
+fn fill_list(
+    list: &gtk::ListBox,
+    status: &gtk::Label,
+    database: &Rc<MimeDatabase>,
+    selected: &Selection,
+    query: &str,
+) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+    for mime in database.search(query, SEARCH_LIMIT) {
+        list.append(&build_row(mime, database, selected, status));
+    }
+}
+
+fn build_row(
+    mime: &MimeType,
+    database: &Rc<MimeDatabase>,
+    selected: &Selection,
+    status: &gtk::Label,
+) -> gtk::ListBoxRow {
+    let row = gtk::ListBoxRow::new();
+    let body = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    body.set_margin_top(4);
+    body.set_margin_bottom(4);
+    body.set_margin_start(8);
+    body.set_margin_end(8);
+    let check = gtk::CheckButton::new();
+    check.set_active(selected.borrow().contains(&mime.name));
+    let text = gtk::Box::new(gtk::Orientation::Vertical, 2);
+    let name = gtk::Label::new(Some(&mime.name));
+    name.set_xalign(0.0);
+    let mut details: Vec<String> = mime.comment.iter().cloned().collect();
+    if !mime.globs.is_empty() {
+        details.push(mime.globs.join(" "));
+    }
+    if !mime.parents.is_empty() {
+        details.push(format!("subclass of {}", mime.parents.join(", ")));
+    }
+    let description = gtk::Label::new(Some(&details.join(" · ")));
+    description.set_xalign(0.0);
+    description.set_wrap(true);
+    description.add_css_class("dim-label");
+    description.add_css_class("caption");
+    text.append(&name);
+    text.append(&description);
+    body.append(&check);
+    body.append(&text);
+    row.set_child(Some(&body));
+    {
+        let (name, database, selected, status) = (
+            mime.name.clone(),
+            database.clone(),
+            selected.clone(),
+            status.clone(),
+        );
+        check.connect_toggled(move |c| {
+            let mut selected = selected.borrow_mut();
+            selected.retain(|s| *s != name);
+            if c.is_active() {
+                selected.push(name.clone());
+            }
+            show_status(&status, &database, &selected);
+        });
+    }
+    row
+}
+
+fn show_status(label: &gtk::Label, database: &MimeDatabase, selected: &[String]) {
+    let issues: Vec<String> = selected
+        .iter()
+        .filter_map(|name| match database.lookup(name) {
+            MimeLookup::Known => None,
+            MimeLookup::Alias(canonical) => Some(format!("{} is an alias of {}", name, canonical)),
+            MimeLookup::Unknown => Some(format!("{} is not in the MIME database", name)),
+        })
+        .collect();
+    let mut text = if selected.is_empty() {
+        "No MIME types selected".to_string()
+    } else {
+        format!("Selected: {}", selected.join(";"))
+    };
+    for issue in &issues {
+        text.push('\n');
+        text.push_str(issue);
+    }
+    label.set_text(&text);
+    if issues.is_empty() {
+        label.remove_css_class("warning");
+    } else {
+        label.add_css_class("warning");
+    }
+}
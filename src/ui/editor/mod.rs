@@ -1,3 +1,14 @@
+pub mod actions_editor;
+pub mod category_picker;
+pub mod default_apps_view;
+pub mod diagnostics_panel;
+pub mod diff_view;
 pub mod entry_form;
+pub mod health_view;
+pub mod history_view;
 pub mod icon_picker;
+pub mod merge_view;
+pub mod mime_picker;
+pub mod run_dialog;
+pub mod translation_table;
 pub mod widgets;
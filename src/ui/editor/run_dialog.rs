@@ -0,0 +1,379 @@
+use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::locale::Locale;
+use crate::services::launch_service::{LaunchEvent, LaunchPlan, LaunchService};
+use adw::{
+    HeaderBar as AdwHeaderBar, ToolbarView as AdwToolbarView, Window as AdwWindow,
+    WindowTitle as AdwWindowTitle, prelude::*,
+};
+use gtk4 as gtk;
+use gtk4::gio;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const CONTENT_PADDING: i32 = 16;
+const SECTION_SPACING: i32 = 12;
+
+type PlanFn = Rc<dyn Fn() -> anyhow::Result<LaunchPlan>>;
+
+// Starts the launcher as it is in the form, optionally with sample files for
+// its field codes, and logs what each process prints and how it ended. The
+// dry run above the log shows what Run would start for the chosen files and
+// locale.
+pub struct RunDialog {
+    window: AdwWindow,
+    run_button: gtk::Button,
+    close_button: gtk::Button,
+    log: gtk::TextBuffer,
+    plan: PlanFn,
+}
+
+impl RunDialog {
+    pub fn new(
+        parent: Option<&impl IsA<gtk::Window>>,
+        entry: DesktopEntry,
+        desktop_file: Option<PathBuf>,
+    ) -> Self {
+        let window = AdwWindow::builder()
+            .title("Run Launcher")
+            .modal(true)
+            .default_width(760)
+            .default_height(680)
+            .resizable(true)
+            .build();
+
+        if let Some(p) = parent {
+            window.set_transient_for(Some(p));
+            if let Some(app) = p.application() {
+                window.set_application(Some(&app));
+            }
+        }
+
+        let header = AdwHeaderBar::new();
+        let header_title = AdwWindowTitle::new("Run Launcher", &entry.exec);
+        header.set_title_widget(Some(&header_title));
+
+        let close_button = gtk::Button::with_label("Close");
+        let run_button = gtk::Button::with_label("Run");
+        run_button.add_css_class("suggested-action");
+
+        let toolbar_view = AdwToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+
+        let main_box = gtk::Box::new(gtk::Orientation::Vertical, SECTION_SPACING);
+        main_box.set_margin_top(CONTENT_PADDING);
+        main_box.set_margin_bottom(CONTENT_PADDING);
+        main_box.set_margin_start(CONTENT_PADDING);
+        main_box.set_margin_end(CONTENT_PADDING);
+
+        let files = Rc::new(RefCell::new(Vec::new()));
+        let locale_combo = build_locale_combo(&entry);
+        let plan: PlanFn = {
+            let (files, locale_combo) = (files.clone(), locale_combo.clone());
+            Rc::new(move || {
+                let locale = match locale_combo.active_id() {
+                    Some(id) if !id.is_empty() => Locale::parse(&id),
+                    _ => Locale::current(),
+                };
+                LaunchService::plan(
+                    &entry,
+                    desktop_file.as_deref(),
+                    &files.borrow(),
+                    locale.as_ref(),
+                )
+            })
+        };
+        let dry_run = gtk::Box::new(gtk::Orientation::Vertical, 4);
+        let refresh: Rc<dyn Fn()> = {
+            let (dry_run, plan) = (dry_run.clone(), plan.clone());
+            Rc::new(move || show_plan(&dry_run, plan()))
+        };
+        main_box.append(&build_sample_files(&window, files, refresh.clone()));
+
+        let locale_row = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        locale_row.append(&gtk::Label::new(Some("Locale for %c and %i")));
+        locale_row.append(&locale_combo);
+        main_box.append(&locale_row);
+        {
+            let refresh = refresh.clone();
+            locale_combo.connect_changed(move |_| refresh());
+        }
+
+        let dry_title = gtk::Label::new(Some("Dry Run"));
+        dry_title.set_xalign(0.0);
+        dry_title.add_css_class("heading");
+        main_box.append(&dry_title);
+        main_box.append(&dry_run);
+        refresh();
+
+        let log_title = gtk::Label::new(Some("Output"));
+        log_title.set_xalign(0.0);
+        log_title.add_css_class("heading");
+        main_box.append(&log_title);
+
+        let view = gtk::TextView::new();
+        view.set_editable(false);
+        view.set_cursor_visible(false);
+        view.set_monospace(true);
+        view.set_wrap_mode(gtk::WrapMode::WordChar);
+        let log = view.buffer();
+        log.create_tag(Some("stderr"), &[("foreground", &"#c01c28")]);
+        log.create_tag(Some("status"), &[("weight", &700)]);
+        let log_scroll = gtk::ScrolledWindow::builder()
+            .hexpand(true)
+            .vexpand(true)
+            .build();
+        log_scroll.set_child(Some(&view));
+        log_scroll.add_css_class("card");
+        main_box.append(&log_scroll);
+
+        let actions_row = gtk::Box::new(gtk::Orientation::Horizontal, SECTION_SPACING);
+        actions_row.set_halign(gtk::Align::Center);
+        actions_row.append(&close_button);
+        actions_row.append(&run_button);
+        main_box.append(&actions_row);
+
+        toolbar_view.set_content(Some(&main_box));
+        window.set_content(Some(&toolbar_view));
+
+        Self {
+            window,
+            run_button,
+            close_button,
+            log,
+            plan,
+        }
+    }
+
+    pub fn present(self) {
+        {
+            let window = self.window.clone();
+            self.close_button.connect_clicked(move |_| window.close());
+        }
+        let (log, plan) = (self.log.clone(), self.plan.clone());
+        self.run_button.connect_clicked(move |_| match plan() {
+            Ok(plan) => {
+                let sink = log.clone();
+                LaunchService::run(&plan, move |e| append_event(&sink, e));
+            }
+            Err(e) => append(&log, &format!("Cannot run: {}\n", e), Some("stderr")),
+        });
+        self.window.present();
+    }
+}
+
+// Files and URIs handed to `%f`, `%F`, `%u` and `%U`.
+fn build_sample_files(
+    window: &AdwWindow,
+    files: Rc<RefCell<Vec<String>>>,
+    on_change: Rc<dyn Fn()>,
+) -> gtk::Box {
+    let section = gtk::Box::new(gtk::Orientation::Vertical, 6);
+    let title = gtk::Label::new(Some("Sample Files"));
+    title.set_xalign(0.0);
+    title.add_css_class("heading");
+    section.append(&title);
+
+    let list = gtk::Label::new(None);
+    list.set_xalign(0.0);
+    list.set_wrap(true);
+    list.set_selectable(true);
+    section.append(&list);
+    let show = {
+        let (list, files) = (list.clone(), files.clone());
+        Rc::new(move || {
+            let files = files.borrow();
+            if files.is_empty() {
+                list.set_text("None: the launcher starts without files");
+                list.add_css_class("dim-label");
+            } else {
+                list.set_text(&files.join("\n"));
+                list.remove_css_class("dim-label");
+            }
+            on_change();
+        })
+    };
+    show();
+
+    let row = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+    let add_files = gtk::Button::with_label("Add Files...");
+    let uri_entry = gtk::Entry::new();
+    uri_entry.set_placeholder_text(Some("https://example.org/ or any URI"));
+    uri_entry.set_hexpand(true);
+    let add_uri = gtk::Button::with_label("Add URI");
+    let clear = gtk::Button::with_label("Clear");
+    row.append(&add_files);
+    row.append(&uri_entry);
+    row.append(&add_uri);
+    row.append(&clear);
+    section.append(&row);
+
+    {
+        let (window, files, show) = (window.clone(), files.clone(), show.clone());
+        add_files.connect_clicked(move |_| {
+            let dialog = gtk::FileChooserDialog::new(
+                Some("Select Sample Files"),
+                Some(&window),
+                gtk::FileChooserAction::Open,
+                &[
+                    ("Cancel", gtk::ResponseType::Cancel),
+                    ("Add", gtk::ResponseType::Accept),
+                ],
+            );
+            dialog.set_select_multiple(true);
+            let (files, show) = (files.clone(), show.clone());
+            dialog.connect_response(move |d, resp| {
+                if resp == gtk::ResponseType::Accept {
+                    let chosen = d.files();
+                    for idx in 0..chosen.n_items() {
+                        if let Some(path) = chosen
+                            .item(idx)
+                            .and_downcast::<gio::File>()
+                            .and_then(|f| f.path())
+                        {
+                            files.borrow_mut().push(path.to_string_lossy().to_string());
+                        }
+                    }
+                    show();
+                }
+                d.close();
+            });
+            dialog.show();
+        });
+    }
+    {
+        let add = Rc::new({
+            let (files, show, uri_entry) = (files.clone(), show.clone(), uri_entry.clone());
+            move || {
+                let uri = uri_entry.text().trim().to_string();
+                if !uri.is_empty() {
+                    files.borrow_mut().push(uri);
+                    uri_entry.set_text("");
+                    show();
+                }
+            }
+        });
+        {
+            let add = add.clone();
+            add_uri.connect_clicked(move |_| add());
+        }
+        uri_entry.connect_activate(move |_| add());
+    }
+    clear.connect_clicked(move |_| {
+        files.borrow_mut().clear();
+        show();
+    });
+    section
+}
+
+// "Session default" follows the environment; the others are the locales
+// Name is translated into.
+fn build_locale_combo(entry: &DesktopEntry) -> gtk::ComboBoxText {
+    let combo = gtk::ComboBoxText::new();
+    combo.append(Some(""), "Session default");
+    let mut locales: Vec<&str> = entry.name_localized.iter().map(|(l, _)| l).collect();
+    locales.sort_unstable();
+    for locale in locales {
+        combo.append(Some(locale), locale);
+    }
+    combo.set_active_id(Some(""));
+    combo
+}
+
+fn show_plan(container: &gtk::Box, plan: anyhow::Result<LaunchPlan>) {
+    while let Some(child) = container.first_child() {
+        container.remove(&child);
+    }
+    let line = |text: &str, class: Option<&str>| {
+        let lbl = gtk::Label::new(Some(text));
+        lbl.set_xalign(0.0);
+        lbl.set_wrap(true);
+        lbl.set_selectable(true);
+        if let Some(class) = class {
+            lbl.add_css_class(class);
+        }
+        container.append(&lbl);
+    };
+    let plan = match plan {
+        Ok(plan) => plan,
+        Err(e) => return line(&format!("Would not start: {}", e), Some("error")),
+    };
+    line(
+        &match &plan.cwd {
+            Some(dir) => format!("Working directory: {} (from Path)", dir.display()),
+            None => "Working directory: inherited from Launcher Studio".to_string(),
+        },
+        None,
+    );
+    let program = plan.processes.first().and_then(|argv| argv.first());
+    match (&plan.program, program) {
+        (Some(found), _) => line(&format!("Program: {}", found.display()), None),
+        (None, Some(name)) => line(
+            &format!("Program: {} is not an executable file or on PATH", name),
+            Some("error"),
+        ),
+        (None, None) => line("Program: none, Exec expands to nothing", Some("error")),
+    }
+    if let Some(terminal) = &plan.terminal {
+        line(&format!("Runs inside: {}", terminal.join(" ")), None);
+    }
+    for (idx, argv) in plan.processes.iter().enumerate() {
+        let args: Vec<String> = argv
+            .iter()
+            .enumerate()
+            .map(|(n, arg)| format!("  argv[{}] = {:?}", n, arg))
+            .collect();
+        let lbl = gtk::Label::new(Some(&format!("Process {}:\n{}", idx + 1, args.join("\n"))));
+        lbl.set_xalign(0.0);
+        lbl.set_selectable(true);
+        lbl.add_css_class("monospace");
+        container.append(&lbl);
+    }
+    for note in &plan.notes {
+        line(&note.to_string(), Some("warning"));
+    }
+}
+
+fn append_event(log: &gtk::TextBuffer, event: LaunchEvent) {
+    match event {
+        LaunchEvent::Started { process, argv } => append(
+            log,
+            &format!("[{}] $ {}\n", process + 1, argv.join(" ")),
+            Some("status"),
+        ),
+        LaunchEvent::Stdout { process, line } => {
+            append(log, &format!("[{}] {}\n", process + 1, line), None)
+        }
+        LaunchEvent::Stderr { process, line } => append(
+            log,
+            &format!("[{}] {}\n", process + 1, line),
+            Some("stderr"),
+        ),
+        LaunchEvent::Exited {
+            process,
+            code,
+            signal,
+        } => {
+            let how = match (code, signal) {
+                (Some(code), _) => format!("exited with status {}", code),
+                (None, Some(signal)) => format!("killed by signal {}", signal),
+                (None, None) => "ended".to_string(),
+            };
+            let tag = if code == Some(0) { "status" } else { "stderr" };
+            append(log, &format!("[{}] {}\n", process + 1, how), Some(tag));
+        }
+        LaunchEvent::Failed { process, error } => append(
+            log,
+            &format!("[{}] failed to start: {}\n", process + 1, error),
+            Some("stderr"),
+        ),
+    }
+}
+
+fn append(log: &gtk::TextBuffer, text: &str, tag: Option<&str>) {
+    let mut end = log.end_iter();
+    match tag {
+        Some(tag) => log.insert_with_tags_by_name(&mut end, text, &[tag]),
+        None => log.insert(&mut end, text),
+    }
+}
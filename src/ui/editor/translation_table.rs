@@ -0,0 +1,272 @@
+use crate::domain::desktop_entry::{DesktopEntry, join_list, split_list};
+use crate::domain::locale::Locale;
+use crate::services::locale_service::LocaleService;
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, ComboBoxText, Entry, Grid, Label, Orientation};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type ChangeHandler = Rc<RefCell<Option<Rc<dyn Fn()>>>>;
+
+// Translatable keys of the main group, one column each.
+const KEYS: [&str; 5] = ["Name", "GenericName", "Comment", "Icon", "Keywords"];
+
+// Grid of translations: one row per locale, one column per key. Cells that
+// are empty while the untranslated key has a value are highlighted.
+#[derive(Clone)]
+pub struct TranslationTable {
+    pub container: GtkBox,
+    grid: Grid,
+    picker: ComboBoxText,
+    rows: Rc<RefCell<Vec<LocaleRow>>>,
+    on_changed: ChangeHandler,
+}
+
+#[derive(Clone)]
+struct LocaleRow {
+    locale: String,
+    cells: Vec<Entry>,
+}
+
+impl TranslationTable {
+    pub fn new() -> Self {
+        let container = GtkBox::new(Orientation::Vertical, 6);
+        let title = Label::new(Some("Translations"));
+        title.set_xalign(0.0);
+        title.add_css_class("heading");
+
+        let add_row = GtkBox::new(Orientation::Horizontal, 6);
+        let picker = ComboBoxText::with_entry();
+        picker.set_hexpand(true);
+        for code in LocaleService::available_locales() {
+            picker.append_text(&code);
+        }
+        if let Some(entry) = picker_entry(&picker) {
+            entry.set_placeholder_text(Some("Locale, e.g. de or pt_BR"));
+        }
+        let add_btn = Button::with_label("Add Language");
+        add_row.append(&picker);
+        add_row.append(&add_btn);
+
+        let grid = Grid::new();
+        grid.set_row_spacing(4);
+        grid.set_column_spacing(6);
+
+        container.append(&title);
+        container.append(&add_row);
+        container.append(&grid);
+
+        let table = Self {
+            container,
+            grid,
+            picker,
+            rows: Rc::new(RefCell::new(Vec::new())),
+            on_changed: Rc::new(RefCell::new(None)),
+        };
+        {
+            let t = table.clone();
+            add_btn.connect_clicked(move |_| t.add_language());
+        }
+        if let Some(entry) = picker_entry(&table.picker) {
+            let t = table.clone();
+            entry.connect_activate(move |_| t.add_language());
+        }
+        table.rebuild(Vec::new());
+        table
+    }
+
+    pub fn connect_changed(&self, f: impl Fn() + 'static) {
+        *self.on_changed.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn set_entry(&self, de: &DesktopEntry) {
+        let mut data: Vec<(String, Vec<String>)> = Vec::new();
+        let mut put = |locale: &str, col: usize, value: String| {
+            let idx = match data.iter().position(|(l, _)| l == locale) {
+                Some(idx) => idx,
+                None => {
+                    data.push((locale.to_string(), vec![String::new(); KEYS.len()]));
+                    data.len() - 1
+                }
+            };
+            data[idx].1[col] = value;
+        };
+        for (col, translations) in [
+            &de.name_localized,
+            &de.generic_name_localized,
+            &de.comment_localized,
+            &de.icon_localized,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            for (locale, value) in translations.iter() {
+                put(locale, col, value.clone());
+            }
+        }
+        for (locale, words) in de.keywords_localized.iter() {
+            let joined = join_list(words);
+            put(
+                locale,
+                4,
+                joined.strip_suffix(';').unwrap_or(&joined).to_string(),
+            );
+        }
+        self.rebuild(data);
+        self.highlight_missing(de);
+    }
+
+    /// Writes the table into the `*_localized` fields of `de`. Values are
+    /// kept as typed, leading and trailing spaces included.
+    pub fn apply_to(&self, de: &mut DesktopEntry) {
+        for row in self.rows.borrow().iter() {
+            let locale = row.locale.trim().to_string();
+            let text = |col: usize| row.cells[col].text().to_string();
+            for (col, target) in [
+                &mut de.name_localized,
+                &mut de.generic_name_localized,
+                &mut de.comment_localized,
+                &mut de.icon_localized,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                if !text(col).trim().is_empty() {
+                    target.set(locale.clone(), text(col));
+                }
+            }
+            let words = split_list(&text(4));
+            if !words.is_empty() {
+                de.keywords_localized.set(locale.clone(), words);
+            }
+        }
+    }
+
+    /// Marks cells left empty although `de` has an untranslated value.
+    pub fn highlight_missing(&self, de: &DesktopEntry) {
+        let has_base = [
+            !de.name.trim().is_empty(),
+            de.generic_name.is_some(),
+            de.comment.is_some(),
+            de.icon.is_some(),
+            !de.keywords.is_empty(),
+        ];
+        for row in self.rows.borrow().iter() {
+            for (cell, has_base) in row.cells.iter().zip(has_base) {
+                if has_base && cell.text().trim().is_empty() {
+                    cell.add_css_class("warning");
+                    cell.set_tooltip_text(Some("Missing translation"));
+                } else {
+                    cell.remove_css_class("warning");
+                    cell.set_tooltip_text(None);
+                }
+            }
+        }
+    }
+
+    fn notify(&self) {
+        let cb = self.on_changed.borrow().clone();
+        if let Some(cb) = cb {
+            cb();
+        }
+    }
+
+    fn values(&self) -> Vec<(String, Vec<String>)> {
+        self.rows
+            .borrow()
+            .iter()
+            .map(|r| {
+                let cells = r.cells.iter().map(|c| c.text().to_string()).collect();
+                (r.locale.clone(), cells)
+            })
+            .collect()
+    }
+
+    fn add_language(&self) {
+        let Some(entry) = picker_entry(&self.picker) else {
+            return;
+        };
+        let code = entry.text().trim().to_string();
+        let mut data = self.values();
+        match Locale::parse(&code) {
+            Some(_) if !data.iter().any(|(l, _)| *l == code) => {
+                entry.remove_css_class("error");
+                entry.set_tooltip_text(None);
+                entry.set_text("");
+                data.push((code, vec![String::new(); KEYS.len()]));
+                self.rebuild(data);
+                self.notify();
+            }
+            Some(_) => {
+                entry.add_css_class("error");
+                entry.set_tooltip_text(Some("This language is already in the table"));
+            }
+            None => {
+                entry.add_css_class("error");
+                entry.set_tooltip_text(Some("Not a locale code (lang_COUNTRY@MODIFIER)"));
+            }
+        }
+    }
+
+    fn remove_language(&self, locale: &str) {
+        let mut data = self.values();
+        data.retain(|(l, _)| l != locale);
+        self.rebuild(data);
+        self.notify();
+    }
+
+    fn rebuild(&self, data: Vec<(String, Vec<String>)>) {
+        while let Some(child) = self.grid.first_child() {
+            self.grid.remove(&child);
+        }
+        self.rows.borrow_mut().clear();
+
+        let header = Label::new(Some("Locale"));
+        header.add_css_class("dim-label");
+        self.grid.attach(&header, 0, 0, 1, 1);
+        for (col, key) in KEYS.iter().enumerate() {
+            let lbl = Label::new(Some(key));
+            lbl.add_css_class("dim-label");
+            self.grid.attach(&lbl, col as i32 + 1, 0, 1, 1);
+        }
+
+        for (idx, (locale, values)) in data.into_iter().enumerate() {
+            let y = idx as i32 + 1;
+            let lbl = Label::new(Some(&locale));
+            lbl.set_xalign(0.0);
+            // Locales read from a file may be malformed; typed ones never are.
+            if Locale::parse(&locale).is_none() {
+                lbl.add_css_class("error");
+                lbl.set_tooltip_text(Some("Not a valid locale code"));
+            }
+            self.grid.attach(&lbl, 0, y, 1, 1);
+
+            let mut cells = Vec::new();
+            for (col, value) in values.iter().enumerate() {
+                let cell = Entry::new();
+                cell.set_text(value);
+                cell.set_hexpand(true);
+                cell.set_width_chars(10);
+                let t = self.clone();
+                cell.connect_changed(move |_| t.notify());
+                self.grid.attach(&cell, col as i32 + 1, y, 1, 1);
+                cells.push(cell);
+            }
+
+            let remove_btn = Button::from_icon_name("user-trash-symbolic");
+            remove_btn.set_tooltip_text(Some("Remove language"));
+            {
+                let t = self.clone();
+                let l = locale.clone();
+                remove_btn.connect_clicked(move |_| t.remove_language(&l));
+            }
+            self.grid
+                .attach(&remove_btn, KEYS.len() as i32 + 1, y, 1, 1);
+            self.rows.borrow_mut().push(LocaleRow { locale, cells });
+        }
+    }
+}
+
+fn picker_entry(picker: &ComboBoxText) -> Option<Entry> {
+    picker.child().and_downcast::<Entry>()
+}
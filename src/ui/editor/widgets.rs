@@ -1,4 +1,10 @@
+use crate::domain::ini_document::IniDocument;
+use crate::services::mime_database::MimeDatabase;
+use crate::ui::editor::actions_editor::ActionsEditor;
+use crate::ui::editor::translation_table::TranslationTable;
 use gtk4::{Box as GtkBox, Button, CheckButton, ComboBoxText, Entry, Label, TextView};
+use std::cell::RefCell;
+use std::rc::Rc;
 #[derive(Clone)]
 pub struct EntryWidgets {
     pub type_combo: ComboBoxText,
@@ -18,16 +24,25 @@ pub struct EntryWidgets {
     pub tryexec_entry: Entry,
     pub path_entry: Entry,
     pub url_entry: Entry,
-    pub actions_entry: Entry,
-    pub localized_name: TextView,
-    pub localized_gname: TextView,
-    pub localized_comment: TextView,
+    pub version_entry: Entry,
+    pub wmclass_entry: Entry,
+    pub implements_entry: Entry,
+    pub hidden_check: CheckButton,
+    pub dbus_check: CheckButton,
+    pub gpu_check: CheckButton,
+    pub single_window_check: CheckButton,
+    pub actions_editor: ActionsEditor,
+    pub translations: TranslationTable,
     pub extra_kv: TextView,
     pub exec_lbl: Label,
     pub exec_app_box: GtkBox,
     pub exec_link_box: GtkBox,
     pub exec_btn: Button,
     pub url_btn: Button,
+    // Layout of the file being edited, so unchanged lines are written back as-is.
+    pub document: Rc<RefCell<Option<IniDocument>>>,
+    // Parsed once when the editor is built and shared with the dialogs.
+    pub mime_db: Rc<MimeDatabase>,
 }
 impl EntryWidgets {
     pub fn clone_all(&self) -> Self {
@@ -49,16 +64,23 @@ impl EntryWidgets {
             tryexec_entry: self.tryexec_entry.clone(),
             path_entry: self.path_entry.clone(),
             url_entry: self.url_entry.clone(),
-            actions_entry: self.actions_entry.clone(),
-            localized_name: self.localized_name.clone(),
-            localized_gname: self.localized_gname.clone(),
-            localized_comment: self.localized_comment.clone(),
+            version_entry: self.version_entry.clone(),
+            wmclass_entry: self.wmclass_entry.clone(),
+            implements_entry: self.implements_entry.clone(),
+            hidden_check: self.hidden_check.clone(),
+            dbus_check: self.dbus_check.clone(),
+            gpu_check: self.gpu_check.clone(),
+            single_window_check: self.single_window_check.clone(),
+            actions_editor: self.actions_editor.clone(),
+            translations: self.translations.clone(),
             extra_kv: self.extra_kv.clone(),
             exec_lbl: self.exec_lbl.clone(),
             exec_app_box: self.exec_app_box.clone(),
             exec_link_box: self.exec_link_box.clone(),
             exec_btn: self.exec_btn.clone(),
             url_btn: self.url_btn.clone(),
+            document: self.document.clone(),
+            mime_db: self.mime_db.clone(),
         }
     }
 }
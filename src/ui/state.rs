@@ -1,3 +1,4 @@
+use crate::services::file_stamp::FileStamp;
 use gtk4::ListBoxRow;
 use std::cell::RefCell;
 use std::path::PathBuf;
@@ -9,6 +10,8 @@ pub struct UiState {
     pub in_edit: bool,
     pub temp_row: Option<ListBoxRow>,
     pub is_dirty: bool,
+    // The selected file as it was when loaded or last saved.
+    pub on_disk: Option<FileStamp>,
 }
 
 pub type SharedState = Rc<RefCell<UiState>>;
@@ -3,12 +3,32 @@ use gtk4::gio::SimpleAction;
 use gtk4::{Application, FileChooserAction, FileChooserDialog, FileFilter, ResponseType};
 use std::path::PathBuf;
 
+use crate::domain::autostart::autostart_info;
 use crate::domain::desktop_entry::DesktopEntry;
+use crate::domain::entry_diff::merge_entries;
+use crate::domain::locale::Locale;
+use crate::services::autostart_service::AutostartService;
 use crate::services::desktop_reader::DesktopReader;
-use crate::services::desktop_writer::DesktopWriter;
-use crate::ui::dialogs;
-use crate::ui::editor::entry_form::{EntryWidgets, collect_entry, set_form_from_entry};
+use crate::services::desktop_scanner::{DesktopScanner, Origin};
+use crate::services::desktop_shortcut_service::DesktopShortcutService;
+use crate::services::desktop_writer::{DesktopWriter, Written};
+use crate::services::file_stamp::FileStamp;
+use crate::services::gettext_service::GettextService;
+use crate::services::health_service::HealthService;
+use crate::services::history_store::HistoryStore;
+use crate::services::mime_apps_service::MimeAppsService;
+use crate::services::override_service::OverrideService;
+use crate::services::trash_service::TrashService;
+use crate::ui::dialogs::{self, ConflictChoice};
+use crate::ui::editor::default_apps_view::DefaultAppsDialog;
+use crate::ui::editor::diff_view::DiffDialog;
+use crate::ui::editor::entry_form::{EntryWidgets, collect_entry, read_form, set_form_from_entry};
+use crate::ui::editor::health_view::HealthDialog;
+use crate::ui::editor::history_view::HistoryDialog;
+use crate::ui::editor::merge_view::MergeDialog;
+use crate::ui::editor::run_dialog::RunDialog;
 use crate::ui::state::SharedState;
+use crate::ui::windows::list_manager;
 
 pub fn register_actions(
     app: &Application,
@@ -30,9 +50,31 @@ pub fn register_actions(
     register_open_action(app, win, widgets, state.clone(), status_label);
     register_save_action(app, win, widgets, state.clone(), status_label);
     register_save_as_action(app, win, widgets, state.clone(), status_label);
-    register_refresh_action(app, refresh_list);
+    register_refresh_action(app, refresh_list.clone());
     register_quit_action(app, win, state.clone());
     register_dir_actions(app, win);
+    register_translation_actions(app, win, widgets, state.clone(), status_label);
+    register_history_action(app, win, widgets, state.clone(), status_label);
+    register_run_action(app, win, widgets, state.clone());
+    register_default_apps_action(app, win, widgets, state.clone());
+    register_autostart_actions(
+        app,
+        win,
+        widgets,
+        state.clone(),
+        status_label,
+        refresh_list.clone(),
+    );
+    register_desktop_action(app, win, state.clone(), status_label, refresh_list.clone());
+    register_health_action(
+        app,
+        win,
+        widgets,
+        state.clone(),
+        status_label,
+        refresh_list.clone(),
+    );
+    register_override_actions(app, win, widgets, state.clone(), status_label, refresh_list);
     register_about_actions(app, win);
     register_fullscreen_action(win);
     register_shortcuts(app);
@@ -109,18 +151,21 @@ fn register_save_action(
     let s = state.clone();
     let lbl = status_label.clone();
     let wwin = win.clone();
-    action.connect_activate(move |_, _| match save_entry(&w, &s) {
-        Ok((path, updated)) => {
-            if updated {
-                lbl.set_text(&format!("Updated: {}", path.display()));
-            } else {
-                lbl.set_text(&format!("Saved: {}", path.display()));
+    action.connect_activate(move |_, _| {
+        let (lbl, err_win) = (lbl.clone(), wwin.clone());
+        save_checked(&w, &s, &wwin, move |result| match result {
+            Ok(saved) => {
+                let verb = if saved.updated { "Updated" } else { "Saved" };
+                lbl.set_text(&with_warning(
+                    format!("{}: {}", verb, saved.path.display()),
+                    saved.warning.as_deref(),
+                ));
             }
-        }
-        Err(e) => {
-            lbl.set_text(&format!("Save failed: {}", e));
-            dialogs::show_error(&wwin, &e);
-        }
+            Err(e) => {
+                lbl.set_text(&format!("Save failed: {}", e));
+                dialogs::show_error(&err_win, &e);
+            }
+        });
     });
     app.add_action(&action);
 }
@@ -140,7 +185,12 @@ fn register_save_as_action(
     action.connect_activate(move |_, _| {
         do_save_as(&w, &s, &wwin, {
             let lbl2 = lbl.clone();
-            move |path| lbl2.set_text(&format!("Saved: {}", path.display()))
+            move |written: Written| {
+                lbl2.set_text(&with_warning(
+                    format!("Saved: {}", written.path.display()),
+                    written.warning.as_deref(),
+                ))
+            }
         });
     });
     app.add_action(&action);
@@ -198,6 +248,638 @@ fn register_dir_actions(app: &Application, win: &AdwApplicationWindow) {
     app.add_action(&open_user);
 }
 
+fn register_translation_actions(
+    app: &Application,
+    win: &AdwApplicationWindow,
+    widgets: &EntryWidgets,
+    state: SharedState,
+    status_label: &gtk4::Label,
+) {
+    for (name, all) in [("export_pot", false), ("export_pot_all", true)] {
+        let action = SimpleAction::new(name, None);
+        let w = widgets.clone();
+        let s = state.clone();
+        let lbl = status_label.clone();
+        let wwin = win.clone();
+        action.connect_activate(move |_, _| do_export_pot(&w, &s, &wwin, &lbl, all));
+        app.add_action(&action);
+    }
+
+    let action = SimpleAction::new("import_po", None);
+    let w = widgets.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    action.connect_activate(move |_, _| do_import_po(&w, &wwin, &lbl));
+    app.add_action(&action);
+}
+
+// Exports the entry being edited, or every launcher in the user directory.
+pub fn do_export_pot(
+    widgets: &EntryWidgets,
+    state: &SharedState,
+    win: &impl IsA<gtk4::Window>,
+    status_label: &gtk4::Label,
+    all: bool,
+) {
+    let sources: Vec<(String, DesktopEntry)> = if all {
+        DesktopScanner::scan()
+            .into_iter()
+            .filter(|e| e.active.origin == Origin::User)
+            .filter_map(|e| {
+                let de = DesktopReader::read_from_path(&e.active.path).ok()?;
+                Some((e.id, de))
+            })
+            .collect()
+    } else {
+        let de = read_form(widgets);
+        let name = match &state.borrow().selected_path {
+            Some(p) => file_name_of(p),
+            None => format!("{}.desktop", de.name.trim()),
+        };
+        vec![(name, de)]
+    };
+
+    let dialog = FileChooserDialog::new(
+        Some("Export translation template"),
+        Some(win.upcast_ref::<gtk4::Window>()),
+        FileChooserAction::Save,
+        &[
+            ("Cancel", ResponseType::Cancel),
+            ("Export", ResponseType::Accept),
+        ],
+    );
+    dialog.set_current_name("launchers.pot");
+    let lbl = status_label.clone();
+    let parent = win.clone().upcast::<gtk4::Window>();
+    dialog.connect_response(move |d, resp| {
+        if resp == ResponseType::Accept
+            && let Some(path) = d.file().and_then(|f| f.path())
+        {
+            match GettextService::export_template(&sources, &path) {
+                Ok(()) => lbl.set_text(&format!("Exported: {}", path.display())),
+                Err(e) => dialogs::show_error(&parent, &e.to_string()),
+            }
+        }
+        d.close();
+    });
+    dialog.show();
+}
+
+pub fn do_import_po(
+    widgets: &EntryWidgets,
+    win: &impl IsA<gtk4::Window>,
+    status_label: &gtk4::Label,
+) {
+    let dialog = FileChooserDialog::new(
+        Some("Import translations"),
+        Some(win.upcast_ref::<gtk4::Window>()),
+        FileChooserAction::Open,
+        &[
+            ("Cancel", ResponseType::Cancel),
+            ("Import", ResponseType::Accept),
+        ],
+    );
+    let filter = FileFilter::new();
+    filter.set_name(Some("Gettext catalogs"));
+    filter.add_pattern("*.po");
+    dialog.add_filter(&filter);
+
+    let w = widgets.clone();
+    let lbl = status_label.clone();
+    // Reports outlive the file chooser, so they belong to the main window.
+    let parent = win.clone().upcast::<gtk4::Window>();
+    dialog.connect_response(move |d, resp| {
+        if resp == ResponseType::Accept
+            && let Some(path) = d.file().and_then(|f| f.path())
+        {
+            let mut de = read_form(&w);
+            match GettextService::import(&mut de, &path) {
+                Ok((locale, report)) => {
+                    set_form_from_entry(&w, &de);
+                    lbl.set_text(&format!("Imported {} translations", locale));
+                    dialogs::show_import_report(&parent, &locale, &report);
+                }
+                Err(e) => dialogs::show_error(&parent, &e.to_string()),
+            }
+        }
+        d.close();
+    });
+    dialog.show();
+}
+
+fn file_name_of(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn register_autostart_actions(
+    app: &Application,
+    win: &AdwApplicationWindow,
+    widgets: &EntryWidgets,
+    state: SharedState,
+    status_label: &gtk4::Label,
+    refresh_list: impl Fn() + Clone + 'static,
+) {
+    // Parameter: (path, enabled), sent by the switches in the sidebar.
+    let action = SimpleAction::new(
+        "set_autostart",
+        Some(&<(String, bool)>::static_variant_type()),
+    );
+    let w = widgets.clone();
+    let s = state.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    let rl = refresh_list.clone();
+    action.connect_activate(move |_, param| {
+        let Some((path, enabled)) = param.and_then(|p| p.get::<(String, bool)>()) else {
+            return;
+        };
+        let path = PathBuf::from(path);
+        match AutostartService::set_enabled(&path, enabled) {
+            Ok(written) => {
+                reopen_if_current(&w, &s, &lbl, &path, &written.path);
+                rl();
+                lbl.set_text(&with_warning(
+                    format!(
+                        "{} {} at login",
+                        file_name_of(&written.path),
+                        if enabled {
+                            "starts"
+                        } else {
+                            "no longer starts"
+                        }
+                    ),
+                    written.warning.as_deref(),
+                ));
+            }
+            Err(e) => {
+                rl();
+                dialogs::show_error(&wwin, &e.to_string());
+            }
+        }
+    });
+    app.add_action(&action);
+
+    let action = SimpleAction::new("add_to_autostart", None);
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    let rl = refresh_list.clone();
+    let s = state.clone();
+    action.connect_activate(move |_, _| {
+        run_on_selection(&s, &wwin, |path| {
+            let target = AutostartService::add(path)?;
+            rl();
+            lbl.set_text(&format!("Added to autostart: {}", target.display()));
+            Ok(())
+        });
+    });
+    app.add_action(&action);
+
+    let action = SimpleAction::new("autostart_delay", None);
+    let w = widgets.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    action.connect_activate(move |_, _| {
+        run_on_selection(&state, &wwin, |path| {
+            if !AutostartService::is_autostart(path) {
+                return Err(anyhow::anyhow!(
+                    "Select a login item in the Autostart section first"
+                ));
+            }
+            let current = DesktopReader::read_from_path(path)
+                .ok()
+                .and_then(|de| autostart_info(&de, &[]).delay);
+            let (w, s, lbl, rl, win, path) = (
+                w.clone(),
+                state.clone(),
+                lbl.clone(),
+                refresh_list.clone(),
+                wwin.clone(),
+                path.to_path_buf(),
+            );
+            dialogs::ask_autostart_delay(&wwin, current, move |seconds| {
+                match AutostartService::set_delay(&path, seconds) {
+                    Ok(written) => {
+                        reopen_if_current(&w, &s, &lbl, &path, &written.path);
+                        rl();
+                        let text = match seconds {
+                            Some(d) if d > 0 => format!("Starts {} s after login", d),
+                            _ => "Starts right after login".to_string(),
+                        };
+                        lbl.set_text(&with_warning(text, written.warning.as_deref()));
+                    }
+                    Err(e) => dialogs::show_error(&win, &e.to_string()),
+                }
+            });
+            Ok(())
+        });
+    });
+    app.add_action(&action);
+}
+
+// Copies the selected launcher to the desktop, where it then follows every
+// save of the launcher.
+fn register_desktop_action(
+    app: &Application,
+    win: &AdwApplicationWindow,
+    state: SharedState,
+    status_label: &gtk4::Label,
+    refresh_list: impl Fn() + 'static,
+) {
+    let action = SimpleAction::new("place_on_desktop", None);
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    action.connect_activate(move |_, _| {
+        run_on_selection(&state, &wwin, |path| {
+            let (written, trust_error) = DesktopShortcutService::place(path)?;
+            refresh_list();
+            let text = match trust_error {
+                None => format!("Placed on the desktop: {}", written.path.display()),
+                Some(e) => format!(
+                    "Placed on the desktop: {}, but not marked trusted: {}",
+                    written.path.display(),
+                    e
+                ),
+            };
+            lbl.set_text(&with_warning(text, written.warning.as_deref()));
+            Ok(())
+        });
+    });
+    app.add_action(&action);
+}
+
+// Scans every launcher for dangling Exec, TryExec, Icon and Path keys. Once
+// the report closes, the sidebar and the open launcher catch up with the
+// fixes.
+fn register_health_action(
+    app: &Application,
+    win: &AdwApplicationWindow,
+    widgets: &EntryWidgets,
+    state: SharedState,
+    status_label: &gtk4::Label,
+    refresh_list: impl Fn() + Clone + 'static,
+) {
+    let action = SimpleAction::new("check_health", None);
+    let w = widgets.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    action.connect_activate(move |_, _| {
+        let theme = gtk4::IconTheme::for_display(&WidgetExt::display(&wwin));
+        let dialog = HealthDialog::new(Some(&wwin), move || {
+            HealthService::scan(|icon| theme.has_icon(icon))
+        });
+        let (w, s, lbl, rl, win) = (
+            w.clone(),
+            state.clone(),
+            lbl.clone(),
+            refresh_list.clone(),
+            wwin.clone(),
+        );
+        dialog.run(move |touched| {
+            if touched.is_empty() {
+                return;
+            }
+            rl();
+            reload_if_changed(&touched, &w, &s, &win, &lbl);
+        });
+    });
+    app.add_action(&action);
+}
+
+// Test-launches the form as it is now, saved or not.
+fn register_run_action(
+    app: &Application,
+    win: &AdwApplicationWindow,
+    widgets: &EntryWidgets,
+    state: SharedState,
+) {
+    let action = SimpleAction::new("run_launcher", None);
+    let w = widgets.clone();
+    let wwin = win.clone();
+    action.connect_activate(move |_, _| {
+        let desktop_file = state.borrow().selected_path.clone();
+        RunDialog::new(Some(&wwin), read_form(&w), desktop_file).present();
+    });
+    app.add_action(&action);
+}
+
+// Makes the saved launcher the default for the types and schemes in its
+// MimeType, through the user's mimeapps.list.
+fn register_default_apps_action(
+    app: &Application,
+    win: &AdwApplicationWindow,
+    widgets: &EntryWidgets,
+    state: SharedState,
+) {
+    let action = SimpleAction::new("default_apps", None);
+    let wwin = win.clone();
+    let db = widgets.mime_db.clone();
+    action.connect_activate(move |_, _| {
+        run_on_selection(&state, &wwin, |path| {
+            let id = MimeAppsService::desktop_id(path)?;
+            let entry = DesktopReader::read_from_path(path)?;
+            let locale = Locale::current();
+            DefaultAppsDialog::new(
+                Some(&wwin),
+                entry.localized_name(locale.as_ref()),
+                &id,
+                entry.mime_type.clone(),
+                db.clone(),
+            )
+            .present();
+            Ok(())
+        });
+    });
+    app.add_action(&action);
+}
+
+// After a service rewrote `path` (possibly into a new override at `target`),
+// shows the new file if `path` was open and has no unsaved edits.
+fn reopen_if_current(
+    widgets: &EntryWidgets,
+    state: &SharedState,
+    status_label: &gtk4::Label,
+    path: &std::path::Path,
+    target: &std::path::Path,
+) {
+    let current = {
+        let st = state.borrow();
+        st.selected_path.as_deref() == Some(path) && !st.is_dirty
+    };
+    if current {
+        open_path(widgets, state, status_label, target);
+    }
+}
+
+fn register_history_action(
+    app: &Application,
+    win: &AdwApplicationWindow,
+    widgets: &EntryWidgets,
+    state: SharedState,
+    status_label: &gtk4::Label,
+) {
+    let action = SimpleAction::new("show_history", None);
+    let w = widgets.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    action.connect_activate(move |_, _| {
+        run_on_selection(&state, &wwin, |path| {
+            let (w, s, lbl, win, path) = (
+                w.clone(),
+                state.clone(),
+                lbl.clone(),
+                wwin.clone(),
+                path.to_path_buf(),
+            );
+            HistoryDialog::new(Some(&wwin), &path.clone()).run(move |snapshot| {
+                let Some(snapshot) = snapshot else {
+                    return;
+                };
+                let (s2, win2) = (s.clone(), win.clone());
+                run_after_unsaved_confirmation(&win, &s, move || {
+                    // A system launcher, e.g. after its override was
+                    // reverted, gets the snapshot back as a new override.
+                    let restored = OverrideService::writable_copy(&path).and_then(|target| {
+                        HistoryStore::restore(&snapshot, &target).map(|()| target)
+                    });
+                    match restored {
+                        Ok(target) => {
+                            open_path(&w, &s2, &lbl, &target);
+                            lbl.set_text(&format!(
+                                "Restored an earlier version of {}",
+                                target.display()
+                            ));
+                        }
+                        Err(e) => dialogs::show_error(&win2, &e.to_string()),
+                    }
+                });
+            });
+            Ok(())
+        });
+    });
+    app.add_action(&action);
+}
+
+fn register_override_actions(
+    app: &Application,
+    win: &AdwApplicationWindow,
+    widgets: &EntryWidgets,
+    state: SharedState,
+    status_label: &gtk4::Label,
+    refresh_list: impl Fn() + Clone + 'static,
+) {
+    let action = SimpleAction::new("customize_launcher", None);
+    let w = widgets.clone();
+    let s = state.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    let rl = refresh_list.clone();
+    action.connect_activate(move |_, _| {
+        let (w2, s2, lbl2, win2, rl2) =
+            (w.clone(), s.clone(), lbl.clone(), wwin.clone(), rl.clone());
+        run_after_unsaved_confirmation(&wwin, &s, move || {
+            run_on_selection(&s2, &win2, |path| {
+                let copy = OverrideService::customize(path)?;
+                open_path(&w2, &s2, &lbl2, &copy);
+                rl2();
+                lbl2.set_text(&format!("Customizing a copy: {}", copy.display()));
+                Ok(())
+            });
+        });
+    });
+    app.add_action(&action);
+
+    let action = SimpleAction::new("hide_launcher", None);
+    let w = widgets.clone();
+    let s = state.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    let rl = refresh_list.clone();
+    action.connect_activate(move |_, _| {
+        let (w2, s2, lbl2, win2, rl2) =
+            (w.clone(), s.clone(), lbl.clone(), wwin.clone(), rl.clone());
+        run_after_unsaved_confirmation(&wwin, &s, move || {
+            run_on_selection(&s2, &win2, |path| {
+                let hidden = OverrideService::hide(path)?;
+                open_path(&w2, &s2, &lbl2, &hidden.path);
+                rl2();
+                lbl2.set_text(&with_warning(
+                    format!("Hidden with {}", hidden.path.display()),
+                    hidden.warning.as_deref(),
+                ));
+                Ok(())
+            });
+        });
+    });
+    app.add_action(&action);
+
+    let action = SimpleAction::new("compare_launcher", None);
+    let w = widgets.clone();
+    let s = state.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    action.connect_activate(move |_, _| {
+        run_on_selection(&s, &wwin, |path| {
+            let system = OverrideService::system_version(path)?;
+            let ours = read_form(&w);
+            let (w2, s2, lbl2) = (w.clone(), s.clone(), lbl.clone());
+            DiffDialog::new(Some(&wwin), &system.entry, &ours, &system.upstream).run(
+                move |updated| {
+                    let Some(updated) = updated else {
+                        return;
+                    };
+                    set_form_from_entry(&w2, &updated);
+                    s2.borrow_mut().is_dirty = true;
+                    match OverrideService::record_base(&system.id, &system.path) {
+                        Ok(()) => {
+                            lbl2.set_text(&format!("Took values from {}", system.path.display()))
+                        }
+                        Err(e) => lbl2.set_text(&format!("Could not record review: {}", e)),
+                    }
+                },
+            );
+            Ok(())
+        });
+    });
+    app.add_action(&action);
+
+    let action = SimpleAction::new("revert_launcher", None);
+    let w = widgets.clone();
+    let s = state.clone();
+    let lbl = status_label.clone();
+    let wwin = win.clone();
+    action.connect_activate(move |_, _| {
+        let Some(path) = s.borrow().selected_path.clone() else {
+            dialogs::show_error(&wwin, "No launcher selected");
+            return;
+        };
+        let (w2, s2, lbl2, win2, rl2) = (
+            w.clone(),
+            s.clone(),
+            lbl.clone(),
+            wwin.clone(),
+            refresh_list.clone(),
+        );
+        dialogs::confirm_revert(&wwin, &path, move || {
+            run_on_selection(&s2, &win2, |path| {
+                let original = OverrideService::revert(path)?;
+                open_path(&w2, &s2, &lbl2, &original);
+                rl2();
+                lbl2.set_text(&format!("Reverted to {}", original.display()));
+                Ok(())
+            });
+        });
+    });
+    app.add_action(&action);
+}
+
+// `text` with the warning of a write after it, if there was one.
+fn with_warning(text: String, warning: Option<&str>) -> String {
+    match warning {
+        Some(w) => format!("{}; {}", text, w),
+        None => text,
+    }
+}
+
+// Runs `f` on the selected launcher, reporting failures in a dialog.
+fn run_on_selection(
+    state: &SharedState,
+    win: &impl IsA<gtk4::Window>,
+    f: impl FnOnce(&std::path::Path) -> anyhow::Result<()>,
+) {
+    let Some(path) = state.borrow().selected_path.clone() else {
+        dialogs::show_error(win, "No launcher selected");
+        return;
+    };
+    if let Err(e) = f(&path) {
+        dialogs::show_error(win, &e.to_string());
+    }
+}
+
+/// Carries launchers among the `changed` paths over to their desktop copies,
+/// reporting the outcome as a toast so a sidebar refresh cannot hide it.
+pub fn sync_desktop_copies(changed: &[PathBuf], toasts: &adw::ToastOverlay) {
+    let mut updated = 0;
+    let mut warnings = Vec::new();
+    for path in changed {
+        match DesktopShortcutService::sync(path) {
+            Ok((n, warning)) => {
+                updated += n;
+                warnings.extend(warning);
+            }
+            Err(e) => {
+                toasts.add_toast(adw::Toast::new(&format!(
+                    "Updating the desktop copy of {} failed: {}",
+                    path.display(),
+                    e
+                )));
+                return;
+            }
+        }
+    }
+    if updated > 0 {
+        toasts.add_toast(adw::Toast::new(&with_warning(
+            format!("Updated {} desktop copies", updated),
+            (!warnings.is_empty())
+                .then(|| warnings.join("; "))
+                .as_deref(),
+        )));
+    }
+}
+
+/// Offers to reload the open launcher when it is among the `changed` paths
+/// reported by the directory watcher and really differs from what was loaded.
+pub fn reload_if_changed(
+    changed: &[PathBuf],
+    widgets: &EntryWidgets,
+    state: &SharedState,
+    win: &impl IsA<gtk4::Window>,
+    status_label: &gtk4::Label,
+) {
+    let (path, dirty) = {
+        let st = state.borrow();
+        match (&st.selected_path, &st.on_disk) {
+            (Some(path), Some(stamp)) if changed.contains(path) && stamp.changed_on_disk(path) => {
+                (path.clone(), st.is_dirty)
+            }
+            _ => return,
+        }
+    };
+    if !path.exists() {
+        status_label.set_text(&format!(
+            "{} was deleted on disk; saving will recreate it",
+            path.display()
+        ));
+        return;
+    }
+    let (w, s, lbl) = (widgets.clone(), state.clone(), status_label.clone());
+    dialogs::confirm_reload(win, &path.clone(), dirty, move || {
+        open_path(&w, &s, &lbl, &path);
+    });
+}
+
+fn open_path(
+    widgets: &EntryWidgets,
+    state: &SharedState,
+    status_label: &gtk4::Label,
+    path: &std::path::Path,
+) {
+    match DesktopReader::read_from_path(path) {
+        Ok(de) => {
+            set_form_from_entry(widgets, &de);
+            widgets.type_combo.set_sensitive(false);
+            let mut st = state.borrow_mut();
+            st.selected_path = Some(path.to_path_buf());
+            st.on_disk = FileStamp::read(path).ok();
+            st.in_edit = false;
+            st.is_dirty = false;
+            status_label.set_text(&path.to_string_lossy());
+        }
+        Err(e) => status_label.set_text(&format!("Open failed: {}", e)),
+    }
+}
+
 fn register_about_actions(app: &Application, win: &AdwApplicationWindow) {
     let about = SimpleAction::new("about", None);
     let w = win.clone();
@@ -245,6 +927,7 @@ pub fn do_new(
     {
         let mut st = state.borrow_mut();
         st.selected_path = None;
+        st.on_disk = None;
         st.in_edit = true;
         st.is_dirty = false;
     }
@@ -291,6 +974,7 @@ pub fn do_open(
                     set_form_from_entry(&w, &de);
                     w.type_combo.set_sensitive(false);
                     let mut st = s.borrow_mut();
+                    st.on_disk = FileStamp::read(&path).ok();
                     st.selected_path = Some(path.clone());
                     st.in_edit = false;
                     st.is_dirty = false;
@@ -304,12 +988,21 @@ pub fn do_open(
     dialog.show();
 }
 
-fn save_entry(widgets: &EntryWidgets, state: &SharedState) -> Result<(PathBuf, bool), String> {
+// What a save did. `warning` is set when the launcher was written but no
+// history snapshot was kept of the old version, or its desktop copy could
+// not be updated; the directory watcher tries the copy again.
+struct Saved {
+    path: PathBuf,
+    updated: bool,
+    warning: Option<String>,
+}
+
+fn save_entry(widgets: &EntryWidgets, state: &SharedState) -> Result<Saved, String> {
     let de = collect_entry(widgets)?;
     let sel_path = state.borrow().selected_path.clone();
-    let result = if let Some(path) = sel_path {
+    let (written, updated) = if let Some(path) = sel_path {
         DesktopWriter::write_to_path(&de, &path)
-            .map(|p| (p, true))
+            .map(|w| (w, true))
             .map_err(|e| e.to_string())
     } else {
         let fname = if !de.name.trim().is_empty() {
@@ -318,19 +1011,33 @@ fn save_entry(widgets: &EntryWidgets, state: &SharedState) -> Result<(PathBuf, b
             "desktop-entry".into()
         };
         DesktopWriter::write(&de, &fname, true)
-            .map(|p| (p, false))
+            .map(|w| (w, false))
             .map_err(|e| e.to_string())
     }?;
+    let path = written.path;
 
-    state.borrow_mut().is_dirty = false;
-    Ok(result)
+    {
+        let mut st = state.borrow_mut();
+        st.on_disk = FileStamp::read(&path).ok();
+        st.is_dirty = false;
+    }
+    let mut warnings: Vec<String> = written.warning.into_iter().collect();
+    match DesktopShortcutService::sync(&path) {
+        Ok((_, warning)) => warnings.extend(warning),
+        Err(e) => warnings.push(format!("its desktop copy was not updated: {}", e)),
+    }
+    Ok(Saved {
+        path,
+        updated,
+        warning: (!warnings.is_empty()).then(|| warnings.join("; ")),
+    })
 }
 
 pub fn do_save_as(
     widgets: &EntryWidgets,
     state: &SharedState,
     win: &impl IsA<gtk4::Window>,
-    on_success: impl Fn(PathBuf) + 'static,
+    on_success: impl Fn(Written) + 'static,
 ) {
     let de = match collect_entry(widgets) {
         Ok(de) => de,
@@ -369,11 +1076,12 @@ pub fn do_save_as(
                 path.set_extension("desktop");
             }
             match DesktopWriter::write_to_path(&de, &path) {
-                Ok(saved_path) => {
+                Ok(written) => {
                     let mut st = s.borrow_mut();
-                    st.selected_path = Some(saved_path.clone());
+                    st.on_disk = FileStamp::read(&written.path).ok();
+                    st.selected_path = Some(written.path.clone());
                     st.is_dirty = false;
-                    on_success(saved_path);
+                    on_success(written);
                 }
                 Err(e) => dialogs::show_error(d, &e.to_string()),
             }
@@ -384,10 +1092,75 @@ pub fn do_save_as(
 }
 
 pub fn do_save(widgets: &EntryWidgets, state: &SharedState, win: &impl IsA<gtk4::Window>) {
-    match save_entry(widgets, state) {
-        Ok((path, updated)) => dialogs::show_save_success(win, path, updated),
-        Err(e) => dialogs::show_error(win, &e),
-    }
+    let parent = win.clone().upcast::<gtk4::Window>();
+    save_checked(widgets, state, win, move |result| match result {
+        Ok(saved) => {
+            dialogs::show_save_success(&parent, saved.path, saved.updated, saved.warning.as_deref())
+        }
+        Err(e) => dialogs::show_error(&parent, &e),
+    });
+}
+
+// Saves the form, first asking what to do when the file was changed by
+// someone else since it was loaded.
+fn save_checked(
+    widgets: &EntryWidgets,
+    state: &SharedState,
+    win: &impl IsA<gtk4::Window>,
+    done: impl Fn(Result<Saved, String>) + Clone + 'static,
+) {
+    let conflict = {
+        let st = state.borrow();
+        match (&st.selected_path, &st.on_disk) {
+            (Some(path), Some(stamp)) if stamp.changed_on_disk(path) => {
+                Some((path.clone(), stamp.clone()))
+            }
+            _ => None,
+        }
+    };
+    let Some((path, base)) = conflict else {
+        done(save_entry(widgets, state));
+        return;
+    };
+    let (w, s, parent) = (
+        widgets.clone(),
+        state.clone(),
+        win.clone().upcast::<gtk4::Window>(),
+    );
+    dialogs::resolve_conflict(win, &path.clone(), move |choice| match choice {
+        ConflictChoice::Overwrite => done(save_entry(&w, &s)),
+        ConflictChoice::Reload => match FileStamp::read(&path) {
+            Ok(theirs) => {
+                set_form_from_entry(&w, &DesktopEntry::from_ini_string(&theirs.content));
+                let mut st = s.borrow_mut();
+                st.on_disk = Some(theirs);
+                st.is_dirty = false;
+            }
+            Err(e) => dialogs::show_error(&parent, &e.to_string()),
+        },
+        ConflictChoice::Merge => {
+            let theirs = match FileStamp::read(&path) {
+                Ok(theirs) => theirs,
+                Err(e) => {
+                    dialogs::show_error(&parent, &e.to_string());
+                    return;
+                }
+            };
+            let merge = merge_entries(
+                &DesktopEntry::from_ini_string(&base.content),
+                &read_form(&w),
+                &DesktopEntry::from_ini_string(&theirs.content),
+            );
+            let (w, s, done) = (w.clone(), s.clone(), done.clone());
+            MergeDialog::new(Some(&parent), &path, merge).run(move |merged| {
+                if let Some(merged) = merged {
+                    set_form_from_entry(&w, &merged);
+                    s.borrow_mut().on_disk = Some(theirs);
+                    done(save_entry(&w, &s));
+                }
+            });
+        }
+    });
 }
 
 pub fn do_preview(widgets: &EntryWidgets, win: &impl IsA<gtk4::Window>) {
@@ -400,33 +1173,94 @@ pub fn do_preview(widgets: &EntryWidgets, win: &impl IsA<gtk4::Window>) {
 pub fn do_delete(
     state: &SharedState,
     widgets: &EntryWidgets,
+    listbox: &gtk4::ListBox,
+    toasts: &adw::ToastOverlay,
     win: &impl IsA<gtk4::Window>,
-    status_label: &gtk4::Label,
-    refresh_list: impl Fn() + 'static,
+    refresh_list: impl Fn() + Clone + 'static,
 ) {
-    let maybe_path = state.borrow().selected_path.clone();
-    if let Some(path) = maybe_path {
-        let s = state.clone();
-        let w = widgets.clone();
-        let lbl = status_label.clone();
-        let win_clone = win.clone();
-        let path_clone = path.clone();
-        dialogs::confirm_delete(win, &path, move || {
-            if let Err(e) = std::fs::remove_file(&path_clone) {
-                dialogs::show_error(&win_clone, &format!("Failed to delete: {}", e));
-            } else {
-                set_form_from_entry(&w, &DesktopEntry::default());
-                w.type_combo.set_sensitive(true);
-                {
-                    let mut st = s.borrow_mut();
-                    st.selected_path = None;
-                    st.is_dirty = false;
-                }
-                refresh_list();
-                lbl.set_text("Deleted");
-            }
-        });
-    } else {
+    let mut paths: Vec<PathBuf> = listbox
+        .selected_rows()
+        .iter()
+        .map(|row| row.widget_name().to_string())
+        .filter(|name| name != ":unsaved")
+        .map(PathBuf::from)
+        .collect();
+    if paths.is_empty() {
+        paths.extend(state.borrow().selected_path.clone());
+    }
+    if paths.is_empty() {
         dialogs::show_error(win, "No file selected to delete");
+        return;
+    }
+
+    let mut trashed = Vec::new();
+    let mut failures = Vec::new();
+    for path in &paths {
+        match TrashService::delete(path) {
+            Ok(t) => trashed.push(t),
+            Err(e) => failures.push(e.to_string()),
+        }
+    }
+    let open = state.borrow().selected_path.clone();
+    if open.is_some_and(|p| trashed.iter().any(|t| t.original() == p)) {
+        set_form_from_entry(widgets, &DesktopEntry::default());
+        widgets.type_combo.set_sensitive(true);
+        let mut st = state.borrow_mut();
+        st.selected_path = None;
+        st.on_disk = None;
+        st.is_dirty = false;
+    }
+    refresh_list();
+    if !failures.is_empty() {
+        dialogs::show_error(win, &failures.join("\n"));
+    }
+    if trashed.is_empty() {
+        return;
     }
+
+    let title = match trashed.as_slice() {
+        [one] => format!("Moved {} to the trash", file_name_of(one.original())),
+        many => format!("Moved {} launchers to the trash", many.len()),
+    };
+    let toast = adw::Toast::builder()
+        .title(title.as_str())
+        .button_label("Undo")
+        .timeout(10)
+        .build();
+    let (s, lb, ts, parent) = (
+        state.clone(),
+        listbox.clone(),
+        toasts.clone(),
+        win.clone().upcast::<gtk4::Window>(),
+    );
+    toast.connect_button_clicked(move |_| {
+        let mut restored = Vec::new();
+        let mut failures = Vec::new();
+        for t in &trashed {
+            match TrashService::restore(t) {
+                Ok(path) => restored.push(path),
+                Err(e) => failures.push(e.to_string()),
+            }
+        }
+        refresh_list();
+        list_manager::select_paths(&lb, &restored);
+        // Reopen a restored launcher when the editor is free, not over edits.
+        let editor_free = {
+            let st = s.borrow();
+            st.selected_path.is_none() && !st.is_dirty && !st.in_edit
+        };
+        if editor_free && let Some(row) = lb.selected_rows().first() {
+            row.activate();
+        }
+        if !restored.is_empty() {
+            ts.add_toast(adw::Toast::new(&format!(
+                "Restored {} launcher(s)",
+                restored.len()
+            )));
+        }
+        if !failures.is_empty() {
+            dialogs::show_error(&parent, &failures.join("\n"));
+        }
+    });
+    toasts.add_toast(toast);
 }
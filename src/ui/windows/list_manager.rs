@@ -1,41 +1,237 @@
+use crate::domain::autostart::autostart_info;
+use crate::domain::locale::Locale;
+use crate::services::autostart_service::AutostartService;
 use crate::services::desktop_reader::DesktopReader;
+use crate::services::desktop_scanner::{DesktopScanner, ScannedEntry};
+use crate::services::desktop_shortcut_service::DesktopShortcutService;
+use crate::services::file_stamp::FileStamp;
+use crate::services::xdg;
 use crate::ui::editor::entry_form::{EntryWidgets, set_form_from_entry};
 use crate::ui::state::SharedState;
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Image, Label, ListBox, ListBoxRow, Orientation};
+use gtk4::{Box as GtkBox, Image, Label, ListBox, ListBoxRow, Orientation, Switch, glib};
 use std::path::{Path, PathBuf};
+
+const AUTOSTART_HEADER: &str = ":autostart";
+const DESKTOP_HEADER: &str = ":desktop";
 pub fn refresh_desktop_list(
     listbox: &ListBox,
     state: &SharedState,
     status_label: &Label,
     ensure_temp_row: &dyn Fn(),
 ) {
+    // The unsaved row survives refreshes; it is moved back to the end below.
+    let temp_row = state.borrow().temp_row.clone();
     while let Some(child) = listbox.first_child() {
         listbox.remove(&child);
     }
-    match DesktopReader::list_desktop_files() {
-        Ok(paths) => {
-            for path in paths {
-                let (name, icon_str) = match DesktopReader::read_from_path(&path) {
-                    Ok(de) => (de.name, de.icon),
-                    Err(_) => (
-                        path.file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("")
-                            .to_string(),
-                        None,
-                    ),
-                };
-                let row = create_list_row(&name, icon_str.as_deref(), &path);
-                listbox.append(&row);
+    let locale = Locale::current();
+    let display = |path: &Path| row_display(path, locale.as_ref());
+    let mut items: Vec<(String, Option<String>, bool, ScannedEntry)> = DesktopScanner::scan()
+        .into_iter()
+        .map(|entry| {
+            let (name, icon, hidden) = display(&entry.active.path);
+            (name, icon, hidden, entry)
+        })
+        .collect();
+    items.sort_by_key(|(name, _, _, _)| name.to_lowercase());
+    let shadowing = items
+        .iter()
+        .filter(|(_, _, _, e)| !e.shadowed.is_empty())
+        .count();
+    for (name, icon, hidden, entry) in &items {
+        let row = create_list_row(name, icon.as_deref(), &entry.active.path);
+        let origin = entry.active.origin.label();
+        let caption = if *hidden {
+            format!("{}, hidden", origin)
+        } else {
+            origin.to_string()
+        };
+        decorate_row(&row, &caption, &scanned_tooltip(entry));
+        listbox.append(&row);
+        // Shadowed copies stay listed, dimmed, so the hidden originals can be
+        // opened and compared.
+        for file in &entry.shadowed {
+            let row = create_list_row(name, icon.as_deref(), &file.path);
+            decorate_row(
+                &row,
+                &format!("{}, shadowed", file.origin.label()),
+                &format!(
+                    "{}\nShadowed by {}",
+                    file.path.display(),
+                    entry.active.path.display()
+                ),
+            );
+            row.add_css_class("dim-label");
+            if let Some(hb) = row.child() {
+                hb.set_margin_start(18);
             }
-            status_label.set_text("List refreshed");
-            if state.borrow().in_edit {
-                ensure_temp_row();
+            listbox.append(&row);
+        }
+    }
+    let autostart = append_autostart_section(listbox, locale.as_ref());
+    let on_desktop = append_desktop_section(listbox, locale.as_ref());
+    status_label.set_text(&format!(
+        "{} launchers, {} shadowing another file, {} login items, {} on the desktop",
+        items.len(),
+        shadowing,
+        autostart,
+        on_desktop
+    ));
+    let selected = state.borrow().selected_path.clone();
+    match temp_row {
+        Some(row) => {
+            listbox.insert(&row, applications_end(listbox));
+            listbox.select_row(Some(&row));
+        }
+        None if state.borrow().in_edit => ensure_temp_row(),
+        None => select_paths(listbox, selected.as_slice()),
+    }
+}
+/// Selects the rows of `paths`, in addition to the current selection.
+pub fn select_paths(listbox: &ListBox, paths: &[PathBuf]) {
+    let mut child = listbox.first_child();
+    while let Some(widget) = child {
+        if paths
+            .iter()
+            .any(|p| p.to_string_lossy() == widget.widget_name().as_str())
+        {
+            listbox.select_row(widget.downcast_ref::<ListBoxRow>());
+        }
+        child = widget.next_sibling();
+    }
+}
+// Name, icon and `Hidden` of the entry at `path`, falling back to the file
+// name when it cannot be read.
+fn row_display(path: &Path, locale: Option<&Locale>) -> (String, Option<String>, bool) {
+    match DesktopReader::read_from_path(path) {
+        Ok(de) => (
+            de.localized_name(locale).to_string(),
+            de.icon.clone(),
+            de.hidden,
+        ),
+        Err(_) => (
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("")
+                .to_string(),
+            None,
+            false,
+        ),
+    }
+}
+// Login items follow the launchers under their own header, each with a
+// switch that turns it on or off through `app.set_autostart`.
+fn append_autostart_section(listbox: &ListBox, locale: Option<&Locale>) -> usize {
+    listbox.append(&section_header("Autostart", AUTOSTART_HEADER));
+    let desktops = xdg::current_desktops();
+    let entries = AutostartService::scan();
+    for entry in &entries {
+        let path = &entry.active.path;
+        let (name, icon, _) = row_display(path, locale);
+        let info = DesktopReader::read_from_path(path)
+            .map(|de| autostart_info(&de, &desktops))
+            .ok();
+        let row = create_list_row(&name, icon.as_deref(), path);
+        let mut caption = vec![entry.active.origin.label().to_string()];
+        if let Some(info) = &info {
+            if !info.enabled {
+                caption.push("off".into());
+            }
+            if let Some(delay) = info.delay {
+                caption.push(format!("after {} s", delay));
             }
+            if !info.runs_here {
+                caption.push("not in this desktop".into());
+            }
+        }
+        decorate_row(&row, &caption.join(", "), &scanned_tooltip(entry));
+        if let Some(hb) = row.child().and_downcast::<GtkBox>() {
+            let switch = Switch::new();
+            switch.set_valign(gtk4::Align::Center);
+            switch.set_active(info.as_ref().is_some_and(|i| i.enabled));
+            switch.set_tooltip_text(Some("Start at login"));
+            let path = path.to_string_lossy().to_string();
+            switch.connect_state_set(move |sw, enabled| {
+                let _ = sw.activate_action(
+                    "app.set_autostart",
+                    Some(&(path.as_str(), enabled).to_variant()),
+                );
+                glib::Propagation::Proceed
+            });
+            hb.append(&switch);
         }
-        Err(e) => status_label.set_text(&format!("Failed to list: {}", e)),
+        listbox.append(&row);
     }
+    entries.len()
+}
+// Launcher files on the desktop, captioned with the launcher each was copied
+// from and whether the file manager trusts it.
+fn append_desktop_section(listbox: &ListBox, locale: Option<&Locale>) -> usize {
+    listbox.append(&section_header("Desktop", DESKTOP_HEADER));
+    let shortcuts = DesktopShortcutService::list();
+    for shortcut in &shortcuts {
+        let (name, icon, _) = row_display(&shortcut.path, locale);
+        let row = create_list_row(&name, icon.as_deref(), &shortcut.path);
+        let mut caption = vec!["Desktop".to_string()];
+        let mut tooltip = shortcut.path.display().to_string();
+        if let Some(source) = &shortcut.source {
+            tooltip.push_str(&format!("\nCopy of {}", source.display()));
+            if !source.exists() {
+                caption.push("launcher gone".into());
+            }
+        }
+        if !shortcut.trusted {
+            caption.push("not trusted".into());
+        }
+        decorate_row(&row, &caption.join(", "), &tooltip);
+        listbox.append(&row);
+    }
+    shortcuts.len()
+}
+fn section_header(title: &str, name: &str) -> ListBoxRow {
+    let header = ListBoxRow::new();
+    let lbl = Label::new(Some(title));
+    lbl.set_xalign(0.0);
+    lbl.add_css_class("heading");
+    lbl.set_margin_top(12);
+    header.set_child(Some(&lbl));
+    header.set_selectable(false);
+    header.set_activatable(false);
+    header.set_widget_name(name);
+    header
+}
+// Where launcher rows end: the position of the autostart header.
+fn applications_end(listbox: &ListBox) -> i32 {
+    let mut idx = 0;
+    let mut child = listbox.first_child();
+    while let Some(widget) = child {
+        if widget.widget_name() == AUTOSTART_HEADER {
+            return idx;
+        }
+        idx += 1;
+        child = widget.next_sibling();
+    }
+    -1
+}
+fn scanned_tooltip(entry: &ScannedEntry) -> String {
+    let mut text = format!("{}\n{}", entry.id, entry.active.path.display());
+    for file in &entry.shadowed {
+        text.push_str(&format!("\nShadows {}", file.path.display()));
+    }
+    text
+}
+// Appends the origin as a caption and sets the tooltip.
+fn decorate_row(row: &ListBoxRow, caption: &str, tooltip: &str) {
+    if let Some(hb) = row.child().and_downcast::<GtkBox>() {
+        let lbl = Label::new(Some(caption));
+        lbl.set_hexpand(true);
+        lbl.set_xalign(1.0);
+        lbl.add_css_class("dim-label");
+        lbl.add_css_class("caption");
+        hb.append(&lbl);
+    }
+    row.set_tooltip_text(Some(tooltip));
 }
 pub fn create_list_row(name: &str, icon: Option<&str>, path: &Path) -> ListBoxRow {
     let row = ListBoxRow::new();
@@ -93,7 +289,7 @@ pub fn create_temp_row(
     row.set_sensitive(false);
     row.add_css_class("activatable");
     row.set_widget_name(":unsaved");
-    listbox.append(&row);
+    listbox.insert(&row, applications_end(listbox));
     listbox.select_row(Some(&row));
     state.borrow_mut().temp_row = Some(row);
 }
@@ -121,8 +317,35 @@ pub fn on_row_activated(
             widgets.type_combo.set_sensitive(false);
             let mut st = state.borrow_mut();
             st.selected_path = Some(path.clone());
+            st.on_disk = FileStamp::read(&path).ok();
             st.is_dirty = false;
-            status_label.set_text(&path.to_string_lossy());
+            let user_dir = DesktopReader::user_applications_dir();
+            let autostart_dir = AutostartService::user_dir().ok();
+            let source = DesktopShortcutService::list()
+                .into_iter()
+                .find(|s| s.path == path)
+                .and_then(|s| s.source);
+            if let Some(source) = source {
+                status_label.set_text(&format!(
+                    "{} is a copy of {}; changes to that launcher replace it",
+                    path.display(),
+                    source.display()
+                ));
+            } else if user_dir.is_some_and(|d| path.starts_with(d))
+                || autostart_dir.is_some_and(|d| path.starts_with(d))
+            {
+                status_label.set_text(&path.to_string_lossy());
+            } else if AutostartService::is_autostart(&path) {
+                status_label.set_text(&format!(
+                    "{} is read-only, use its switch or Launcher > Autostart Delay to override it",
+                    path.display()
+                ));
+            } else {
+                status_label.set_text(&format!(
+                    "{} is read-only, use Launcher > Customize System Launcher to edit a copy",
+                    path.display()
+                ));
+            }
         }
         Err(e) => {
             status_label.set_text(&format!("Open failed: {}", e));
@@ -1,11 +1,16 @@
+use crate::services::autostart_service::AutostartService;
+use crate::services::desktop_scanner::DesktopScanner;
+use crate::services::desktop_shortcut_service::DesktopShortcutService;
+use crate::services::dir_watcher::DirWatcher;
 use crate::ui::editor::entry_form::{self};
 use crate::ui::state;
 use crate::ui::theme;
 use crate::ui::windows::{actions, list_manager};
 use adw::{
-    ApplicationWindow as AdwApplicationWindow, HeaderBar as AdwHeaderBar, ToolbarView, prelude::*,
+    ApplicationWindow as AdwApplicationWindow, HeaderBar as AdwHeaderBar, ToastOverlay,
+    ToolbarView, prelude::*,
 };
-use gtk4::{Align, Application, Box as GtkBox, Button, Orientation, ScrolledWindow};
+use gtk4::{Align, Application, Box as GtkBox, Button, Orientation, ScrolledWindow, glib};
 use std::rc::Rc;
 pub fn show_main_window(app: &impl IsA<Application>) {
     let app: Application = app.upcast_ref::<Application>().clone();
@@ -35,6 +40,9 @@ pub fn show_main_window(app: &impl IsA<Application>) {
         .vexpand(true)
         .build();
     scroller.set_child(Some(&editor.notebook));
+    let editor_column = GtkBox::new(Orientation::Vertical, 6);
+    editor_column.append(&scroller);
+    editor_column.append(&editor.diagnostics.container);
     {
         let st = state.clone();
         entry_form::wire_source_sync(&editor, move || {
@@ -45,7 +53,7 @@ pub fn show_main_window(app: &impl IsA<Application>) {
     let buttons = build_action_buttons();
     let main_area = GtkBox::new(Orientation::Horizontal, 12);
     main_area.append(&sidebar_data.container);
-    main_area.append(&scroller);
+    main_area.append(&editor_column);
     root.append(&menubar);
     root.append(&toolbar_data.container);
     root.append(&main_area);
@@ -53,7 +61,9 @@ pub fn show_main_window(app: &impl IsA<Application>) {
     root.append(&status_data.container);
     let toolbar_view = ToolbarView::new();
     toolbar_view.add_top_bar(&header);
-    toolbar_view.set_content(Some(&root));
+    let toasts = ToastOverlay::new();
+    toasts.set_child(Some(&root));
+    toolbar_view.set_content(Some(&toasts));
     win.set_content(Some(&toolbar_view));
     let widgets = editor.widgets.clone();
     let listbox = sidebar_data.listbox.clone();
@@ -93,13 +103,51 @@ pub fn show_main_window(app: &impl IsA<Application>) {
         &buttons,
         &widgets,
         state.clone(),
+        &listbox,
+        &toasts,
         &win,
-        &status_label,
         refresh_list.clone(),
     );
     refresh_list();
+    watch_applications_dirs(
+        &win,
+        &widgets,
+        state.clone(),
+        &status_label,
+        &toasts,
+        refresh_list,
+    );
     win.present();
 }
+// Keeps the sidebar in sync with launchers, login items and desktop copies
+// added, changed or removed by other programs, and desktop copies in sync
+// with their launchers.
+fn watch_applications_dirs(
+    win: &AdwApplicationWindow,
+    widgets: &entry_form::EntryWidgets,
+    state: state::SharedState,
+    status_label: &gtk4::Label,
+    toasts: &ToastOverlay,
+    refresh_list: Rc<dyn Fn()>,
+) {
+    let (w, wn, sl) = (widgets.clone(), win.clone(), status_label.clone());
+    let ts = toasts.clone();
+    let watcher = DirWatcher::new(move |changed| {
+        actions::sync_desktop_copies(&changed, &ts);
+        refresh_list();
+        actions::reload_if_changed(&changed, &w, &state, &wn, &sl);
+    });
+    let mut dirs = DesktopScanner::applications_dirs();
+    dirs.extend(AutostartService::dirs());
+    // The desktop is often the home directory itself; only its top level
+    // holds launchers.
+    let desktop = DesktopShortcutService::dir().into_iter().collect();
+    watcher.watch(dirs, desktop);
+    win.connect_close_request(move |_| {
+        watcher.stop();
+        glib::Propagation::Proceed
+    });
+}
 fn setup_css() {
     // Intentionally left empty: use GNOME default design system (Adwaita).
 }
@@ -115,9 +163,13 @@ fn build_action_buttons() -> ActionButtons {
     let delete = Button::with_label("Delete");
     delete.add_css_class("destructive-action");
     let preview = Button::with_label("Preview");
+    let run = Button::with_label("Run");
+    // Runs through the action so the menu item and the button share it.
+    run.set_action_name(Some("app.run_launcher"));
     let save = Button::with_label("Save .desktop");
     container.append(&delete);
     container.append(&preview);
+    container.append(&run);
     container.append(&save);
     ActionButtons {
         container,
@@ -164,17 +216,18 @@ fn connect_action_buttons(
     buttons: &ActionButtons,
     widgets: &entry_form::EntryWidgets,
     state: state::SharedState,
+    listbox: &gtk4::ListBox,
+    toasts: &ToastOverlay,
     win: &AdwApplicationWindow,
-    status_label: &gtk4::Label,
     refresh_list: Rc<dyn Fn()>,
 ) {
     let w = widgets.clone();
     let s = state.clone();
     let wn = win.clone();
-    let sl = status_label.clone();
     let rl = refresh_list.clone();
+    let (lb, ts) = (listbox.clone(), toasts.clone());
     buttons.delete.connect_clicked(move |_| {
-        actions::do_delete(&s, &w, &wn, &sl, {
+        actions::do_delete(&s, &w, &lb, &ts, &wn, {
             let r = rl.clone();
             move || r()
         });